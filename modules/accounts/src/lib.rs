@@ -17,7 +17,8 @@ use frame_support::{
 		WithdrawReasons,
 	},
 	weights::{
-		DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo, Weight, WeightToFeeCoefficient, WeightToFeePolynomial,
+		DispatchClass, DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo, Weight, WeightToFeeCoefficient,
+		WeightToFeePolynomial,
 	},
 	StorageMap,
 };
@@ -464,11 +465,25 @@ impl<T: Trait> OnReceived<T::AccountId, CurrencyId, Balance> for Module<T> {
 
 		if !<Self as StoredMap<_, _>>::is_explicit(who) && currency_id != native_currency_id {
 			let stable_currency_id = T::StableCurrencyId::get();
-			let trading_path = if currency_id == stable_currency_id {
-				vec![stable_currency_id, native_currency_id]
-			} else {
-				vec![currency_id, stable_currency_id, native_currency_id]
-			};
+			let price_impact_limit = Some(T::MaxSlippageSwapWithDEX::get());
+			// the trading path search is not covered by the weight of the dispatch
+			<system::Module<T>>::register_extra_weight_unchecked(
+				T::DEX::get_best_path_weight(),
+				DispatchClass::Mandatory,
+			);
+			let trading_path = T::DEX::get_best_path_with_exact_target(
+				currency_id,
+				native_currency_id,
+				T::NewAccountDeposit::get(),
+				price_impact_limit,
+			)
+			.unwrap_or_else(|| {
+				if currency_id == stable_currency_id {
+					vec![stable_currency_id, native_currency_id]
+				} else {
+					vec![currency_id, stable_currency_id, native_currency_id]
+				}
+			});
 
 			// Successful swap will cause changes in native currency,
 			// which also means that it will open a new account
@@ -484,7 +499,7 @@ impl<T: Trait> OnReceived<T::AccountId, CurrencyId, Balance> for Module<T> {
 				&trading_path,
 				T::NewAccountDeposit::get(),
 				<T as Trait>::MultiCurrency::free_balance(currency_id, who),
-				Some(T::MaxSlippageSwapWithDEX::get()),
+				price_impact_limit,
 			);
 		}
 	}
@@ -770,11 +785,24 @@ where
 
 			// iterator non-native currencies to get enough fee
 			for currency_id in other_currency_ids {
-				let trading_path = if currency_id == stable_currency_id {
-					vec![stable_currency_id, native_currency_id]
-				} else {
-					vec![currency_id, stable_currency_id, native_currency_id]
-				};
+				// the trading path search is not covered by the weight of the extrinsic
+				<system::Module<T>>::register_extra_weight_unchecked(
+					T::DEX::get_best_path_weight(),
+					DispatchClass::Mandatory,
+				);
+				let trading_path = T::DEX::get_best_path_with_exact_target(
+					currency_id,
+					native_currency_id,
+					balance_fee,
+					price_impact_limit,
+				)
+				.unwrap_or_else(|| {
+					if currency_id == stable_currency_id {
+						vec![stable_currency_id, native_currency_id]
+					} else {
+						vec![currency_id, stable_currency_id, native_currency_id]
+					}
+				});
 
 				if T::DEX::swap_with_exact_target(
 					who,
@@ -7,10 +7,15 @@ use frame_support::{
 	impl_outer_dispatch, impl_outer_event, impl_outer_origin, ord_parameter_types, parameter_types,
 	weights::WeightToFeeCoefficients,
 };
+use frame_system::{offchain::SendTransactionTypes, EnsureSignedBy};
 use primitives::{Amount, TokenSymbol, TradingPair};
 use smallvec::smallvec;
 use sp_core::H256;
-use sp_runtime::{testing::Header, traits::IdentityLookup, FixedPointNumber, Perbill};
+use sp_runtime::{
+	testing::{Header, TestXt},
+	traits::IdentityLookup,
+	FixedPointNumber, Perbill,
+};
 use sp_std::cell::RefCell;
 use support::Ratio;
 
@@ -36,6 +41,7 @@ impl_outer_dispatch! {
 		orml_currencies::Currencies,
 		pallet_balances::PalletBalances,
 		frame_system::System,
+		dex::DEXModule,
 	}
 }
 
@@ -137,21 +143,49 @@ parameter_types! {
 	pub const DEXModuleId: ModuleId = ModuleId(*b"aca/dexm");
 	pub const GetExchangeFee: (u32, u32) = (0, 100);
 	pub const TradingPathLimit: usize = 3;
+	pub const TradingPathCandidatesLimit: usize = 3;
+	pub const TWAPWindow: BlockNumber = 10;
+	pub const LimitOrderDeposit: Balance = 1;
+	pub const MaxLimitOrdersPerAccount: u32 = 10;
+	pub const UnsignedPriority: u64 = 1 << 20;
+	pub const ProtocolFeeReceiver: AccountId = 10;
 	pub EnabledTradingPairs : Vec<TradingPair> = vec![TradingPair::new(AUSD, ACA), TradingPair::new(AUSD, BTC)];
 }
 
 impl dex::Trait for Runtime {
 	type Event = TestEvent;
 	type Currency = Currencies;
-	type EnabledTradingPairs = EnabledTradingPairs;
 	type GetExchangeFee = GetExchangeFee;
 	type TradingPathLimit = TradingPathLimit;
+	type TradingPathCandidatesLimit = TradingPathCandidatesLimit;
 	type ModuleId = DEXModuleId;
 	type DEXIncentives = ();
 	type WeightInfo = ();
+	type UpdateOrigin = EnsureSignedBy<Zero, AccountId>;
+	type ProtocolFeeReceiver = ProtocolFeeReceiver;
+	type TWAPWindow = TWAPWindow;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type LimitOrderDeposit = LimitOrderDeposit;
+	type MaxLimitOrdersPerAccount = MaxLimitOrdersPerAccount;
+	type UnsignedPriority = UnsignedPriority;
+	type Call = Call;
+	type LegacyEnabledTradingPairs = EnabledTradingPairs;
+	type StableSwapRateProvider = ();
+	type FlashSwapCallFilter = ();
 }
 pub type DEXModule = dex::Module<Runtime>;
 
+/// An extrinsic type used for tests.
+pub type Extrinsic = TestXt<Call, ()>;
+
+impl<LocalCall> SendTransactionTypes<LocalCall> for Runtime
+where
+	Call: From<LocalCall>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = Extrinsic;
+}
+
 parameter_types! {
 	pub AllNonNativeCurrencyIds: Vec<CurrencyId> = vec![AUSD, BTC];
 	pub const NewAccountDeposit: Balance = 100;
@@ -268,6 +302,12 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		dex::GenesisConfig {
+			initial_enabled_trading_pairs: EnabledTradingPairs::get(),
+		}
+		.assimilate_storage::<Runtime>(&mut t)
+		.unwrap();
+
 		t.into()
 	}
 }
@@ -0,0 +1,112 @@
+//! Runtime API definition for auction manager module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Codec, Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sp_runtime::traits::{MaybeDisplay, MaybeFromStr};
+use sp_std::prelude::*;
+use support::Price;
+
+/// The type of an auction.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum AuctionType {
+	Collateral,
+	DutchCollateral,
+	Debit,
+	Surplus,
+}
+
+/// The stage of an auction.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum AuctionStage {
+	/// Bidders compete on the stable currency amount to pay.
+	Forward,
+	/// Bidders compete on the amount to receive for a fixed payment.
+	Reverse,
+	/// The price descends over time, and anyone can take at the current
+	/// price.
+	Descending,
+}
+
+/// An active auction together with its bid state.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ActiveAuction<AccountId, AuctionId, CurrencyId, Balance, BlockNumber> {
+	/// The id of the auction.
+	pub auction_id: AuctionId,
+	/// The type of the auction.
+	pub auction_type: AuctionType,
+	/// The currency for sale.
+	pub currency_id: CurrencyId,
+	/// The stage of the auction.
+	pub stage: AuctionStage,
+	/// The last bidder, `None` if there's no bid yet.
+	pub last_bidder: Option<AccountId>,
+	/// The last bid price, zero if there's no bid yet.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub last_bid_price: Balance,
+	/// The minimum bid price of the next valid bid, zero for dutch collateral
+	/// auction which can only be taken.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub minimum_next_bid: Balance,
+	/// The current price of dutch collateral auction.
+	pub current_price: Option<Price>,
+	/// The end time of the auction. For dutch collateral auction, it's the
+	/// time after which the auction can be reset.
+	pub end_time: Option<BlockNumber>,
+	/// The blocks left until `end_time`.
+	pub time_left: Option<BlockNumber>,
+	/// The amount for sale under the current bid.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub amount: Balance,
+	/// The amount to be paid under the current bid. For dutch collateral
+	/// auction, it's the payment to take all collateral at the current price.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub payment: Balance,
+}
+
+#[cfg(feature = "std")]
+fn serialize_as_string<S: Serializer, T: std::fmt::Display>(t: &T, serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_str(&t.to_string())
+}
+
+#[cfg(feature = "std")]
+fn deserialize_from_string<'de, D: Deserializer<'de>, T: std::str::FromStr>(deserializer: D) -> Result<T, D::Error> {
+	let s = String::deserialize(deserializer)?;
+	s.parse::<T>()
+		.map_err(|_| serde::de::Error::custom("Parse from string failed"))
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait AuctionManagerApi<AccountId, AuctionId, CurrencyId, Balance, BlockNumber> where
+		AccountId: Codec,
+		AuctionId: Codec,
+		CurrencyId: Codec,
+		Balance: Codec + MaybeDisplay + MaybeFromStr,
+		BlockNumber: Codec,
+	{
+		fn get_active_auctions() -> Vec<ActiveAuction<AccountId, AuctionId, CurrencyId, Balance, BlockNumber>>;
+	}
+}
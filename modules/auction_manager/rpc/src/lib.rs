@@ -0,0 +1,84 @@
+//! RPC interface for the auction manager module.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use module_auction_manager_rpc_runtime_api::ActiveAuction;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
+};
+use std::sync::Arc;
+
+pub use self::gen_client::Client as AuctionManagerClient;
+pub use module_auction_manager_rpc_runtime_api::AuctionManagerApi as AuctionManagerRuntimeApi;
+
+#[rpc]
+pub trait AuctionManagerApi<BlockHash, AccountId, AuctionId, CurrencyId, Balance, BlockNumber> {
+	#[rpc(name = "auctionManager_getActiveAuctions")]
+	fn get_active_auctions(
+		&self,
+		at: Option<BlockHash>,
+	) -> Result<Vec<ActiveAuction<AccountId, AuctionId, CurrencyId, Balance, BlockNumber>>>;
+}
+
+/// A struct that implements the [`AuctionManagerApi`].
+pub struct AuctionManager<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> AuctionManager<C, B> {
+	/// Create new `AuctionManager` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		AuctionManager {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AccountId, AuctionId, CurrencyId, Balance, BlockNumber>
+	AuctionManagerApi<<Block as BlockT>::Hash, AccountId, AuctionId, CurrencyId, Balance, BlockNumber>
+	for AuctionManager<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: AuctionManagerRuntimeApi<Block, AccountId, AuctionId, CurrencyId, Balance, BlockNumber>,
+	AccountId: Codec,
+	AuctionId: Codec,
+	CurrencyId: Codec,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+	BlockNumber: Codec,
+{
+	fn get_active_auctions(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<ActiveAuction<AccountId, AuctionId, CurrencyId, Balance, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.get_active_auctions(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get active auctions.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+}
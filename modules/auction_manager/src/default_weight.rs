@@ -21,4 +21,26 @@ impl crate::WeightInfo for () {
 			.saturating_add(DbWeight::get().reads(9 as Weight))
 			.saturating_add(DbWeight::get().writes(7 as Weight))
 	}
+	fn take() -> Weight {
+		(322_518_000 as Weight)
+			.saturating_add(DbWeight::get().reads(11 as Weight))
+			.saturating_add(DbWeight::get().writes(9 as Weight))
+	}
+	fn reset() -> Weight {
+		(97_364_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn batch_bid(c: u32) -> Weight {
+		(39_682_000 as Weight)
+			.saturating_add((296_417_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().reads((10 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes((8 as Weight).saturating_mul(c as Weight)))
+	}
+	fn partial_bid() -> Weight {
+		(412_853_000 as Weight)
+			.saturating_add(DbWeight::get().reads(14 as Weight))
+			.saturating_add(DbWeight::get().writes(11 as Weight))
+	}
 }
@@ -6,10 +6,21 @@
 //! business. Auction types include:
 //!   - `collateral auction`: sell collateral assets for getting stable currency
 //!     to eliminate the system's bad debit by auction
+//!   - `dutch collateral auction`: sell collateral assets at a descending
+//!     price, which can be taken partially at any time
 //!   - `surplus auction`: sell excessive surplus for getting native coin to
 //!     burn by auction
 //!   - `debit auction`: inflation some native token to sell for getting stable
 //!     coin to eliminate excessive bad debit by auction
+//!
+//! ## Offchain bidding worker
+//!
+//! The optional offchain bidding worker bids on the active collateral
+//! auctions with the local keystore account of `AUCTION_BIDDER_KEY_TYPE`,
+//! when the discount of the collateral versus `PriceSource` is large enough.
+//! It is disabled unless a SCALE encoded `BiddingConfig` is set in the
+//! persistent offchain local storage under the key
+//! `acala/auction-manager/bidding-config/`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -18,31 +29,38 @@ use frame_support::{
 	debug, decl_error, decl_event, decl_module, decl_storage, ensure,
 	traits::Get,
 	weights::{DispatchClass, Weight},
+	IterableStorageMap,
 };
 use frame_system::{
-	self as system, ensure_none,
-	offchain::{SendTransactionTypes, SubmitTransaction},
+	self as system, ensure_none, ensure_signed,
+	offchain::{
+		AppCrypto, CreateSignedTransaction, SendSignedTransaction, SendTransactionTypes, Signer, SubmitTransaction,
+	},
 };
 use orml_traits::{Auction, AuctionHandler, Change, MultiCurrency, OnNewBidResult};
 use orml_utilities::{with_transaction_result, IterableStorageMapExtended, OffchainErr};
 use primitives::{AuctionId, Balance, CurrencyId};
+use sp_core::U256;
 use sp_runtime::{
 	offchain::{
 		storage::StorageValueRef,
 		storage_lock::{StorageLock, Time},
 		Duration,
 	},
-	traits::{BlakeTwo256, CheckedDiv, Hash, Saturating, Zero},
+	traits::{BlakeTwo256, CheckedDiv, Hash, IdentifyAccount, One, SaturatedConversion, Saturating, Zero},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
-	DispatchError, DispatchResult, FixedPointNumber, RandomNumberGenerator, RuntimeDebug,
+	DispatchError, DispatchResult, FixedPointNumber, KeyTypeId, RandomNumberGenerator, RuntimeAppPublic, RuntimeDebug,
 };
 use sp_std::{
 	cmp::{Eq, PartialEq},
+	convert::TryInto,
 	prelude::*,
 };
-use support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, PriceProvider, Rate};
+use support::{
+	AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, Price, PriceProvider, Rate,
+};
 
 mod default_weight;
 mod mock;
@@ -52,14 +70,43 @@ pub trait WeightInfo {
 	fn cancel_surplus_auction() -> Weight;
 	fn cancel_debit_auction() -> Weight;
 	fn cancel_collateral_auction() -> Weight;
+	fn take() -> Weight;
+	fn reset() -> Weight;
+	fn batch_bid(c: u32) -> Weight;
+	fn partial_bid() -> Weight;
 }
 
 const OFFCHAIN_WORKER_DATA: &[u8] = b"acala/auction-manager/data/";
 const OFFCHAIN_WORKER_LOCK: &[u8] = b"acala/auction-manager/lock/";
 const OFFCHAIN_WORKER_MAX_ITERATIONS: &[u8] = b"acala/auction-manager/max-iterations/";
+const OFFCHAIN_BIDDING_CONFIG: &[u8] = b"acala/auction-manager/bidding-config/";
+const OFFCHAIN_BIDDING_LOCK: &[u8] = b"acala/auction-manager/bidding-lock/";
 const LOCK_DURATION: u64 = 100;
 const DEFAULT_MAX_ITERATIONS: u32 = 1000;
 
+/// The key type of the offchain bidding worker account
+pub const AUCTION_BIDDER_KEY_TYPE: KeyTypeId = KeyTypeId(*b"acab");
+
+pub mod crypto {
+	use super::AUCTION_BIDDER_KEY_TYPE;
+	use sp_core::sr25519::{Public as Sr25519Public, Signature as Sr25519Signature};
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		MultiSignature, MultiSigner,
+	};
+
+	app_crypto!(sr25519, AUCTION_BIDDER_KEY_TYPE);
+
+	/// The identifier of the offchain bidding worker account
+	pub struct BidderAuthorityId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for BidderAuthorityId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = Sr25519Signature;
+		type GenericPublic = Sr25519Public;
+	}
+}
+
 /// Information of an collateral auction
 #[cfg_attr(feature = "std", derive(PartialEq, Eq))]
 #[derive(Encode, Decode, Clone, RuntimeDebug)]
@@ -159,7 +206,121 @@ pub struct SurplusAuctionItem<BlockNumber> {
 	start_time: BlockNumber,
 }
 
-pub trait Trait: SendTransactionTypes<Call<Self>> + system::Trait {
+/// Information of a dutch collateral auction
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+#[derive(Encode, Decode, Clone, RuntimeDebug)]
+pub struct DutchCollateralAuctionItem<AccountId, BlockNumber> {
+	/// Refund recipient for may receive refund
+	refund_recipient: AccountId,
+	/// Collateral type for sale
+	currency_id: CurrencyId,
+	/// Initial collateral amount for sale
+	#[codec(compact)]
+	initial_amount: Balance,
+	/// Current collateral amount for sale
+	#[codec(compact)]
+	amount: Balance,
+	/// Remaining target sales amount of this auction
+	/// if zero, all the collateral is for sale
+	#[codec(compact)]
+	target: Balance,
+	/// The price of collateral in stable currency when the price starts to
+	/// decay
+	start_price: Price,
+	/// The time when the price starts to decay, updated on reset
+	start_time: BlockNumber,
+}
+
+impl<AccountId, BlockNumber> DutchCollateralAuctionItem<AccountId, BlockNumber> {
+	/// Return the collateral amount to sell and the stable currency amount to
+	/// pay when taking at most `max_amount` collateral at `price`
+	fn take_amount(&self, price: Price, max_amount: Balance) -> (Balance, Balance) {
+		let collateral_amount = sp_std::cmp::min(self.amount, max_amount);
+		let payment = price.saturating_mul_int(collateral_amount);
+
+		if !self.target.is_zero() && payment > self.target {
+			// only sell the collateral which is enough to raise the remaining target
+			let collateral_amount = Price::checked_from_rational(self.target, payment)
+				.and_then(|n| n.checked_mul_int(collateral_amount))
+				.unwrap_or(collateral_amount);
+			(collateral_amount, self.target)
+		} else {
+			(collateral_amount, payment)
+		}
+	}
+}
+
+/// The price decay curve of dutch collateral auction
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum PriceDecayCurve<BlockNumber> {
+	/// The price decreases linearly from the start price, and reaches zero
+	/// after the duration. \[duration\]
+	Linear(BlockNumber),
+	/// The price is multiplied by the cut rate once every step blocks.
+	/// \[step, cut\]
+	StairstepExponential(BlockNumber, Rate),
+}
+
+/// The config of the offchain bidding worker
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct BiddingConfig {
+	/// Only bid when the discount of the collateral under the bid versus the
+	/// price of `PriceSource` is at least this rate
+	pub min_discount: Rate,
+	/// The max payment of stable currency of the bid on one auction
+	#[codec(compact)]
+	pub max_payment: Balance,
+}
+
+/// The type of an active auction
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum AuctionType {
+	Collateral,
+	DutchCollateral,
+	Debit,
+	Surplus,
+}
+
+/// The stage of an active auction
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum AuctionStage {
+	/// Bidders compete on the stable currency amount to pay
+	Forward,
+	/// Bidders compete on the amount to receive for a fixed payment
+	Reverse,
+	/// The price descends over time, and anyone can take at the current price
+	Descending,
+}
+
+/// The bid state of an active auction
+#[derive(Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct AuctionBidState<AccountId, BlockNumber> {
+	/// The type of the auction
+	pub auction_type: AuctionType,
+	/// The currency for sale
+	pub currency_id: CurrencyId,
+	/// The stage of the auction
+	pub stage: AuctionStage,
+	/// The last bidder and bid price, if any
+	pub last_bid: Option<(AccountId, Balance)>,
+	/// The minimum bid price of the next valid bid, `None` for dutch
+	/// collateral auction which can only be taken
+	pub minimum_next_bid: Option<Balance>,
+	/// The current price of dutch collateral auction
+	pub current_price: Option<Price>,
+	/// The end time of the auction. For dutch collateral auction, it's the
+	/// time after which the auction can be reset
+	pub end_time: Option<BlockNumber>,
+	/// The blocks left until `end_time`
+	pub time_left: Option<BlockNumber>,
+	/// The amount for sale under the current bid
+	pub amount: Balance,
+	/// The amount to be paid under the current bid. For dutch collateral
+	/// auction, it's the payment to take all collateral at the current price
+	pub payment: Balance,
+}
+
+pub trait Trait: SendTransactionTypes<Call<Self>> + CreateSignedTransaction<Call<Self>> + system::Trait {
 	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
 	/// The minimum increment size of each bid compared to the previous one
@@ -178,6 +339,24 @@ pub trait Trait: SendTransactionTypes<Call<Self>> + system::Trait {
 	/// The native currency id
 	type GetNativeCurrencyId: Get<CurrencyId>;
 
+	/// The premium of the start price of dutch collateral auction over the
+	/// price of collateral from price source
+	type DutchAuctionStartPremium: Get<Rate>;
+
+	/// The price decay curve of dutch collateral auction
+	type DutchAuctionPriceDecay: Get<PriceDecayCurve<Self::BlockNumber>>;
+
+	/// The dutch collateral auction can be reset to restart from the latest
+	/// price after the price has decayed for this duration
+	type DutchAuctionResetDuration: Get<Self::BlockNumber>;
+
+	/// The minimum collateral amount of both the split part and the rest of a
+	/// collateral auction when it is split by a partial bid
+	type MinimumCollateralSplitAmount: Get<Balance>;
+
+	/// The maximum number of bids in a batch bid
+	type MaxBidsPerBatch: Get<u32>;
+
 	/// Currency to transfer assets
 	type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
 
@@ -199,6 +378,10 @@ pub trait Trait: SendTransactionTypes<Call<Self>> + system::Trait {
 	/// multiple modules send unsigned transactions.
 	type UnsignedPriority: Get<TransactionPriority>;
 
+	/// The identifier of the local keystore account used by the offchain
+	/// bidding worker
+	type BidderAuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
 	/// Emergency shutdown.
 	type EmergencyShutdown: EmergencyShutdown;
 
@@ -213,9 +396,16 @@ decl_event!(
 		AuctionId = AuctionId,
 		CurrencyId = CurrencyId,
 		Balance = Balance,
+		Price = Price,
 	{
 		/// Collateral auction created. \[auction_id, collateral_type, collateral_amount, target_bid_price\]
 		NewCollateralAuction(AuctionId, CurrencyId, Balance, Balance),
+		/// Dutch collateral auction created. \[auction_id, collateral_type, collateral_amount, target_sales_amount, start_price\]
+		NewDutchCollateralAuction(AuctionId, CurrencyId, Balance, Balance, Price),
+		/// Dutch collateral auction reset. \[auction_id, start_price\]
+		DutchCollateralAuctionReset(AuctionId, Price),
+		/// Dutch collateral auction taken. \[auction_id, collateral_type, collateral_amount, taker, payment_amount\]
+		DutchCollateralAuctionTaken(AuctionId, CurrencyId, Balance, AccountId, Balance),
 		/// Debit auction created. \[auction_id, initial_supply_amount, fix_payment_amount\]
 		NewDebitAuction(AuctionId, Balance, Balance),
 		/// Surplus auction created. \[auction_id, fix_surplus_amount\]
@@ -230,6 +420,10 @@ decl_event!(
 		DebitAuctionDealt(AuctionId, Balance, AccountId, Balance),
 		/// Dex take collateral auction. \[auction_id, collateral_type, collateral_amount, turnover\]
 		DEXTakeCollateralAuction(AuctionId, CurrencyId, Balance, Balance),
+		/// Bid placed on auction. \[auction_id, bidder, bid_price\]
+		Bid(AuctionId, AccountId, Balance),
+		/// Part of collateral auction split into a new collateral auction. \[auction_id, new_auction_id, collateral_amount, target_bid_price\]
+		CollateralAuctionSplit(AuctionId, AuctionId, Balance, Balance),
 	}
 );
 
@@ -248,6 +442,22 @@ decl_error! {
 		InvalidBidPrice,
 		/// Invalid input amount
 		InvalidAmount,
+		/// Must before system shutdown
+		MustBeforeShutdown,
+		/// The current price of dutch collateral auction is above the max price
+		PriceAboveMaximum,
+		/// The dutch collateral auction must be reset before being taken
+		AuctionNeedsReset,
+		/// The dutch collateral auction can not be reset yet
+		AuctionCannotReset,
+		/// The auction has not started yet
+		AuctionNotStarted,
+		/// The collateral auction which already has bid can not be split
+		AuctionAlreadyBid,
+		/// The auction has already ended
+		AuctionExpired,
+		/// The number of bids in the batch exceeds the limit
+		TooManyBids,
 	}
 }
 
@@ -257,6 +467,10 @@ decl_storage! {
 		pub CollateralAuctions get(fn collateral_auctions): map hasher(twox_64_concat) AuctionId =>
 			Option<CollateralAuctionItem<T::AccountId, T::BlockNumber>>;
 
+		/// Mapping from auction id to dutch collateral auction info
+		pub DutchCollateralAuctions get(fn dutch_collateral_auctions): map hasher(twox_64_concat) AuctionId =>
+			Option<DutchCollateralAuctionItem<T::AccountId, T::BlockNumber>>;
+
 		/// Mapping from auction id to debit auction info
 		pub DebitAuctions get(fn debit_auctions): map hasher(twox_64_concat) AuctionId =>
 			Option<DebitAuctionItem<T::BlockNumber>>;
@@ -301,6 +515,18 @@ decl_module! {
 		/// The native currency id
 		const GetNativeCurrencyId: CurrencyId = T::GetNativeCurrencyId::get();
 
+		/// The premium of the start price of dutch collateral auction over the price of collateral
+		const DutchAuctionStartPremium: Rate = T::DutchAuctionStartPremium::get();
+
+		/// The price decay curve of dutch collateral auction
+		const DutchAuctionPriceDecay: PriceDecayCurve<T::BlockNumber> = T::DutchAuctionPriceDecay::get();
+
+		/// The dutch collateral auction can be reset after the price has decayed for this duration
+		const DutchAuctionResetDuration: T::BlockNumber = T::DutchAuctionResetDuration::get();
+
+		/// The maximum number of bids in a batch bid
+		const MaxBidsPerBatch: u32 = T::MaxBidsPerBatch::get();
+
 		/// Cancel active auction after system shutdown
 		///
 		/// The dispatch origin of this call must be _None_.
@@ -339,6 +565,121 @@ decl_module! {
 			})?;
 		}
 
+		/// Take at most `max_amount` collateral from the dutch collateral auction at its current price,
+		/// the auction is finished when all the collateral is sold or the target is raised
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `id`: auction id
+		/// - `max_amount`: the max amount of collateral to take
+		/// - `max_price`: the max price of collateral in stable currency the caller accepts
+		///
+		/// # <weight>
+		/// - Preconditions:
+		/// 	- T::Currency is orml_currencies
+		/// 	- T::CDPTreasury is module_cdp_treasury
+		/// 	- T::Auction is orml_auction
+		/// - Complexity: `O(1)`
+		/// - Db reads: 11
+		/// - Db writes: 9
+		/// -------------------
+		/// Base Weight: 161.3 µs
+		/// # </weight>
+		#[weight = T::WeightInfo::take()]
+		pub fn take(origin, id: AuctionId, #[compact] max_amount: Balance, max_price: Price) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::MustBeforeShutdown);
+				Self::take_dutch_collateral_auction(&who, id, max_amount, max_price)?;
+				Ok(())
+			})?;
+		}
+
+		/// Reset the dutch collateral auction to restart from the latest price of collateral,
+		/// when the price has decayed for `DutchAuctionResetDuration` or reached zero
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `id`: auction id
+		///
+		/// # <weight>
+		/// - Complexity: `O(1)`
+		/// - Db reads: 4
+		/// - Db writes: 1
+		/// -------------------
+		/// Base Weight: 48.62 µs
+		/// # </weight>
+		#[weight = T::WeightInfo::reset()]
+		pub fn reset(origin, id: AuctionId) {
+			with_transaction_result(|| {
+				ensure_signed(origin)?;
+				ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::MustBeforeShutdown);
+				Self::reset_dutch_collateral_auction(id)?;
+				Ok(())
+			})?;
+		}
+
+		/// Bid on multiple auctions atomically, all the bids are rejected if any of them fails
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `bids`: the list of auction id and bid price, at most `MaxBidsPerBatch` bids
+		///
+		/// # <weight>
+		/// - Preconditions:
+		/// 	- T::Currency is orml_currencies
+		/// 	- T::CDPTreasury is module_cdp_treasury
+		/// 	- T::Auction is orml_auction
+		/// - Complexity: `O(N)` where `N` is the number of bids
+		/// - Db reads: 1 + 10 * N
+		/// - Db writes: 8 * N
+		/// -------------------
+		/// Base Weight: 19.84 + 148.2 * N µs
+		/// # </weight>
+		#[weight = T::WeightInfo::batch_bid(bids.len() as u32)]
+		pub fn batch_bid(origin, bids: Vec<(AuctionId, Balance)>) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				ensure!(bids.len() <= T::MaxBidsPerBatch::get() as usize, Error::<T>::TooManyBids);
+				ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::MustBeforeShutdown);
+				for (id, bid_price) in bids {
+					Self::bid_auction(&who, id, bid_price)?;
+				}
+				Ok(())
+			})?;
+		}
+
+		/// Bid on part of the collateral of a collateral auction which has no bid yet. The part is split
+		/// into a new collateral auction with proportional target, and the bid is placed on the new auction.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `id`: auction id
+		/// - `amount`: the amount of collateral to bid on
+		/// - `bid_price`: the bid price in stable currency for `amount` collateral
+		///
+		/// # <weight>
+		/// - Preconditions:
+		/// 	- T::Currency is orml_currencies
+		/// 	- T::CDPTreasury is module_cdp_treasury
+		/// 	- T::Auction is orml_auction
+		/// - Complexity: `O(1)`
+		/// - Db reads: 14
+		/// - Db writes: 11
+		/// -------------------
+		/// Base Weight: 206.4 µs
+		/// # </weight>
+		#[weight = T::WeightInfo::partial_bid()]
+		pub fn partial_bid(origin, id: AuctionId, #[compact] amount: Balance, #[compact] bid_price: Balance) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::MustBeforeShutdown);
+				let new_id = Self::split_collateral_auction(id, amount)?;
+				Self::bid_auction(&who, new_id, bid_price)?;
+				Ok(())
+			})?;
+		}
+
 		/// Start offchain worker in order to submit unsigned tx to cancel active auction after system shutdown.
 		fn offchain_worker(now: T::BlockNumber) {
 			if T::EmergencyShutdown::is_shutdown() && sp_io::offchain::is_validator() {
@@ -356,6 +697,15 @@ decl_module! {
 						now,
 					);
 				}
+			} else if !T::EmergencyShutdown::is_shutdown() {
+				if let Err(e) = Self::_bidding_offchain_worker(now) {
+					debug::info!(
+						target: "auction-manager bidding worker",
+						"cannot run bidding worker at {:?}: {:?}",
+						now,
+						e,
+					);
+				}
 			}
 		}
 	}
@@ -366,6 +716,119 @@ impl<T: Trait> Module<T> {
 		T::Auction::auction_info(auction_id).and_then(|auction_info| auction_info.bid)
 	}
 
+	/// Get the minimum bid price of the next valid bid on the english
+	/// auction `id` at `now`, `None` if there's no such auction.
+	pub fn get_minimum_next_bid(id: AuctionId, now: T::BlockNumber) -> Option<Balance> {
+		let (target_price, start_time, lower_bound) = if let Some(collateral_auction) = Self::collateral_auctions(id) {
+			(collateral_auction.target, collateral_auction.start_time, Zero::zero())
+		} else if let Some(debit_auction) = Self::debit_auctions(id) {
+			(debit_auction.fix, debit_auction.start_time, debit_auction.fix)
+		} else if let Some(surplus_auction) = Self::surplus_auctions(id) {
+			(Zero::zero(), surplus_auction.start_time, Zero::zero())
+		} else {
+			return None;
+		};
+
+		let last_bid_price = Self::get_last_bid(id).map_or(Zero::zero(), |(_, price)| price);
+		let increment = Self::get_minimum_increment_size(now, start_time)
+			.saturating_mul_int(sp_std::cmp::max(target_price, last_bid_price));
+
+		// the new bid price must be greater than the last one
+		Some(sp_std::cmp::max(
+			last_bid_price.saturating_add(sp_std::cmp::max(increment, One::one())),
+			lower_bound,
+		))
+	}
+
+	/// Get the bid state of the active auction `id` at `now`, `None` if
+	/// there's no such auction.
+	pub fn get_auction_bid_state(
+		id: AuctionId,
+		now: T::BlockNumber,
+	) -> Option<AuctionBidState<T::AccountId, T::BlockNumber>> {
+		let auction_info = T::Auction::auction_info(id)?;
+		let last_bid = auction_info.bid;
+		let last_bid_price = last_bid.as_ref().map_or(Zero::zero(), |(_, price)| *price);
+		let mut end_time = auction_info.end;
+
+		let (auction_type, currency_id, stage, amount, payment, current_price) =
+			if let Some(collateral_auction) = Self::collateral_auctions(id) {
+				let stage = if collateral_auction.in_reverse_stage(last_bid_price) {
+					AuctionStage::Reverse
+				} else {
+					AuctionStage::Forward
+				};
+				(
+					AuctionType::Collateral,
+					collateral_auction.currency_id,
+					stage,
+					collateral_auction.amount,
+					collateral_auction.payment_amount(last_bid_price),
+					None,
+				)
+			} else if let Some(dutch_collateral_auction) = Self::dutch_collateral_auctions(id) {
+				let price = Self::get_dutch_auction_price(&dutch_collateral_auction, now);
+				let (_, payment) = dutch_collateral_auction.take_amount(price, dutch_collateral_auction.amount);
+				end_time = Some(dutch_collateral_auction.start_time + T::DutchAuctionResetDuration::get());
+				(
+					AuctionType::DutchCollateral,
+					dutch_collateral_auction.currency_id,
+					AuctionStage::Descending,
+					dutch_collateral_auction.amount,
+					payment,
+					Some(price),
+				)
+			} else if let Some(debit_auction) = Self::debit_auctions(id) {
+				let payment = if last_bid.is_some() {
+					debit_auction.fix
+				} else {
+					Zero::zero()
+				};
+				(
+					AuctionType::Debit,
+					T::GetNativeCurrencyId::get(),
+					AuctionStage::Reverse,
+					debit_auction.amount,
+					payment,
+					None,
+				)
+			} else if let Some(surplus_auction) = Self::surplus_auctions(id) {
+				(
+					AuctionType::Surplus,
+					T::GetStableCurrencyId::get(),
+					AuctionStage::Forward,
+					surplus_auction.amount,
+					last_bid_price,
+					None,
+				)
+			} else {
+				return None;
+			};
+
+		Some(AuctionBidState {
+			auction_type,
+			currency_id,
+			stage,
+			last_bid,
+			minimum_next_bid: Self::get_minimum_next_bid(id, now),
+			current_price,
+			end_time,
+			time_left: end_time.map(|end_time| end_time.saturating_sub(now)),
+			amount,
+			payment,
+		})
+	}
+
+	/// Get the ids of all active auctions.
+	pub fn active_auction_ids() -> Vec<AuctionId> {
+		<CollateralAuctions<T> as IterableStorageMap<_, _>>::iter()
+			.map(|(id, _)| id)
+			.chain(<DutchCollateralAuctions<T> as IterableStorageMap<_, _>>::iter().map(|(id, _)| id))
+			.chain(<DebitAuctions<T> as IterableStorageMap<_, _>>::iter().map(|(id, _)| id))
+			.chain(<SurplusAuctions<T> as IterableStorageMap<_, _>>::iter().map(|(id, _)| id))
+			.collect()
+	}
+
 	fn submit_cancel_auction_tx(auction_id: AuctionId) {
 		let call = Call::<T>::cancel(auction_id);
 		if let Err(err) = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()) {
@@ -395,7 +858,7 @@ impl<T: Trait> Module<T> {
 		} else {
 			let random_seed = sp_io::offchain::random_seed();
 			let mut rng = RandomNumberGenerator::<BlakeTwo256>::new(BlakeTwo256::hash(&random_seed[..]));
-			(rng.pick_u32(2), None)
+			(rng.pick_u32(3), None)
 		};
 
 		// get the max iterationns config
@@ -405,8 +868,8 @@ impl<T: Trait> Module<T> {
 
 		debug::debug!(target: "auction-manager offchain worker", "max iterations is {:?}", max_iterations);
 
-		// Randomly choose to start iterations to cancel collateral/surplus/debit
-		// auctions
+		// Randomly choose to start iterations to cancel collateral/surplus/debit/dutch
+		// collateral auctions
 		match auction_type_num {
 			0 => {
 				let mut iterator =
@@ -438,6 +901,20 @@ impl<T: Trait> Module<T> {
 					to_be_continue.set(&(auction_type_num, iterator.storage_map_iterator.previous_key));
 				}
 			}
+			2 => {
+				let mut iterator =
+					<DutchCollateralAuctions<T> as IterableStorageMapExtended<_, _>>::iter(max_iterations, start_key);
+				while let Some((dutch_collateral_auction_id, _)) = iterator.next() {
+					Self::submit_cancel_auction_tx(dutch_collateral_auction_id);
+					guard.extend_lock().map_err(|_| OffchainErr::OffchainLock)?;
+				}
+
+				if iterator.finished {
+					to_be_continue.clear();
+				} else {
+					to_be_continue.set(&(auction_type_num, iterator.storage_map_iterator.previous_key));
+				}
+			}
 			_ => {
 				let mut iterator =
 					<CollateralAuctions<T> as IterableStorageMapExtended<_, _>>::iter(max_iterations, start_key);
@@ -470,6 +947,98 @@ impl<T: Trait> Module<T> {
 		Ok(())
 	}
 
+	/// Get the first account of `AUCTION_BIDDER_KEY_TYPE` in the local
+	/// keystore.
+	fn get_local_bidder() -> Option<(T::AccountId, T::Public)> {
+		<T::BidderAuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all()
+			.into_iter()
+			.next()
+			.map(|key| {
+				let generic_public =
+					<T::BidderAuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(key);
+				let public: T::Public = generic_public.into();
+				(public.clone().into_account(), public)
+			})
+	}
+
+	/// Get the bid price and the payment for the offchain bidding worker to
+	/// bid on the collateral auction `id` at `now`, `None` if the discount of
+	/// the collateral under the bid versus the price of `PriceSource` is less
+	/// than `min_discount`, or the payment exceeds `max_payment` of `config`.
+	pub fn get_worker_bid(id: AuctionId, now: T::BlockNumber, config: &BiddingConfig) -> Option<(Balance, Balance)> {
+		let collateral_auction = Self::collateral_auctions(id)?;
+		let last_bid_price = Self::get_last_bid(id).map_or(Zero::zero(), |(_, price)| price);
+		let bid_price = Self::get_minimum_next_bid(id, now)?;
+		let payment = collateral_auction.payment_amount(bid_price);
+		let collateral_amount = collateral_auction.collateral_amount(last_bid_price, bid_price);
+
+		let price = T::PriceSource::get_relative_price(collateral_auction.currency_id, T::GetStableCurrencyId::get())?;
+		let max_payment = price
+			.saturating_mul(Rate::one().saturating_sub(config.min_discount))
+			.saturating_mul_int(collateral_amount);
+
+		if payment <= max_payment && payment <= config.max_payment {
+			Some((bid_price, payment))
+		} else {
+			None
+		}
+	}
+
+	fn _bidding_offchain_worker(now: T::BlockNumber) -> Result<(), OffchainErr> {
+		// the bidding worker is disabled if there's no config
+		let config = match StorageValueRef::persistent(&OFFCHAIN_BIDDING_CONFIG).get::<BiddingConfig>() {
+			Some(Some(config)) => config,
+			_ => return Ok(()),
+		};
+		let (bidder, public) = match Self::get_local_bidder() {
+			Some(local_bidder) => local_bidder,
+			None => return Ok(()),
+		};
+
+		// acquire offchain bidding worker lock.
+		let lock_expiration = Duration::from_millis(LOCK_DURATION);
+		let mut lock = StorageLock::<'_, Time>::with_deadline(&OFFCHAIN_BIDDING_LOCK, lock_expiration);
+		let guard = lock.try_lock().map_err(|_| OffchainErr::OffchainLock)?;
+
+		let mut free_balance = T::Currency::free_balance(T::GetStableCurrencyId::get(), &bidder);
+		let mut bids: Vec<(AuctionId, Balance)> = vec![];
+		for (id, _) in <CollateralAuctions<T> as IterableStorageMap<_, _>>::iter().take(DEFAULT_MAX_ITERATIONS as usize)
+		{
+			if bids.len() >= T::MaxBidsPerBatch::get() as usize {
+				break;
+			}
+
+			// skip the auction which the local bidder is winning
+			if matches!(Self::get_last_bid(id), Some((last_bidder, _)) if last_bidder == bidder) {
+				continue;
+			}
+
+			if let Some((bid_price, payment)) = Self::get_worker_bid(id, now, &config) {
+				if payment <= free_balance {
+					free_balance = free_balance.saturating_sub(payment);
+					bids.push((id, bid_price));
+				}
+			}
+		}
+
+		// Consume the guard but **do not** unlock the underlying lock.
+		guard.forget();
+
+		if bids.is_empty() {
+			return Ok(());
+		}
+
+		// bid all in one tx, since the txs signed by the same account at the same block
+		// will conflict on nonce
+		match Signer::<T, T::BidderAuthorityId>::any_account()
+			.with_filter(vec![public])
+			.send_signed_transaction(|_| Call::<T>::batch_bid(bids.clone()))
+		{
+			Some((_, Ok(()))) => Ok(()),
+			_ => Err(OffchainErr::SubmitTransaction),
+		}
+	}
+
 	fn cancel_surplus_auction(id: AuctionId, surplus_auction: SurplusAuctionItem<T::BlockNumber>) -> DispatchResult {
 		// if there's bid
 		if let Some((bidder, bid_price)) = Self::get_last_bid(id) {
@@ -558,6 +1127,266 @@ impl<T: Trait> Module<T> {
 		Ok(())
 	}
 
+	fn cancel_dutch_collateral_auction(
+		dutch_collateral_auction: DutchCollateralAuctionItem<T::AccountId, T::BlockNumber>,
+	) -> DispatchResult {
+		// calculate how much collateral to offset remaining target in settle price
+		let stable_currency_id = T::GetStableCurrencyId::get();
+		let settle_price = T::PriceSource::get_relative_price(stable_currency_id, dutch_collateral_auction.currency_id)
+			.ok_or(Error::<T>::InvalidFeedPrice)?;
+		let confiscate_collateral_amount = if dutch_collateral_auction.target.is_zero() {
+			dutch_collateral_auction.amount
+		} else {
+			sp_std::cmp::min(
+				settle_price.saturating_mul_int(dutch_collateral_auction.target),
+				dutch_collateral_auction.amount,
+			)
+		};
+		let refund_collateral_amount = dutch_collateral_auction
+			.amount
+			.saturating_sub(confiscate_collateral_amount);
+
+		// refund remain collateral to refund recipient from CDP treasury
+		T::CDPTreasury::withdraw_collateral(
+			&dutch_collateral_auction.refund_recipient,
+			dutch_collateral_auction.currency_id,
+			refund_collateral_amount,
+		)?;
+
+		// decrease account ref of refund recipient
+		system::Module::<T>::dec_ref(&dutch_collateral_auction.refund_recipient);
+
+		// decrease total collateral and target in auction
+		TotalCollateralInAuction::mutate(dutch_collateral_auction.currency_id, |balance| {
+			*balance = balance.saturating_sub(dutch_collateral_auction.amount)
+		});
+		TotalTargetInAuction::mutate(|balance| *balance = balance.saturating_sub(dutch_collateral_auction.target));
+
+		Ok(())
+	}
+
+	/// Get the start price of dutch collateral auction under `currency_id`,
+	/// which is the price of collateral with the start premium.
+	fn get_dutch_auction_start_price(currency_id: CurrencyId) -> sp_std::result::Result<Price, DispatchError> {
+		let price = T::PriceSource::get_relative_price(currency_id, T::GetStableCurrencyId::get())
+			.ok_or(Error::<T>::InvalidFeedPrice)?;
+		Ok(price.saturating_mul(Rate::one().saturating_add(T::DutchAuctionStartPremium::get())))
+	}
+
+	/// Get the price of dutch collateral auction at `now`, decayed from the
+	/// start price according to `DutchAuctionPriceDecay`.
+	pub fn get_dutch_auction_price(
+		dutch_collateral_auction: &DutchCollateralAuctionItem<T::AccountId, T::BlockNumber>,
+		now: T::BlockNumber,
+	) -> Price {
+		let start_price = dutch_collateral_auction.start_price;
+		let elapsed = now.saturating_sub(dutch_collateral_auction.start_time);
+
+		match T::DutchAuctionPriceDecay::get() {
+			PriceDecayCurve::Linear(duration) => {
+				if elapsed >= duration {
+					Zero::zero()
+				} else {
+					Rate::checked_from_rational(
+						duration.saturating_sub(elapsed).saturated_into::<u128>(),
+						duration.saturated_into::<u128>(),
+					)
+					.map(|rate| start_price.saturating_mul(rate))
+					.unwrap_or_default()
+				}
+			}
+			PriceDecayCurve::StairstepExponential(step, cut) => {
+				if step.is_zero() {
+					start_price
+				} else {
+					let steps: u32 = (elapsed / step).saturated_into();
+					start_price.saturating_mul(cut.saturating_pow(steps as usize))
+				}
+			}
+		}
+	}
+
+	/// Return `true` if the dutch collateral auction has decayed for
+	/// `DutchAuctionResetDuration` or its price has reached zero.
+	fn dutch_auction_needs_reset(
+		dutch_collateral_auction: &DutchCollateralAuctionItem<T::AccountId, T::BlockNumber>,
+		now: T::BlockNumber,
+	) -> bool {
+		now >= dutch_collateral_auction.start_time + T::DutchAuctionResetDuration::get()
+			|| Self::get_dutch_auction_price(dutch_collateral_auction, now).is_zero()
+	}
+
+	/// Take at most `max_amount` collateral from dutch collateral auction `id`
+	/// by `who`, finish the auction if all the collateral is sold or the
+	/// target is raised.
+	fn take_dutch_collateral_auction(
+		who: &T::AccountId,
+		id: AuctionId,
+		max_amount: Balance,
+		max_price: Price,
+	) -> DispatchResult {
+		let mut dutch_collateral_auction = Self::dutch_collateral_auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
+		let currency_id = dutch_collateral_auction.currency_id;
+		let now = <system::Module<T>>::block_number();
+		ensure!(
+			!Self::dutch_auction_needs_reset(&dutch_collateral_auction, now),
+			Error::<T>::AuctionNeedsReset
+		);
+
+		let price = Self::get_dutch_auction_price(&dutch_collateral_auction, now);
+		ensure!(price <= max_price, Error::<T>::PriceAboveMaximum);
+
+		let (collateral_amount, payment) = dutch_collateral_auction.take_amount(price, max_amount);
+		ensure!(
+			!collateral_amount.is_zero() && !payment.is_zero(),
+			Error::<T>::InvalidAmount
+		);
+		let finished = collateral_amount == dutch_collateral_auction.amount
+			|| (!dutch_collateral_auction.target.is_zero() && payment == dutch_collateral_auction.target);
+
+		// transfer payment from taker to CDP treasury, and collateral from CDP treasury to taker
+		T::CDPTreasury::deposit_surplus(who, payment)?;
+		T::CDPTreasury::withdraw_collateral(who, currency_id, collateral_amount)?;
+
+		TotalCollateralInAuction::mutate(currency_id, |balance| {
+			*balance = balance.saturating_sub(collateral_amount)
+		});
+		dutch_collateral_auction.amount = dutch_collateral_auction.amount.saturating_sub(collateral_amount);
+		if !dutch_collateral_auction.target.is_zero() {
+			TotalTargetInAuction::mutate(|balance| *balance = balance.saturating_sub(payment));
+			dutch_collateral_auction.target = dutch_collateral_auction.target.saturating_sub(payment);
+		}
+
+		<Module<T>>::deposit_event(RawEvent::DutchCollateralAuctionTaken(
+			id,
+			currency_id,
+			collateral_amount,
+			who.clone(),
+			payment,
+		));
+
+		if finished {
+			// refund remain collateral to refund recipient from CDP treasury
+			T::CDPTreasury::withdraw_collateral(
+				&dutch_collateral_auction.refund_recipient,
+				currency_id,
+				dutch_collateral_auction.amount,
+			)?;
+			TotalCollateralInAuction::mutate(currency_id, |balance| {
+				*balance = balance.saturating_sub(dutch_collateral_auction.amount)
+			});
+
+			// decrement recipient account reference
+			system::Module::<T>::dec_ref(&dutch_collateral_auction.refund_recipient);
+
+			<DutchCollateralAuctions<T>>::remove(id);
+			T::Auction::remove_auction(id);
+		} else {
+			<DutchCollateralAuctions<T>>::insert(id, dutch_collateral_auction);
+		}
+
+		Ok(())
+	}
+
+	/// Reset dutch collateral auction `id` to restart from the latest start
+	/// price.
+	fn reset_dutch_collateral_auction(id: AuctionId) -> DispatchResult {
+		<DutchCollateralAuctions<T>>::try_mutate(id, |dutch_collateral_auction| -> DispatchResult {
+			let dutch_collateral_auction = dutch_collateral_auction.as_mut().ok_or(Error::<T>::AuctionNotExists)?;
+			let now = <system::Module<T>>::block_number();
+			ensure!(
+				Self::dutch_auction_needs_reset(dutch_collateral_auction, now),
+				Error::<T>::AuctionCannotReset
+			);
+
+			let start_price = Self::get_dutch_auction_start_price(dutch_collateral_auction.currency_id)?;
+			dutch_collateral_auction.start_price = start_price;
+			dutch_collateral_auction.start_time = now;
+
+			<Module<T>>::deposit_event(RawEvent::DutchCollateralAuctionReset(id, start_price));
+			Ok(())
+		})
+	}
+
+	/// Place a bid on the english auction `id`, and update the auction info
+	/// of `T::Auction` as a normal bid does.
+	fn bid_auction(bidder: &T::AccountId, id: AuctionId, bid_price: Balance) -> DispatchResult {
+		let mut auction_info = T::Auction::auction_info(id).ok_or(Error::<T>::AuctionNotExists)?;
+		let now = <system::Module<T>>::block_number();
+		ensure!(now >= auction_info.start, Error::<T>::AuctionNotStarted);
+		ensure!(
+			auction_info.end.map_or(true, |end| now < end),
+			Error::<T>::AuctionExpired
+		);
+
+		let last_bid_price = auction_info.bid.as_ref().map_or(Zero::zero(), |(_, price)| *price);
+		ensure!(bid_price > last_bid_price, Error::<T>::InvalidBidPrice);
+
+		let bid_result = Self::on_new_bid(now, id, (bidder.clone(), bid_price), auction_info.bid.clone());
+		ensure!(bid_result.accept_bid, Error::<T>::InvalidBidPrice);
+
+		if let Change::NewValue(new_end) = bid_result.auction_end_change {
+			auction_info.end = new_end;
+		}
+		auction_info.bid = Some((bidder.clone(), bid_price));
+		T::Auction::update_auction(id, auction_info)?;
+
+		<Module<T>>::deposit_event(RawEvent::Bid(id, bidder.clone(), bid_price));
+		Ok(())
+	}
+
+	/// Split `amount` collateral with proportional target from the collateral
+	/// auction `id` which has no bid yet into a new collateral auction, the
+	/// target is rounded up in favor of the refund recipient. Returns the id of
+	/// the new auction.
+	fn split_collateral_auction(id: AuctionId, amount: Balance) -> sp_std::result::Result<AuctionId, DispatchError> {
+		ensure!(Self::get_last_bid(id).is_none(), Error::<T>::AuctionAlreadyBid);
+		let mut collateral_auction = Self::collateral_auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
+		let minimum_amount = T::MinimumCollateralSplitAmount::get().max(One::one());
+		ensure!(
+			amount >= minimum_amount && collateral_auction.amount.saturating_sub(amount) >= minimum_amount,
+			Error::<T>::InvalidAmount
+		);
+
+		let target: Balance = U256::from(amount)
+			.saturating_mul(U256::from(collateral_auction.target))
+			.saturating_add(U256::from(collateral_auction.amount.saturating_sub(1)))
+			.checked_div(U256::from(collateral_auction.amount))
+			.and_then(|n| TryInto::<Balance>::try_into(n).ok())
+			.ok_or(Error::<T>::InvalidAmount)?;
+		// both the split auction and the rest must have target if the original one has
+		ensure!(
+			collateral_auction.always_forward() || (!target.is_zero() && target < collateral_auction.target),
+			Error::<T>::InvalidAmount
+		);
+
+		collateral_auction.initial_amount = collateral_auction.initial_amount.saturating_sub(amount);
+		collateral_auction.amount = collateral_auction.amount.saturating_sub(amount);
+		collateral_auction.target = collateral_auction.target.saturating_sub(target);
+
+		let new_id = T::Auction::new_auction(<system::Module<T>>::block_number(), None)?;
+		let refund_recipient = collateral_auction.refund_recipient.clone();
+		<CollateralAuctions<T>>::insert(
+			new_id,
+			CollateralAuctionItem {
+				refund_recipient: refund_recipient.clone(),
+				currency_id: collateral_auction.currency_id,
+				initial_amount: amount,
+				amount,
+				target,
+				// keep the start time of the original auction for the soft cap
+				start_time: collateral_auction.start_time,
+			},
+		);
+		<CollateralAuctions<T>>::insert(id, collateral_auction);
+
+		// increment recipient account reference
+		system::Module::<T>::inc_ref(&refund_recipient);
+
+		<Module<T>>::deposit_event(RawEvent::CollateralAuctionSplit(id, new_id, amount, target));
+		Ok(new_id)
+	}
+
 	/// Return `true` if price increment rate is greater than or equal to
 	/// minimum.
 	///
@@ -1020,6 +1849,59 @@ impl<T: Trait> AuctionManager<T::AccountId> for Module<T> {
 		Ok(())
 	}
 
+	fn new_dutch_collateral_auction(
+		refund_recipient: &T::AccountId,
+		currency_id: Self::CurrencyId,
+		amount: Self::Balance,
+		target: Self::Balance,
+	) -> DispatchResult {
+		ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+		let start_price = Self::get_dutch_auction_start_price(currency_id)?;
+
+		TotalCollateralInAuction::try_mutate(currency_id, |total| -> DispatchResult {
+			*total = total.checked_add(amount).ok_or(Error::<T>::InvalidAmount)?;
+			Ok(())
+		})?;
+
+		if !target.is_zero() {
+			// no-op if target is zero
+			TotalTargetInAuction::try_mutate(|total| -> DispatchResult {
+				*total = total.checked_add(target).ok_or(Error::<T>::InvalidAmount)?;
+				Ok(())
+			})?;
+		}
+
+		let start_time = <system::Module<T>>::block_number();
+
+		// dutch collateral auction does not accept bids, only take the auction id
+		let auction_id = T::Auction::new_auction(start_time, None)?;
+
+		<DutchCollateralAuctions<T>>::insert(
+			auction_id,
+			DutchCollateralAuctionItem {
+				refund_recipient: refund_recipient.clone(),
+				currency_id,
+				initial_amount: amount,
+				amount,
+				target,
+				start_price,
+				start_time,
+			},
+		);
+
+		// increment recipient account reference
+		system::Module::<T>::inc_ref(&refund_recipient);
+
+		<Module<T>>::deposit_event(RawEvent::NewDutchCollateralAuction(
+			auction_id,
+			currency_id,
+			amount,
+			target,
+			start_price,
+		));
+		Ok(())
+	}
+
 	fn new_debit_auction(initial_amount: Self::Balance, fix_debit: Self::Balance) -> DispatchResult {
 		ensure!(
 			!initial_amount.is_zero() && !fix_debit.is_zero(),
@@ -1075,6 +1957,8 @@ impl<T: Trait> AuctionManager<T::AccountId> for Module<T> {
 			Self::cancel_debit_auction(id, debit_auction)?;
 		} else if let Some(surplus_auction) = <SurplusAuctions<T>>::take(id) {
 			Self::cancel_surplus_auction(id, surplus_auction)?;
+		} else if let Some(dutch_collateral_auction) = <DutchCollateralAuctions<T>>::take(id) {
+			Self::cancel_dutch_collateral_auction(dutch_collateral_auction)?;
 		} else {
 			return Err(Error::<T>::AuctionNotExists.into());
 		}
@@ -1115,7 +1999,10 @@ impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
 						return InvalidTransaction::Stale.into();
 					}
 				}
-			} else if !<SurplusAuctions<T>>::contains_key(auction_id) && !<DebitAuctions<T>>::contains_key(auction_id) {
+			} else if !<SurplusAuctions<T>>::contains_key(auction_id)
+				&& !<DebitAuctions<T>>::contains_key(auction_id)
+				&& !<DutchCollateralAuctions<T>>::contains_key(auction_id)
+			{
 				return InvalidTransaction::Stale.into();
 			}
 
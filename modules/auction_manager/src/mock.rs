@@ -8,8 +8,8 @@ use frame_system::EnsureSignedBy;
 use primitives::{TokenSymbol, TradingPair};
 use sp_core::H256;
 use sp_runtime::{
-	testing::{Header, TestXt},
-	traits::IdentityLookup,
+	testing::{Header, TestSignature, TestXt, UintAuthorityId},
+	traits::{Extrinsic as ExtrinsicT, IdentityLookup},
 	ModuleId, Perbill,
 };
 use sp_std::cell::RefCell;
@@ -41,6 +41,7 @@ impl_outer_origin! {
 impl_outer_dispatch! {
 	pub enum Call for Runtime where origin: Origin {
 		auction_manager::AuctionManagerModule,
+		dex::DEXModule,
 	}
 }
 
@@ -124,11 +125,17 @@ impl cdp_treasury::Trait for Runtime {
 	type Event = TestEvent;
 	type Currency = Tokens;
 	type GetStableCurrencyId = GetStableCurrencyId;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type AuctionManagerHandler = AuctionManagerModule;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = DEXModule;
+	type CollateralRegistry = ();
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type SurplusBuybackInterval = ();
+	type MaxSurplusBuybackSlippage = ();
+	type StakingRewardPool = ();
+	type PriceSource = ();
 	type WeightInfo = ();
 }
 pub type CDPTreasuryModule = cdp_treasury::Module<Runtime>;
@@ -161,18 +168,34 @@ parameter_types! {
 	pub const DEXModuleId: ModuleId = ModuleId(*b"aca/dexm");
 	pub const GetExchangeFee: (u32, u32) = (0, 100);
 	pub const TradingPathLimit: usize = 3;
+	pub const TradingPathCandidatesLimit: usize = 3;
+	pub const TWAPWindow: BlockNumber = 10;
+	pub const LimitOrderDeposit: Balance = 1;
+	pub const MaxLimitOrdersPerAccount: u32 = 10;
+	pub const ProtocolFeeReceiver: AccountId = 10;
 	pub EnabledTradingPairs : Vec<TradingPair> = vec![TradingPair::new(AUSD, BTC)];
 }
 
 impl dex::Trait for Runtime {
 	type Event = TestEvent;
 	type Currency = Tokens;
-	type EnabledTradingPairs = EnabledTradingPairs;
 	type GetExchangeFee = GetExchangeFee;
 	type TradingPathLimit = TradingPathLimit;
+	type TradingPathCandidatesLimit = TradingPathCandidatesLimit;
 	type ModuleId = DEXModuleId;
 	type DEXIncentives = ();
 	type WeightInfo = ();
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type ProtocolFeeReceiver = ProtocolFeeReceiver;
+	type TWAPWindow = TWAPWindow;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type LimitOrderDeposit = LimitOrderDeposit;
+	type MaxLimitOrdersPerAccount = MaxLimitOrdersPerAccount;
+	type UnsignedPriority = UnsignedPriority;
+	type Call = Call;
+	type LegacyEnabledTradingPairs = EnabledTradingPairs;
+	type StableSwapRateProvider = ();
+	type FlashSwapCallFilter = ();
 }
 pub type DEXModule = dex::Module<Runtime>;
 
@@ -195,6 +218,11 @@ parameter_types! {
 	pub MinimumIncrementSize: Rate = Rate::saturating_from_rational(1, 20);
 	pub const AuctionTimeToClose: u64 = 100;
 	pub const AuctionDurationSoftCap: u64 = 2000;
+	pub DutchAuctionStartPremium: Rate = Rate::saturating_from_rational(1, 10);
+	pub const DutchAuctionPriceDecay: PriceDecayCurve<u64> = PriceDecayCurve::Linear(100);
+	pub const DutchAuctionResetDuration: u64 = 50;
+	pub const MinimumCollateralSplitAmount: Balance = 2;
+	pub const MaxBidsPerBatch: u32 = 3;
 	pub const GetNativeCurrencyId: CurrencyId = ACA;
 	pub const UnsignedPriority: u64 = 1 << 20;
 }
@@ -208,11 +236,17 @@ impl Trait for Runtime {
 	type AuctionDurationSoftCap = AuctionDurationSoftCap;
 	type GetStableCurrencyId = GetStableCurrencyId;
 	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type DutchAuctionStartPremium = DutchAuctionStartPremium;
+	type DutchAuctionPriceDecay = DutchAuctionPriceDecay;
+	type DutchAuctionResetDuration = DutchAuctionResetDuration;
+	type MinimumCollateralSplitAmount = MinimumCollateralSplitAmount;
+	type MaxBidsPerBatch = MaxBidsPerBatch;
 	type CDPTreasury = CDPTreasuryModule;
 	type DEX = DEXModule;
 	type PriceSource = MockPriceSource;
 	type UnsignedPriority = UnsignedPriority;
 	type EmergencyShutdown = MockEmergencyShutdown;
+	type BidderAuthorityId = MockBidderAuthorityId;
 	type WeightInfo = ();
 }
 pub type AuctionManagerModule = Module<Runtime>;
@@ -228,6 +262,56 @@ where
 	type Extrinsic = Extrinsic;
 }
 
+/// The public key of the bidder account for tests.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Encode, Decode)]
+pub struct MockBidderPublic(pub UintAuthorityId);
+
+impl IdentifyAccount for MockBidderPublic {
+	type AccountId = AccountId;
+
+	fn into_account(self) -> AccountId {
+		(self.0).0.into()
+	}
+}
+
+impl From<UintAuthorityId> for MockBidderPublic {
+	fn from(key: UintAuthorityId) -> Self {
+		MockBidderPublic(key)
+	}
+}
+
+impl From<MockBidderPublic> for UintAuthorityId {
+	fn from(public: MockBidderPublic) -> Self {
+		public.0
+	}
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = MockBidderPublic;
+	type Signature = TestSignature;
+}
+
+pub struct MockBidderAuthorityId;
+impl AppCrypto<MockBidderPublic, TestSignature> for MockBidderAuthorityId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+	type GenericPublic = UintAuthorityId;
+}
+
+impl<LocalCall> CreateSignedTransaction<LocalCall> for Runtime
+where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		_public: MockBidderPublic,
+		_account: AccountId,
+		nonce: u64,
+	) -> Option<(Call, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
 pub struct ExtBuilder {
 	endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>,
 }
@@ -262,6 +346,12 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		dex::GenesisConfig {
+			initial_enabled_trading_pairs: EnabledTradingPairs::get(),
+		}
+		.assimilate_storage::<Runtime>(&mut t)
+		.unwrap();
+
 		t.into()
 	}
 }
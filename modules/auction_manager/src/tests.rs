@@ -5,6 +5,8 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::*;
+use sp_core::offchain::{testing, OffchainExt, TransactionPoolExt};
+use sp_runtime::testing::UintAuthorityId;
 
 #[test]
 fn get_auction_time_to_close_work() {
@@ -723,3 +725,540 @@ fn cancel_collateral_auction_work() {
 		assert_eq!(AuctionModule::auction_info(0).is_some(), false);
 	});
 }
+
+#[test]
+fn new_dutch_collateral_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			AuctionManagerModule::new_dutch_collateral_auction(&ALICE, BTC, 0, 100),
+			Error::<Runtime>::InvalidAmount,
+		);
+		MockPriceSource::set_relative_price(None);
+		assert_noop!(
+			AuctionManagerModule::new_dutch_collateral_auction(&ALICE, BTC, 10, 100),
+			Error::<Runtime>::InvalidFeedPrice,
+		);
+		MockPriceSource::set_relative_price(Some(Price::one()));
+
+		assert_ok!(AuctionManagerModule::new_dutch_collateral_auction(&ALICE, BTC, 10, 100));
+		let start_price = Price::saturating_from_rational(11, 10);
+		let new_dutch_collateral_auction_event =
+			TestEvent::auction_manager(RawEvent::NewDutchCollateralAuction(0, BTC, 10, 100, start_price));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == new_dutch_collateral_auction_event));
+
+		let dutch_collateral_auction = AuctionManagerModule::dutch_collateral_auctions(0).unwrap();
+		assert_eq!(dutch_collateral_auction.start_price, start_price);
+		assert_eq!(dutch_collateral_auction.start_time, 1);
+		assert_eq!(AuctionManagerModule::collateral_auctions(0).is_some(), false);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 10);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 100);
+		assert_eq!(AuctionModule::auctions_index(), 1);
+		assert_eq!(System::refs(&ALICE), 1);
+
+		// dutch collateral auction does not accept bids
+		assert_eq!(AuctionModule::bid(Origin::signed(BOB), 0, 100).is_ok(), false);
+	});
+}
+
+#[test]
+fn get_dutch_auction_price_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::new_dutch_collateral_auction(&ALICE, BTC, 10, 100));
+		let dutch_collateral_auction = AuctionManagerModule::dutch_collateral_auctions(0).unwrap();
+
+		assert_eq!(
+			AuctionManagerModule::get_dutch_auction_price(&dutch_collateral_auction, 1),
+			Price::saturating_from_rational(110, 100)
+		);
+		assert_eq!(
+			AuctionManagerModule::get_dutch_auction_price(&dutch_collateral_auction, 11),
+			Price::saturating_from_rational(99, 100)
+		);
+		assert_eq!(
+			AuctionManagerModule::get_dutch_auction_price(&dutch_collateral_auction, 51),
+			Price::saturating_from_rational(55, 100)
+		);
+		assert_eq!(
+			AuctionManagerModule::get_dutch_auction_price(&dutch_collateral_auction, 101),
+			Price::zero()
+		);
+
+		assert_eq!(
+			AuctionManagerModule::dutch_auction_needs_reset(&dutch_collateral_auction, 50),
+			false
+		);
+		assert_eq!(
+			AuctionManagerModule::dutch_auction_needs_reset(&dutch_collateral_auction, 51),
+			true
+		);
+	});
+}
+
+#[test]
+fn take_dutch_collateral_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			AuctionManagerModule::take(Origin::signed(BOB), 0, 20, Price::one()),
+			Error::<Runtime>::AuctionNotExists,
+		);
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_dutch_collateral_auction(&ALICE, BTC, 100, 50));
+		System::set_block_number(11);
+
+		assert_noop!(
+			AuctionManagerModule::take(Origin::signed(BOB), 0, 20, Price::saturating_from_rational(9, 10)),
+			Error::<Runtime>::PriceAboveMaximum,
+		);
+		assert_ok!(AuctionManagerModule::take(Origin::signed(BOB), 0, 20, Price::one()));
+		let take_event = TestEvent::auction_manager(RawEvent::DutchCollateralAuctionTaken(0, BTC, 20, BOB, 19));
+		assert!(System::events().iter().any(|record| record.event == take_event));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 981);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 1020);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 19);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 80);
+		assert_eq!(AuctionManagerModule::dutch_collateral_auctions(0).unwrap().amount, 80);
+		assert_eq!(AuctionManagerModule::dutch_collateral_auctions(0).unwrap().target, 31);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 80);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 31);
+
+		// only take the collateral which is enough to raise the remaining target,
+		// refund the remain collateral and finish the auction
+		assert_ok!(AuctionManagerModule::take(Origin::signed(CAROL), 0, 100, Price::one()));
+		assert_eq!(Tokens::free_balance(AUSD, &CAROL), 969);
+		assert_eq!(Tokens::free_balance(BTC, &CAROL), 1031);
+		assert_eq!(Tokens::free_balance(BTC, &ALICE), 949);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 50);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 0);
+		assert_eq!(AuctionManagerModule::dutch_collateral_auctions(0).is_some(), false);
+		assert_eq!(AuctionModule::auction_info(0).is_some(), false);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 0);
+		assert_eq!(System::refs(&ALICE), 0);
+	});
+}
+
+#[test]
+fn reset_dutch_collateral_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_dutch_collateral_auction(&ALICE, BTC, 100, 0));
+		assert_noop!(
+			AuctionManagerModule::reset(Origin::signed(BOB), 0),
+			Error::<Runtime>::AuctionCannotReset,
+		);
+
+		System::set_block_number(51);
+		assert_noop!(
+			AuctionManagerModule::take(Origin::signed(BOB), 0, 20, Price::one()),
+			Error::<Runtime>::AuctionNeedsReset,
+		);
+
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_integer(2)));
+		assert_ok!(AuctionManagerModule::reset(Origin::signed(BOB), 0));
+		let start_price = Price::saturating_from_rational(22, 10);
+		let reset_event = TestEvent::auction_manager(RawEvent::DutchCollateralAuctionReset(0, start_price));
+		assert!(System::events().iter().any(|record| record.event == reset_event));
+		assert_eq!(
+			AuctionManagerModule::dutch_collateral_auctions(0).unwrap().start_price,
+			start_price
+		);
+		assert_eq!(
+			AuctionManagerModule::dutch_collateral_auctions(0).unwrap().start_time,
+			51
+		);
+
+		assert_ok!(AuctionManagerModule::take(
+			Origin::signed(BOB),
+			0,
+			10,
+			Price::saturating_from_integer(3)
+		));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 978);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 1010);
+	});
+}
+
+#[test]
+fn cancel_dutch_collateral_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_dutch_collateral_auction(&ALICE, BTC, 100, 50));
+		assert_ok!(AuctionManagerModule::take(
+			Origin::signed(BOB),
+			0,
+			20,
+			Price::saturating_from_integer(2)
+		));
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 80);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 28);
+		assert_eq!(System::refs(&ALICE), 1);
+
+		mock_shutdown();
+		assert_noop!(
+			AuctionManagerModule::take(Origin::signed(BOB), 0, 20, Price::saturating_from_integer(2)),
+			Error::<Runtime>::MustBeforeShutdown,
+		);
+		assert_ok!(AuctionManagerModule::cancel(Origin::none(), 0));
+		let cancel_auction_event = TestEvent::auction_manager(RawEvent::CancelAuction(0));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == cancel_auction_event));
+
+		assert_eq!(Tokens::free_balance(BTC, &ALICE), 1052);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 28);
+		assert_eq!(System::refs(&ALICE), 0);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 0);
+		assert_eq!(AuctionManagerModule::dutch_collateral_auctions(0).is_some(), false);
+		assert_eq!(AuctionModule::auction_info(0).is_some(), false);
+	});
+}
+
+#[test]
+fn get_minimum_next_bid_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(AuctionManagerModule::get_minimum_next_bid(0, 1), None);
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		assert_ok!(AuctionManagerModule::new_debit_auction(200, 100));
+		assert_ok!(AuctionManagerModule::new_surplus_auction(100));
+		assert_ok!(AuctionManagerModule::new_dutch_collateral_auction(&ALICE, BTC, 10, 100));
+
+		assert_eq!(AuctionManagerModule::get_minimum_next_bid(0, 1), Some(5));
+		assert_eq!(AuctionManagerModule::get_minimum_next_bid(0, 2001), Some(10));
+		assert_eq!(AuctionManagerModule::get_minimum_next_bid(1, 1), Some(100));
+		assert_eq!(AuctionManagerModule::get_minimum_next_bid(2, 1), Some(1));
+		assert_eq!(AuctionManagerModule::get_minimum_next_bid(3, 1), None);
+
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 0, 200));
+		assert_eq!(AuctionManagerModule::get_minimum_next_bid(0, 1), Some(210));
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 1, 100));
+		assert_eq!(AuctionManagerModule::get_minimum_next_bid(1, 1), Some(105));
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 2, 10));
+		assert_eq!(AuctionManagerModule::get_minimum_next_bid(2, 1), Some(11));
+	});
+}
+
+#[test]
+fn get_auction_bid_state_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(AuctionManagerModule::get_auction_bid_state(0, 1), None);
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		assert_ok!(AuctionManagerModule::new_debit_auction(200, 100));
+		assert_ok!(AuctionManagerModule::new_surplus_auction(100));
+		assert_ok!(AuctionManagerModule::new_dutch_collateral_auction(&ALICE, BTC, 10, 100));
+
+		let mut auction_ids = AuctionManagerModule::active_auction_ids();
+		auction_ids.sort();
+		assert_eq!(auction_ids, vec![0, 1, 2, 3]);
+
+		assert_eq!(
+			AuctionManagerModule::get_auction_bid_state(0, 1),
+			Some(AuctionBidState {
+				auction_type: AuctionType::Collateral,
+				currency_id: BTC,
+				stage: AuctionStage::Forward,
+				last_bid: None,
+				minimum_next_bid: Some(5),
+				current_price: None,
+				end_time: None,
+				time_left: None,
+				amount: 10,
+				payment: 0,
+			})
+		);
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 0, 200));
+		assert_eq!(
+			AuctionManagerModule::get_auction_bid_state(0, 11),
+			Some(AuctionBidState {
+				auction_type: AuctionType::Collateral,
+				currency_id: BTC,
+				stage: AuctionStage::Reverse,
+				last_bid: Some((BOB, 200)),
+				minimum_next_bid: Some(210),
+				current_price: None,
+				end_time: Some(101),
+				time_left: Some(90),
+				amount: 5,
+				payment: 100,
+			})
+		);
+
+		assert_eq!(
+			AuctionManagerModule::get_auction_bid_state(1, 1),
+			Some(AuctionBidState {
+				auction_type: AuctionType::Debit,
+				currency_id: ACA,
+				stage: AuctionStage::Reverse,
+				last_bid: None,
+				minimum_next_bid: Some(100),
+				current_price: None,
+				end_time: Some(101),
+				time_left: Some(100),
+				amount: 200,
+				payment: 0,
+			})
+		);
+
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 2, 10));
+		assert_eq!(
+			AuctionManagerModule::get_auction_bid_state(2, 1),
+			Some(AuctionBidState {
+				auction_type: AuctionType::Surplus,
+				currency_id: AUSD,
+				stage: AuctionStage::Forward,
+				last_bid: Some((BOB, 10)),
+				minimum_next_bid: Some(11),
+				current_price: None,
+				end_time: Some(101),
+				time_left: Some(100),
+				amount: 100,
+				payment: 10,
+			})
+		);
+
+		assert_eq!(
+			AuctionManagerModule::get_auction_bid_state(3, 11),
+			Some(AuctionBidState {
+				auction_type: AuctionType::DutchCollateral,
+				currency_id: BTC,
+				stage: AuctionStage::Descending,
+				last_bid: None,
+				minimum_next_bid: None,
+				current_price: Some(Price::saturating_from_rational(99, 100)),
+				end_time: Some(51),
+				time_left: Some(40),
+				amount: 10,
+				payment: 9,
+			})
+		);
+	});
+}
+
+#[test]
+fn batch_bid_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, BTC, 20));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		assert_ok!(AuctionManagerModule::new_dutch_collateral_auction(&ALICE, BTC, 10, 100));
+
+		assert_noop!(
+			AuctionManagerModule::batch_bid(Origin::signed(BOB), vec![(0, 50), (1, 50), (0, 60), (1, 60)]),
+			Error::<Runtime>::TooManyBids,
+		);
+		assert_noop!(
+			AuctionManagerModule::batch_bid(Origin::signed(BOB), vec![(0, 50), (3, 60)]),
+			Error::<Runtime>::AuctionNotExists,
+		);
+		assert_noop!(
+			AuctionManagerModule::batch_bid(Origin::signed(BOB), vec![(0, 50), (2, 60)]),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+
+		assert_ok!(AuctionManagerModule::batch_bid(
+			Origin::signed(BOB),
+			vec![(0, 50), (1, 60)]
+		));
+		let bid_event = TestEvent::auction_manager(RawEvent::Bid(1, BOB, 60));
+		assert!(System::events().iter().any(|record| record.event == bid_event));
+		assert_eq!(AuctionModule::auction_info(0).unwrap().bid, Some((BOB, 50)));
+		assert_eq!(AuctionModule::auction_info(0).unwrap().end, Some(101));
+		assert_eq!(AuctionModule::auction_info(1).unwrap().bid, Some((BOB, 60)));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 110);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 890);
+
+		// all bids are rejected if any of them fails
+		assert_noop!(
+			AuctionManagerModule::batch_bid(Origin::signed(CAROL), vec![(0, 100), (1, 61)]),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+		assert_ok!(AuctionManagerModule::batch_bid(
+			Origin::signed(CAROL),
+			vec![(0, 100), (1, 65)]
+		));
+		assert_eq!(AuctionModule::auction_info(0).unwrap().bid, Some((CAROL, 100)));
+		assert_eq!(AuctionModule::auction_info(1).unwrap().bid, Some((CAROL, 65)));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 165);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(Tokens::free_balance(AUSD, &CAROL), 835);
+
+		// can not bid on the auction after it has ended
+		System::set_block_number(101);
+		assert_noop!(
+			AuctionManagerModule::batch_bid(Origin::signed(BOB), vec![(0, 200)]),
+			Error::<Runtime>::AuctionExpired,
+		);
+
+		mock_shutdown();
+		assert_noop!(
+			AuctionManagerModule::batch_bid(Origin::signed(BOB), vec![(1, 100)]),
+			Error::<Runtime>::MustBeforeShutdown,
+		);
+	});
+}
+
+#[test]
+fn partial_bid_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		assert_eq!(System::refs(&ALICE), 1);
+
+		assert_noop!(
+			AuctionManagerModule::partial_bid(Origin::signed(BOB), 1, 4, 40),
+			Error::<Runtime>::AuctionNotExists,
+		);
+		assert_noop!(
+			AuctionManagerModule::partial_bid(Origin::signed(BOB), 0, 0, 40),
+			Error::<Runtime>::InvalidAmount,
+		);
+		assert_noop!(
+			AuctionManagerModule::partial_bid(Origin::signed(BOB), 0, 10, 40),
+			Error::<Runtime>::InvalidAmount,
+		);
+		// both the split part and the rest must be at least `MinimumCollateralSplitAmount`
+		assert_noop!(
+			AuctionManagerModule::partial_bid(Origin::signed(BOB), 0, 1, 40),
+			Error::<Runtime>::InvalidAmount,
+		);
+		assert_noop!(
+			AuctionManagerModule::partial_bid(Origin::signed(BOB), 0, 9, 90),
+			Error::<Runtime>::InvalidAmount,
+		);
+		assert_noop!(
+			AuctionManagerModule::partial_bid(Origin::signed(BOB), 0, 4, 0),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+
+		assert_ok!(AuctionManagerModule::partial_bid(Origin::signed(BOB), 0, 4, 40));
+		let split_event = TestEvent::auction_manager(RawEvent::CollateralAuctionSplit(0, 1, 4, 40));
+		assert!(System::events().iter().any(|record| record.event == split_event));
+		let collateral_auction = AuctionManagerModule::collateral_auctions(0).unwrap();
+		assert_eq!(collateral_auction.initial_amount, 6);
+		assert_eq!(collateral_auction.amount, 6);
+		assert_eq!(collateral_auction.target, 60);
+		let split_collateral_auction = AuctionManagerModule::collateral_auctions(1).unwrap();
+		assert_eq!(split_collateral_auction.refund_recipient, ALICE);
+		assert_eq!(split_collateral_auction.amount, 4);
+		assert_eq!(split_collateral_auction.target, 40);
+		assert_eq!(split_collateral_auction.start_time, 1);
+		assert_eq!(AuctionModule::auction_info(0).unwrap().bid, None);
+		assert_eq!(AuctionModule::auction_info(1).unwrap().bid, Some((BOB, 40)));
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 10);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 100);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 40);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 960);
+		assert_eq!(System::refs(&ALICE), 2);
+
+		assert_noop!(
+			AuctionManagerModule::partial_bid(Origin::signed(CAROL), 1, 2, 30),
+			Error::<Runtime>::AuctionAlreadyBid,
+		);
+
+		// the target of the split auction is rounded up
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, BTC, 7));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 7, 100));
+		assert_ok!(AuctionManagerModule::partial_bid(Origin::signed(CAROL), 2, 3, 43));
+		assert_eq!(AuctionManagerModule::collateral_auctions(3).unwrap().target, 43);
+		assert_eq!(AuctionManagerModule::collateral_auctions(2).unwrap().target, 57);
+
+		mock_shutdown();
+		assert_noop!(
+			AuctionManagerModule::partial_bid(Origin::signed(CAROL), 0, 3, 30),
+			Error::<Runtime>::MustBeforeShutdown,
+		);
+	});
+}
+
+#[test]
+fn get_worker_bid_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let config = BiddingConfig {
+			min_discount: Rate::saturating_from_rational(1, 10),
+			max_payment: 1000,
+		};
+		assert_eq!(AuctionManagerModule::get_worker_bid(0, 1, &config), None);
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		assert_eq!(AuctionManagerModule::get_worker_bid(0, 1, &config), Some((5, 5)));
+
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 0, 9));
+		assert_eq!(AuctionManagerModule::get_worker_bid(0, 1, &config), None);
+
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_integer(20)));
+		assert_eq!(AuctionManagerModule::get_worker_bid(0, 1, &config), Some((14, 14)));
+
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 0, 100));
+		assert_eq!(AuctionManagerModule::get_worker_bid(0, 1, &config), Some((105, 100)));
+		assert_eq!(
+			AuctionManagerModule::get_worker_bid(
+				0,
+				1,
+				&BiddingConfig {
+					min_discount: Rate::saturating_from_rational(1, 10),
+					max_payment: 99,
+				}
+			),
+			None
+		);
+
+		MockPriceSource::set_relative_price(None);
+		assert_eq!(AuctionManagerModule::get_worker_bid(0, 1, &config), None);
+	});
+}
+
+#[test]
+fn bidding_offchain_worker_work() {
+	let (offchain, _) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let mut ext = ExtBuilder::default().build();
+	ext.register_extension(OffchainExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&ALICE, BTC, 20));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		UintAuthorityId::set_all_keys(vec![BOB as u64]);
+
+		// disabled without config
+		assert_ok!(AuctionManagerModule::_bidding_offchain_worker(1));
+		assert_eq!(pool_state.read().transactions.len(), 0);
+
+		StorageValueRef::persistent(&OFFCHAIN_BIDDING_CONFIG).set(&BiddingConfig {
+			min_discount: Rate::saturating_from_rational(1, 10),
+			max_payment: 1000,
+		});
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 1, 5));
+		assert_ok!(AuctionManagerModule::_bidding_offchain_worker(1));
+		assert_eq!(pool_state.read().transactions.len(), 1);
+
+		// skip the auction which the local bidder is winning
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, Some((0, ())));
+		assert_eq!(
+			tx.call,
+			Call::AuctionManagerModule(crate::Call::batch_bid(vec![(0, 5)]))
+		);
+	});
+}
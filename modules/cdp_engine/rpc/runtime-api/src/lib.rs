@@ -0,0 +1,160 @@
+//! Runtime API definition for cdp engine module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Codec, Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sp_runtime::traits::{MaybeDisplay, MaybeFromStr};
+use sp_std::prelude::*;
+use support::{Price, Rate, Ratio};
+
+/// The position of a CDP together with its health.
+///
+/// The stability fee accrued on the debit is not supported, as the debit
+/// exchange rate at which the debit was issued is not tracked. The accrued
+/// stability fee is already included in `debit_value`.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct CdpPosition<Balance> {
+	/// The amount of collateral locked in the CDP.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub collateral: Balance,
+	/// The debit of the CDP.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub debit: Balance,
+	/// The value of the debit in stable currency.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub debit_value: Balance,
+	/// The current collateral ratio, `None` if there is no price for the
+	/// collateral.
+	pub collateral_ratio: Option<Ratio>,
+	/// The collateral ratio at which the CDP will be liquidated.
+	pub liquidation_ratio: Ratio,
+	/// The collateral ratio required to issue more debit.
+	pub required_collateral_ratio: Option<Ratio>,
+	/// The collateral price at which the CDP will be liquidated.
+	pub liquidation_price: Option<Price>,
+	/// Whether the CDP can be liquidated now, the CDP in a cross-margin vault
+	/// can be liquidated if the vault is unsafe.
+	pub is_unsafe: bool,
+	/// The debit value that can still be issued before reaching the required
+	/// collateral ratio.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub available_debit_value: Balance,
+	/// The stability fee rate per block.
+	pub stability_fee: Rate,
+}
+
+/// The utilisation of the hard cap of total debit value of a collateral type.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct DebitCeilingUtilisation<Balance> {
+	/// The total debit value of all CDPs under the collateral type.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub total_debit_value: Balance,
+	/// The hard cap of total debit value.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub maximum_total_debit_value: Balance,
+	/// The total debit value divided by the hard cap, `None` if the hard cap
+	/// is zero.
+	pub utilisation: Option<Ratio>,
+}
+
+/// The origin which updated the risk management params.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum RiskParamsUpdateOrigin<AccountId> {
+	Root,
+	Signed(AccountId),
+	Collective(u32, u32),
+	StabilityFeeController,
+	Other,
+}
+
+/// A record of the risk management params history of a collateral type.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct RiskParamsRecord<AccountId, BlockNumber, Balance> {
+	/// The block number when the params updated.
+	pub block_number: BlockNumber,
+	/// The origin which updated the params.
+	pub origin: RiskParamsUpdateOrigin<AccountId>,
+	/// The extra stability fee rate.
+	pub stability_fee: Option<Rate>,
+	/// The liquidation ratio.
+	pub liquidation_ratio: Option<Ratio>,
+	/// The liquidation penalty rate.
+	pub liquidation_penalty: Option<Rate>,
+	/// The required collateral ratio.
+	pub required_collateral_ratio: Option<Ratio>,
+	/// The hard cap of total debit value.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub maximum_total_debit_value: Balance,
+	/// The partial liquidation buffer.
+	pub partial_liquidation_buffer: Option<Ratio>,
+	/// Whether the collateral type is retired.
+	pub retired: bool,
+}
+
+#[cfg(feature = "std")]
+fn serialize_as_string<S: Serializer, T: std::fmt::Display>(t: &T, serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_str(&t.to_string())
+}
+
+#[cfg(feature = "std")]
+fn deserialize_from_string<'de, D: Deserializer<'de>, T: std::str::FromStr>(deserializer: D) -> Result<T, D::Error> {
+	let s = String::deserialize(deserializer)?;
+	s.parse::<T>()
+		.map_err(|_| serde::de::Error::custom("Parse from string failed"))
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait CdpEngineApi<AccountId, CurrencyId, Balance, BlockNumber> where
+		AccountId: Codec,
+		CurrencyId: Codec,
+		Balance: Codec + MaybeDisplay + MaybeFromStr,
+		BlockNumber: Codec,
+	{
+		fn get_cdp_position(
+			who: AccountId,
+			currency_id: CurrencyId,
+		) -> CdpPosition<Balance>;
+
+		fn get_debit_ceiling_utilisation(
+			currency_id: CurrencyId,
+		) -> DebitCeilingUtilisation<Balance>;
+
+		fn get_risk_params_history(
+			currency_id: CurrencyId,
+		) -> Vec<RiskParamsRecord<AccountId, BlockNumber, Balance>>;
+	}
+}
@@ -0,0 +1,137 @@
+//! RPC interface for the cdp engine module.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use module_cdp_engine_rpc_runtime_api::{CdpPosition, DebitCeilingUtilisation, RiskParamsRecord};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
+};
+use std::sync::Arc;
+
+pub use self::gen_client::Client as CdpEngineClient;
+pub use module_cdp_engine_rpc_runtime_api::CdpEngineApi as CdpEngineRuntimeApi;
+
+#[rpc]
+pub trait CdpEngineApi<BlockHash, AccountId, CurrencyId, Balance, BlockNumber> {
+	#[rpc(name = "cdpEngine_getCdpPosition")]
+	fn get_cdp_position(
+		&self,
+		who: AccountId,
+		currency_id: CurrencyId,
+		at: Option<BlockHash>,
+	) -> Result<CdpPosition<Balance>>;
+
+	#[rpc(name = "cdpEngine_getDebitCeilingUtilisation")]
+	fn get_debit_ceiling_utilisation(
+		&self,
+		currency_id: CurrencyId,
+		at: Option<BlockHash>,
+	) -> Result<DebitCeilingUtilisation<Balance>>;
+
+	#[rpc(name = "cdpEngine_getRiskParamsHistory")]
+	fn get_risk_params_history(
+		&self,
+		currency_id: CurrencyId,
+		at: Option<BlockHash>,
+	) -> Result<Vec<RiskParamsRecord<AccountId, BlockNumber, Balance>>>;
+}
+
+/// A struct that implements the [`CdpEngineApi`].
+pub struct CdpEngine<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> CdpEngine<C, B> {
+	/// Create new `CdpEngine` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		CdpEngine {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AccountId, CurrencyId, Balance, BlockNumber>
+	CdpEngineApi<<Block as BlockT>::Hash, AccountId, CurrencyId, Balance, BlockNumber> for CdpEngine<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: CdpEngineRuntimeApi<Block, AccountId, CurrencyId, Balance, BlockNumber>,
+	AccountId: Codec,
+	CurrencyId: Codec,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+	BlockNumber: Codec,
+{
+	fn get_cdp_position(
+		&self,
+		who: AccountId,
+		currency_id: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<CdpPosition<Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.get_cdp_position(&at, who, currency_id).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get cdp position.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_debit_ceiling_utilisation(
+		&self,
+		currency_id: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<DebitCeilingUtilisation<Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.get_debit_ceiling_utilisation(&at, currency_id)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get debit ceiling utilisation.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn get_risk_params_history(
+		&self,
+		currency_id: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<RiskParamsRecord<AccountId, BlockNumber, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.get_risk_params_history(&at, currency_id).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get risk params history.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+}
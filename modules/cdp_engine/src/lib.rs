@@ -13,13 +13,14 @@ use frame_support::{
 	debug, decl_error, decl_event, decl_module, decl_storage, ensure,
 	traits::{EnsureOrigin, Get},
 	weights::{DispatchClass, Weight},
+	IterableStorageMap,
 };
 use frame_system::{
-	self as system, ensure_none,
+	self as system, ensure_signed,
 	offchain::{SendTransactionTypes, SubmitTransaction},
 };
 use loans::Position;
-use orml_traits::Change;
+use orml_traits::{Change, DataProvider, MultiReservableCurrency};
 use orml_utilities::{with_transaction_result, IterableStorageDoubleMapExtended, OffchainErr};
 use primitives::{Amount, Balance, CurrencyId};
 use sp_runtime::{
@@ -28,7 +29,7 @@ use sp_runtime::{
 		storage_lock::{StorageLock, Time},
 		Duration,
 	},
-	traits::{BlakeTwo256, Bounded, Convert, Hash, Saturating, Zero},
+	traits::{BadOrigin, BlakeTwo256, Bounded, Convert, Hash, Saturating, Zero},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
@@ -36,8 +37,8 @@ use sp_runtime::{
 };
 use sp_std::{marker, prelude::*};
 use support::{
-	CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, ExchangeRate, Price, PriceProvider, Rate, Ratio,
-	RiskManager,
+	AuctionManager, CDPTreasury, CDPTreasuryExtended, CollateralRegistry, DEXManager, EmergencyShutdown, ExchangeRate,
+	Price, PriceProvider, Rate, Ratio, RiskManager,
 };
 
 mod debit_exchange_rate_convertor;
@@ -52,7 +53,19 @@ pub trait WeightInfo {
 	fn set_global_params() -> Weight;
 	fn liquidate_by_auction() -> Weight;
 	fn liquidate_by_dex() -> Weight;
+	fn liquidate_cross_margin_vault(c: u32) -> Weight;
 	fn settle() -> Weight;
+	fn set_partial_liquidation_buffer() -> Weight;
+	fn register_keeper() -> Weight;
+	fn deregister_keeper() -> Weight;
+	fn set_keeper_reward_rate() -> Weight;
+	fn set_cross_margin_liquidation_order(c: u32) -> Weight;
+	fn set_stability_fee_controller() -> Weight;
+	fn on_initialize() -> Weight;
+	fn register_collateral_type() -> Weight;
+	fn pause_collateral_type() -> Weight;
+	fn resume_collateral_type() -> Weight;
+	fn retire_collateral_type() -> Weight;
 }
 
 const OFFCHAIN_WORKER_DATA: &[u8] = b"acala/cdp-engine/data/";
@@ -70,9 +83,6 @@ pub trait Trait: SendTransactionTypes<Call<Self>> + system::Trait + loans::Trait
 	/// do this.
 	type UpdateOrigin: EnsureOrigin<Self::Origin>;
 
-	/// The list of valid collateral currency types
-	type CollateralCurrencyIds: Get<Vec<CurrencyId>>;
-
 	/// The default liquidation ratio for all collateral types of CDP
 	type DefaultLiquidationRatio: Get<Ratio>;
 
@@ -94,9 +104,16 @@ pub trait Trait: SendTransactionTypes<Call<Self>> + system::Trait + loans::Trait
 	/// The CDP treasury to maintain bad debts and surplus generated by CDPs
 	type CDPTreasury: CDPTreasuryExtended<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
 
+	/// Auction manager holding the collateral in auction
+	type AuctionManagerHandler: AuctionManager<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
 	/// The price source of all types of currencies related to CDP
 	type PriceSource: PriceProvider<CurrencyId>;
 
+	/// The market price source of stablecoin in USD for the stability fee
+	/// controller, such as the oracle or the DEX
+	type StableCurrencyPriceSource: DataProvider<CurrencyId, Price>;
+
 	/// The DEX participating in liquidation
 	type DEX: DEXManager<Self::AccountId, CurrencyId, Balance>;
 
@@ -109,6 +126,31 @@ pub trait Trait: SendTransactionTypes<Call<Self>> + system::Trait + loans::Trait
 	/// Emergency shutdown.
 	type EmergencyShutdown: EmergencyShutdown;
 
+	/// Currency for reserving the deposit of keepers
+	type Currency: MultiReservableCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+	/// The deposit in stable currency reserved when an account registers as
+	/// a keeper
+	type KeeperDeposit: Get<Balance>;
+
+	/// The amount of the keeper deposit slashed when a signed liquidation or
+	/// settlement of the keeper fails
+	type KeeperSlashAmount: Get<Balance>;
+
+	/// The max number of records in the risk management params history of
+	/// each collateral type and in the global stability fee history, the
+	/// oldest record is dropped when exceeded
+	type MaxRiskParamsHistory: Get<u32>;
+
+	/// Convert the origins of `UpdateOrigin` other than root and signed, such
+	/// as a council, into the origin recorded in the risk management params
+	/// history
+	type RiskParamsUpdateOriginConvertor: Convert<Self::Origin, RiskParamsUpdateOrigin<Self::AccountId>>;
+
+	/// The collateral types of the runtime constant before the collateral
+	/// registry, which are registered once by the runtime upgrade
+	type LegacyCollateralCurrencyIds: Get<Vec<CurrencyId>>;
+
 	/// Weight information for the extrinsics in this module.
 	type WeightInfo: WeightInfo;
 }
@@ -148,6 +190,91 @@ pub struct RiskManagementParams {
 	pub required_collateral_ratio: Option<Ratio>,
 }
 
+/// Params of the controller which adjusts the global stability fee
+/// automatically to defend the peg of stablecoin
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default)]
+pub struct StabilityFeeControllerParams<BlockNumber> {
+	/// The target market price of stablecoin in USD
+	pub target_price: Price,
+
+	/// The global stability fee is not adjusted if the deviation of the market
+	/// price from the target price is within the tolerance
+	pub price_tolerance: Price,
+
+	/// The lower bound of the global stability fee
+	pub min_stability_fee: Rate,
+
+	/// The upper bound of the global stability fee
+	pub max_stability_fee: Rate,
+
+	/// The change of the global stability fee in each adjustment
+	pub step: Rate,
+
+	/// The max total change of the global stability fee in one era
+	pub max_change_per_era: Rate,
+
+	/// The number of blocks of one era
+	pub era_length: BlockNumber,
+}
+
+/// The status of a registered collateral type
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum CollateralStatus {
+	/// CDPs can deposit collateral and issue debit
+	Active,
+	/// CDPs cannot deposit collateral or issue debit, but can still withdraw
+	/// collateral, repay debit and be liquidated
+	Paused,
+}
+
+/// The origin which updated the risk management params
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub enum RiskParamsUpdateOrigin<AccountId> {
+	/// Updated by root
+	Root,
+	/// Updated by a signed account
+	Signed(AccountId),
+	/// Updated by a collective, with the number of members approving the
+	/// proposal and the total number of members
+	Collective(u32, u32),
+	/// Adjusted by the stability fee controller
+	StabilityFeeController,
+	/// Updated by other origins of `UpdateOrigin`
+	Other,
+}
+
+/// A record of the risk management params history
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct RiskParamsRecord<BlockNumber, AccountId> {
+	/// The block number when the params updated
+	pub block_number: BlockNumber,
+
+	/// The origin which updated the params
+	pub origin: RiskParamsUpdateOrigin<AccountId>,
+
+	/// The params after the update
+	pub params: RiskManagementParams,
+
+	/// The partial liquidation buffer after the update
+	pub partial_liquidation_buffer: Option<Ratio>,
+
+	/// Whether the collateral type is retired by the update
+	pub retired: bool,
+}
+
+/// A record of the global stability fee history
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct GlobalStabilityFeeRecord<BlockNumber, AccountId> {
+	/// The block number when the global stability fee updated
+	pub block_number: BlockNumber,
+
+	/// The origin which updated the global stability fee
+	pub origin: RiskParamsUpdateOrigin<AccountId>,
+
+	/// The global stability fee after the update
+	pub global_stability_fee: Rate,
+}
+
 // typedef to help polkadot.js disambiguate Change with different generic
 // parameters
 type ChangeOptionRate = Change<Option<Rate>>;
@@ -158,13 +285,20 @@ decl_event!(
 	pub enum Event<T>
 	where
 		<T as system::Trait>::AccountId,
+		<T as system::Trait>::BlockNumber,
 		CurrencyId = CurrencyId,
 		Balance = Balance,
 	{
 		/// Liquidate the unsafe CDP. \[collateral_type, owner, collateral_amount, bad_debt_value, liquidation_strategy\]
 		LiquidateUnsafeCDP(CurrencyId, AccountId, Balance, Balance, LiquidationStrategy),
+		/// Partially liquidate the unsafe CDP. \[collateral_type, owner, collateral_amount, bad_debt_value, liquidation_strategy\]
+		PartialLiquidateUnsafeCDP(CurrencyId, AccountId, Balance, Balance, LiquidationStrategy),
 		/// Settle the CDP has debit. [collateral_type, owner]
 		SettleCDPInDebit(CurrencyId, AccountId),
+		/// Close the CDP has debit by swapping collateral with DEX. \[collateral_type, owner, sold_collateral_amount, refund_collateral_amount, debit_value\]
+		CloseCDPInDebitByDEX(CurrencyId, AccountId, Balance, Balance, Balance),
+		/// Expand the CDP by issuing debit and swapping it to collateral with DEX. \[collateral_type, owner, increase_collateral_amount, increase_debit_value\]
+		ExpandCDPCollateralByDEX(CurrencyId, AccountId, Balance, Balance),
 		/// The stability fee for specific collateral type updated. \[collateral_type, new_stability_fee\]
 		StabilityFeeUpdated(CurrencyId, Option<Rate>),
 		/// The liquidation fee for specific collateral type updated. \[collateral_type, new_liquidation_ratio\]
@@ -177,6 +311,38 @@ decl_event!(
 		MaximumTotalDebitValueUpdated(CurrencyId, Balance),
 		/// The global stability fee for all types of collateral updated. \[new_global_stability_fee\]
 		GlobalStabilityFeeUpdated(Rate),
+		/// The partial liquidation buffer for specific collateral type updated. \[collateral_type, new_partial_liquidation_buffer\]
+		PartialLiquidationBufferUpdated(CurrencyId, Option<Ratio>),
+		/// The account registered as a keeper. \[keeper, deposit\]
+		KeeperRegistered(AccountId, Balance),
+		/// The account deregistered as a keeper. \[keeper\]
+		KeeperDeregistered(AccountId),
+		/// The keeper is rewarded for liquidating a CDP. \[keeper, reward_amount\]
+		KeeperRewarded(AccountId, Balance),
+		/// The deposit of the keeper is slashed for a failed call. \[keeper, slashed_amount\]
+		KeeperSlashed(AccountId, Balance),
+		/// The share of the liquidation penalty rewarded to keepers updated. \[new_keeper_reward_rate\]
+		KeeperRewardRateUpdated(Rate),
+		/// The cross-margin mode of the account updated. \[owner, enabled\]
+		CrossMarginModeUpdated(AccountId, bool),
+		/// The collateral types of cross-margin vaults and their liquidation order updated. \[new_liquidation_order\]
+		CrossMarginLiquidationOrderUpdated(Vec<CurrencyId>),
+		/// Sell the collateral of the liquidated cross-margin vault. \[owner, collateral_type, collateral_amount, target_stable_amount, liquidation_strategy\]
+		LiquidateCrossMarginCollateral(AccountId, CurrencyId, Balance, Balance, LiquidationStrategy),
+		/// Liquidate the unsafe cross-margin vault. \[owner, bad_debt_value\]
+		LiquidateCrossMarginVault(AccountId, Balance),
+		/// The params of the stability fee controller updated. \[new_controller_params\]
+		StabilityFeeControllerUpdated(Option<StabilityFeeControllerParams<BlockNumber>>),
+		/// The global stability fee adjusted by the stability fee controller. \[stable_currency_market_price, new_global_stability_fee\]
+		GlobalStabilityFeeAdjusted(Price, Rate),
+		/// The collateral type registered. \[collateral_type\]
+		CollateralTypeRegistered(CurrencyId),
+		/// The collateral type paused. \[collateral_type\]
+		CollateralTypePaused(CurrencyId),
+		/// The collateral type resumed. \[collateral_type\]
+		CollateralTypeResumed(CurrencyId),
+		/// The collateral type retired. \[collateral_type\]
+		CollateralTypeRetired(CurrencyId),
 	}
 );
 
@@ -191,8 +357,18 @@ decl_error! {
 		BelowLiquidationRatio,
 		/// The CDP must be unsafe to be liquidated
 		MustBeUnsafe,
+		/// The CDP must be safe to be closed by DEX
+		MustBeSafe,
+		/// The target collateral ratio cannot be reached by expanding the CDP
+		InvalidTargetCollateralRatio,
 		/// Invalid collateral type
 		InvalidCollateralType,
+		/// The collateral type is already registered
+		CollateralTypeAlreadyRegistered,
+		/// The collateral type is paused, cannot deposit collateral or issue debit
+		CollateralTypePaused,
+		/// There are still collateral or debit of the collateral type in CDPs, CDP treasury or auctions
+		CollateralTypeInUse,
 		/// Remain debit value in CDP below the dust amount
 		RemainDebitValueTooSmall,
 		/// Feed price is invalid
@@ -203,11 +379,24 @@ decl_error! {
 		AlreadyShutdown,
 		/// Must after system shutdown
 		MustAfterShutdown,
+		/// The account is already a keeper
+		AlreadyKeeper,
+		/// The account is not a keeper
+		NotKeeper,
+		/// The keeper reward rate must not exceed 100%
+		InvalidKeeperRewardRate,
+		/// The liquidation order contains invalid or duplicated collateral types
+		InvalidLiquidationOrder,
+		/// The params of the stability fee controller are invalid
+		InvalidStabilityFeeControllerParams,
 	}
 }
 
 decl_storage! {
 	trait Store for Module<T: Trait> as CDPEngine {
+		/// Mapping from registered collateral type to its status
+		pub CollateralTypes get(fn collateral_types): map hasher(twox_64_concat) CurrencyId => Option<CollateralStatus>;
+
 		/// Mapping from collateral type to its exchange rate of debit units and debit value
 		pub DebitExchangeRate get(fn debit_exchange_rate): map hasher(twox_64_concat) CurrencyId => Option<ExchangeRate>;
 
@@ -216,6 +405,48 @@ decl_storage! {
 
 		/// Mapping from collateral type to its risk management params
 		pub CollateralParams get(fn collateral_params): map hasher(twox_64_concat) CurrencyId => RiskManagementParams;
+
+		/// Mapping from collateral type to the history of its risk management params,
+		/// ordered from the oldest to the newest and bounded by `MaxRiskParamsHistory`
+		pub RiskParamsHistory get(fn risk_params_history): map hasher(twox_64_concat) CurrencyId => Vec<RiskParamsRecord<T::BlockNumber, T::AccountId>>;
+
+		/// The history of the global stability fee, ordered from the oldest to the newest
+		/// and bounded by `MaxRiskParamsHistory`. The adjustments by the stability fee
+		/// controller are recorded once per era.
+		pub GlobalStabilityFeeHistory get(fn global_stability_fee_history): Vec<GlobalStabilityFeeRecord<T::BlockNumber, T::AccountId>>;
+
+		/// Mapping from collateral type to the buffer above the liquidation ratio that partial
+		/// liquidation restores unsafe CDPs to, `None` means unsafe CDPs are fully liquidated
+		pub PartialLiquidationBuffer get(fn partial_liquidation_buffer): map hasher(twox_64_concat) CurrencyId => Option<Ratio>;
+
+		/// Mapping from keeper to its reserved deposit
+		pub Keepers get(fn keepers): map hasher(twox_64_concat) T::AccountId => Option<Balance>;
+
+		/// The share of the liquidation penalty rewarded to keepers
+		pub KeeperRewardRate get(fn keeper_reward_rate): Rate;
+
+		/// The accounts whose positions of the cross-margin collateral types are
+		/// in one cross-margin vault
+		pub CrossMarginAccounts get(fn is_cross_margin): map hasher(twox_64_concat) T::AccountId => bool;
+
+		/// The collateral types can be in cross-margin vaults, which are sold in this
+		/// order when the vault is liquidated
+		pub CrossMarginLiquidationOrder get(fn cross_margin_liquidation_order): Vec<CurrencyId>;
+
+		/// The params of the controller which adjusts the global stability fee,
+		/// `None` means the controller is disabled
+		pub StabilityFeeController get(fn stability_fee_controller): Option<StabilityFeeControllerParams<T::BlockNumber>>;
+
+		/// The start block of current era of the stability fee controller and the
+		/// total change of the global stability fee in this era
+		pub StabilityFeeControllerEra get(fn stability_fee_controller_era): (T::BlockNumber, Rate);
+
+		/// The CDPs liquidated or settled in current block, keepers failing to process
+		/// them again lose the race and are not slashed. Cleared when the block finalizes.
+		pub ProcessedCDPs get(fn processed_cdps): Vec<(CurrencyId, T::AccountId)>;
+
+		/// Whether the legacy collateral types have been migrated into `CollateralTypes`.
+		CollateralTypesMigrated build(|_: &GenesisConfig| true): bool;
 	}
 
 	add_extra_genesis {
@@ -230,6 +461,7 @@ decl_storage! {
 				required_collateral_ratio,
 				maximum_total_debit_value,
 			)| {
+				CollateralTypes::insert(currency_id, CollateralStatus::Active);
 				CollateralParams::insert(currency_id, RiskManagementParams {
 					maximum_total_debit_value: *maximum_total_debit_value,
 					stability_fee: *stability_fee,
@@ -247,9 +479,6 @@ decl_module! {
 		type Error = Error<T>;
 		fn deposit_event() = default;
 
-		/// The list of valid collateral currency types
-		const CollateralCurrencyIds: Vec<CurrencyId> = T::CollateralCurrencyIds::get();
-
 		/// The minimum debit value allowed exists in CDP which has debit amount to avoid dust
 		const MinimumDebitValue: Balance = T::MinimumDebitValue::get();
 
@@ -271,29 +500,49 @@ decl_module! {
 		/// if the liquidation penalty rate for specific collateral is `None`, it works.
 		const DefaultLiquidationPenalty: Rate = T::DefaultLiquidationPenalty::get();
 
+		/// The deposit in stable currency reserved when an account registers as a keeper
+		const KeeperDeposit: Balance = T::KeeperDeposit::get();
+
+		/// The amount of the keeper deposit slashed when a signed call of the keeper fails
+		const KeeperSlashAmount: Balance = T::KeeperSlashAmount::get();
+
+		/// The max number of records in the risk management params history of each collateral type
+		const MaxRiskParamsHistory: u32 = T::MaxRiskParamsHistory::get();
+
 		/// Liquidate unsafe CDP
 		///
-		/// The dispatch origin of this call must be _None_.
+		/// The dispatch origin of this call must be _None_, or _Signed_ by a keeper
+		/// who will be rewarded with a share of the liquidation penalty. If the
+		/// liquidation of a keeper fails, its deposit is slashed instead, unless
+		/// the CDP has been liquidated by another call in the same block.
 		///
 		/// - `currency_id`: CDP's collateral type.
 		/// - `who`: CDP's owner.
-		#[weight = (T::WeightInfo::liquidate_by_dex(), DispatchClass::Operational)]
+		#[weight = (<Module<T>>::liquidate_weight(currency_id, who), DispatchClass::Operational)]
 		pub fn liquidate(
 			origin,
 			currency_id: CurrencyId,
 			who: T::AccountId,
 		) {
 			with_transaction_result(|| {
-				ensure_none(origin)?;
+				let maybe_keeper = Self::ensure_keeper_or_none(origin)?;
 				ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
-				Self::liquidate_unsafe_cdp(who, currency_id)?;
-				Ok(())
+				let result = with_transaction_result(|| -> DispatchResult {
+					let penalty_value = Self::liquidate_unsafe_cdp(who.clone(), currency_id)?;
+					if let Some(keeper) = &maybe_keeper {
+						Self::reward_keeper(keeper, penalty_value);
+					}
+					Ok(())
+				});
+				Self::slash_keeper_if_failed(maybe_keeper, currency_id, who, result)
 			})?;
 		}
 
 		/// Settle CDP has debit after system shutdown
 		///
-		/// The dispatch origin of this call must be _None_.
+		/// The dispatch origin of this call must be _None_, or _Signed_ by a keeper.
+		/// There is no liquidation penalty after shutdown, so keepers are not
+		/// rewarded, but their deposit is slashed if the settlement fails.
 		///
 		/// - `currency_id`: CDP's collateral type.
 		/// - `who`: CDP's owner.
@@ -314,9 +563,115 @@ decl_module! {
 			who: T::AccountId,
 		) {
 			with_transaction_result(|| {
-				ensure_none(origin)?;
+				let maybe_keeper = Self::ensure_keeper_or_none(origin)?;
 				ensure!(T::EmergencyShutdown::is_shutdown(), Error::<T>::MustAfterShutdown);
-				Self::settle_cdp_has_debit(who, currency_id)?;
+				let result = with_transaction_result(|| Self::settle_cdp_has_debit(who.clone(), currency_id));
+				Self::slash_keeper_if_failed(maybe_keeper, currency_id, who, result)
+			})?;
+		}
+
+		/// Register the caller as a keeper, who can liquidate and settle CDPs by
+		/// signed calls. `KeeperDeposit` of stable currency is reserved until deregistering.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		#[weight = T::WeightInfo::register_keeper()]
+		pub fn register_keeper(origin) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				ensure!(!Keepers::<T>::contains_key(&who), Error::<T>::AlreadyKeeper);
+
+				let deposit = T::KeeperDeposit::get();
+				<T as Trait>::Currency::reserve(T::GetStableCurrencyId::get(), &who, deposit)?;
+				Keepers::<T>::insert(&who, deposit);
+
+				Self::deposit_event(RawEvent::KeeperRegistered(who, deposit));
+				Ok(())
+			})?;
+		}
+
+		/// Deregister the caller as a keeper and unreserve its deposit.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		#[weight = T::WeightInfo::deregister_keeper()]
+		pub fn deregister_keeper(origin) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				let deposit = Keepers::<T>::take(&who).ok_or(Error::<T>::NotKeeper)?;
+				<T as Trait>::Currency::unreserve(T::GetStableCurrencyId::get(), &who, deposit);
+
+				Self::deposit_event(RawEvent::KeeperDeregistered(who));
+				Ok(())
+			})?;
+		}
+
+		/// Update the share of the liquidation penalty rewarded to keepers
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `keeper_reward_rate`: the share of the liquidation penalty, at most 100%.
+		#[weight = (T::WeightInfo::set_keeper_reward_rate(), DispatchClass::Operational)]
+		pub fn set_keeper_reward_rate(
+			origin,
+			keeper_reward_rate: Rate,
+		) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin)?;
+				ensure!(keeper_reward_rate <= Rate::one(), Error::<T>::InvalidKeeperRewardRate);
+				KeeperRewardRate::put(keeper_reward_rate);
+				Self::deposit_event(RawEvent::KeeperRewardRateUpdated(keeper_reward_rate));
+				Ok(())
+			})?;
+		}
+
+		/// Update the collateral types can be in cross-margin vaults, and the order
+		/// to sell them when a cross-margin vault is liquidated
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `liquidation_order`: the collateral types, the first is sold first.
+		#[weight = (T::WeightInfo::set_cross_margin_liquidation_order(liquidation_order.len() as u32), DispatchClass::Operational)]
+		pub fn set_cross_margin_liquidation_order(
+			origin,
+			liquidation_order: Vec<CurrencyId>,
+		) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin)?;
+				for (i, currency_id) in liquidation_order.iter().enumerate() {
+					ensure!(
+						Self::is_collateral(*currency_id) && !liquidation_order[..i].contains(currency_id),
+						Error::<T>::InvalidLiquidationOrder,
+					);
+				}
+
+				CrossMarginLiquidationOrder::put(liquidation_order.clone());
+				Self::deposit_event(RawEvent::CrossMarginLiquidationOrderUpdated(liquidation_order));
+				Ok(())
+			})?;
+		}
+
+		/// Update the params of the controller which adjusts the global stability fee
+		/// by the market price of stablecoin every block
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `controller_params`: the controller params, `None` to disable the controller.
+		#[weight = (T::WeightInfo::set_stability_fee_controller(), DispatchClass::Operational)]
+		pub fn set_stability_fee_controller(
+			origin,
+			controller_params: Option<StabilityFeeControllerParams<T::BlockNumber>>,
+		) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin)?;
+				if let Some(params) = controller_params {
+					ensure!(
+						params.min_stability_fee <= params.max_stability_fee && !params.era_length.is_zero(),
+						Error::<T>::InvalidStabilityFeeControllerParams,
+					);
+				}
+
+				StabilityFeeController::<T>::set(controller_params);
+				StabilityFeeControllerEra::<T>::kill();
+				Self::deposit_event(RawEvent::StabilityFeeControllerUpdated(controller_params));
 				Ok(())
 			})?;
 		}
@@ -328,9 +683,9 @@ decl_module! {
 		/// - `global_stability_fee`: global stability fee rate.
 		///
 		/// # <weight>
-		/// - Complexity: `O(1)`
-		/// - Db reads: 0
-		/// - Db writes: 1
+		/// - Complexity: `O(H)` where H is `MaxRiskParamsHistory`
+		/// - Db reads: 1
+		/// - Db writes: 2
 		/// -------------------
 		/// Base Weight: 24.16 µs
 		/// # </weight>
@@ -340,8 +695,9 @@ decl_module! {
 			global_stability_fee: Rate,
 		) {
 			with_transaction_result(|| {
-				T::UpdateOrigin::ensure_origin(origin)?;
+				T::UpdateOrigin::ensure_origin(origin.clone())?;
 				GlobalStabilityFee::put(global_stability_fee);
+				Self::record_global_stability_fee(Self::risk_params_update_origin(origin), global_stability_fee);
 				Self::deposit_event(RawEvent::GlobalStabilityFeeUpdated(global_stability_fee));
 				Ok(())
 			})?;
@@ -359,11 +715,11 @@ decl_module! {
 		/// - `maximum_total_debit_value`: maximum total debit value.
 		///
 		/// # <weight>
-		/// - Complexity: `O(1)`
-		/// - Db reads:	1
-		/// - Db writes: 1
+		/// - Complexity: `O(H)` where H is `MaxRiskParamsHistory`
+		/// - Db reads:	3
+		/// - Db writes: 2
 		/// -------------------
-		/// Base Weight: 76.08 µs
+		/// Base Weight: 91.25 µs
 		/// # </weight>
 		#[weight = (T::WeightInfo::set_collateral_params(), DispatchClass::Operational)]
 		pub fn set_collateral_params(
@@ -376,11 +732,8 @@ decl_module! {
 			maximum_total_debit_value: ChangeBalance,
 		) {
 			with_transaction_result(|| {
-				T::UpdateOrigin::ensure_origin(origin)?;
-				ensure!(
-					T::CollateralCurrencyIds::get().contains(&currency_id),
-					Error::<T>::InvalidCollateralType,
-				);
+				T::UpdateOrigin::ensure_origin(origin.clone())?;
+				ensure!(Self::is_collateral(currency_id), Error::<T>::InvalidCollateralType);
 
 				let mut collateral_params = Self::collateral_params(currency_id);
 				if let Change::NewValue(update) = stability_fee {
@@ -404,16 +757,174 @@ decl_module! {
 					Self::deposit_event(RawEvent::MaximumTotalDebitValueUpdated(currency_id, val));
 				}
 				CollateralParams::insert(currency_id, collateral_params);
+				Self::record_risk_params(currency_id, Self::risk_params_update_origin(origin), false);
 				Ok(())
 			})?;
 		}
 
+		/// Update the partial liquidation buffer of specific collateral type
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `buffer`: the collateral ratio above the liquidation ratio that unsafe CDPs are
+		/// 	restored to by partial liquidation, `None` means unsafe CDPs are fully liquidated.
+		#[weight = (T::WeightInfo::set_partial_liquidation_buffer(), DispatchClass::Operational)]
+		pub fn set_partial_liquidation_buffer(
+			origin,
+			currency_id: CurrencyId,
+			buffer: Option<Ratio>,
+		) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin.clone())?;
+				ensure!(Self::is_collateral(currency_id), Error::<T>::InvalidCollateralType);
+				if let Some(buffer) = buffer {
+					PartialLiquidationBuffer::insert(currency_id, buffer);
+				} else {
+					PartialLiquidationBuffer::remove(currency_id);
+				}
+				Self::record_risk_params(currency_id, Self::risk_params_update_origin(origin), false);
+				Self::deposit_event(RawEvent::PartialLiquidationBufferUpdated(currency_id, buffer));
+				Ok(())
+			})?;
+		}
+
+		/// Register a new collateral type, the risk management params of which
+		/// should be set by `set_collateral_params` before issuing debit
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		#[weight = (T::WeightInfo::register_collateral_type(), DispatchClass::Operational)]
+		pub fn register_collateral_type(
+			origin,
+			currency_id: CurrencyId,
+		) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin)?;
+				ensure!(
+					currency_id != T::GetStableCurrencyId::get(),
+					Error::<T>::InvalidCollateralType,
+				);
+				ensure!(
+					!Self::is_collateral(currency_id),
+					Error::<T>::CollateralTypeAlreadyRegistered,
+				);
+				CollateralTypes::insert(currency_id, CollateralStatus::Active);
+				Self::deposit_event(RawEvent::CollateralTypeRegistered(currency_id));
+				Ok(())
+			})?;
+		}
+
+		/// Pause a collateral type, CDPs under it cannot deposit collateral or
+		/// issue debit until it is resumed
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		#[weight = (T::WeightInfo::pause_collateral_type(), DispatchClass::Operational)]
+		pub fn pause_collateral_type(
+			origin,
+			currency_id: CurrencyId,
+		) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin)?;
+				ensure!(Self::is_collateral(currency_id), Error::<T>::InvalidCollateralType);
+				CollateralTypes::insert(currency_id, CollateralStatus::Paused);
+				Self::deposit_event(RawEvent::CollateralTypePaused(currency_id));
+				Ok(())
+			})?;
+		}
+
+		/// Resume a paused collateral type
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		#[weight = (T::WeightInfo::resume_collateral_type(), DispatchClass::Operational)]
+		pub fn resume_collateral_type(
+			origin,
+			currency_id: CurrencyId,
+		) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin)?;
+				ensure!(Self::is_collateral(currency_id), Error::<T>::InvalidCollateralType);
+				CollateralTypes::insert(currency_id, CollateralStatus::Active);
+				Self::deposit_event(RawEvent::CollateralTypeResumed(currency_id));
+				Ok(())
+			})?;
+		}
+
+		/// Retire a collateral type, which requires that there is no collateral
+		/// or debit of it left in CDPs, CDP treasury and auctions. Its risk management
+		/// params are removed, while the history of them is kept and ends with a retired record.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		#[weight = (T::WeightInfo::retire_collateral_type(), DispatchClass::Operational)]
+		pub fn retire_collateral_type(
+			origin,
+			currency_id: CurrencyId,
+		) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin.clone())?;
+				ensure!(Self::is_collateral(currency_id), Error::<T>::InvalidCollateralType);
+				let Position { collateral, debit } = <LoansOf<T>>::total_positions(currency_id);
+				ensure!(
+					collateral.is_zero()
+						&& debit.is_zero()
+						&& <T as Trait>::CDPTreasury::get_total_collaterals(currency_id).is_zero()
+						&& T::AuctionManagerHandler::get_total_collateral_in_auction(currency_id).is_zero(),
+					Error::<T>::CollateralTypeInUse,
+				);
+
+				CollateralTypes::remove(currency_id);
+				CollateralParams::remove(currency_id);
+				DebitExchangeRate::remove(currency_id);
+				PartialLiquidationBuffer::remove(currency_id);
+				CrossMarginLiquidationOrder::mutate(|liquidation_order| {
+					liquidation_order.retain(|id| *id != currency_id)
+				});
+				Self::record_risk_params(currency_id, Self::risk_params_update_origin(origin), true);
+				Self::deposit_event(RawEvent::CollateralTypeRetired(currency_id));
+				Ok(())
+			})?;
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			// the collateral types used to be a runtime constant, register them once.
+			if CollateralTypesMigrated::get() {
+				return 0;
+			}
+
+			let mut migrated: Weight = 0;
+			for currency_id in T::LegacyCollateralCurrencyIds::get() {
+				if !Self::is_collateral(currency_id) {
+					CollateralTypes::insert(currency_id, CollateralStatus::Active);
+					migrated += 1;
+				}
+			}
+			CollateralTypesMigrated::put(true);
+
+			let legacy_count = T::LegacyCollateralCurrencyIds::get().len() as Weight;
+			T::DbWeight::get().reads_writes(legacy_count + 1, migrated + 1)
+		}
+
+		/// Adjust the global stability fee by the stability fee controller
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			if !T::EmergencyShutdown::is_shutdown() {
+				Self::adjust_global_stability_fee(now);
+			}
+			T::WeightInfo::on_initialize()
+		}
+
 		/// Issue interest in stable currency for all types of collateral has debit when block end,
 		/// and update their debit exchange rate
 		fn on_finalize(_now: T::BlockNumber) {
 			// collect stability fee for all types of collateral
 			if !T::EmergencyShutdown::is_shutdown() {
-				for currency_id in T::CollateralCurrencyIds::get() {
+				for currency_id in Self::collateral_currency_ids() {
 					let debit_exchange_rate = Self::get_debit_exchange_rate(currency_id);
 					let stability_fee_rate = Self::get_stability_fee(currency_id);
 					let total_debits = <LoansOf<T>>::total_positions(currency_id).debit;
@@ -431,6 +942,8 @@ decl_module! {
 					}
 				}
 			}
+
+			ProcessedCDPs::<T>::kill();
 		}
 
 		/// Runs after every block. Start offchain worker to check CDP and
@@ -455,6 +968,59 @@ decl_module! {
 }
 
 impl<T: Trait> Module<T> {
+	/// Move the global stability fee by a step within the bounds when the
+	/// market price of stablecoin deviates from the target price, raise it if
+	/// stablecoin is below the peg and lower it if above. The total change in
+	/// one era is capped.
+	fn adjust_global_stability_fee(now: T::BlockNumber) {
+		let params = match Self::stability_fee_controller() {
+			Some(params) => params,
+			None => return,
+		};
+		let market_price = match T::StableCurrencyPriceSource::get(&T::GetStableCurrencyId::get()) {
+			Some(price) => price,
+			None => return,
+		};
+
+		let (mut era_start, mut era_change) = Self::stability_fee_controller_era();
+		if now.saturating_sub(era_start) >= params.era_length {
+			// the adjustments are recorded in the history once per era when it ends,
+			// so that they don't push the changes by governance out of the history
+			if !era_change.is_zero() {
+				Self::record_global_stability_fee(
+					RiskParamsUpdateOrigin::StabilityFeeController,
+					Self::global_stability_fee(),
+				);
+			}
+			era_start = now;
+			era_change = Zero::zero();
+		}
+
+		let step = params.step.min(params.max_change_per_era.saturating_sub(era_change));
+		let old_stability_fee = Self::global_stability_fee();
+		let new_stability_fee = if market_price.saturating_add(params.price_tolerance) < params.target_price
+			&& old_stability_fee < params.max_stability_fee
+		{
+			old_stability_fee.saturating_add(step).min(params.max_stability_fee)
+		} else if market_price > params.target_price.saturating_add(params.price_tolerance)
+			&& old_stability_fee > params.min_stability_fee
+		{
+			old_stability_fee.saturating_sub(step).max(params.min_stability_fee)
+		} else {
+			old_stability_fee
+		};
+
+		if new_stability_fee != old_stability_fee {
+			let change = new_stability_fee
+				.max(old_stability_fee)
+				.saturating_sub(new_stability_fee.min(old_stability_fee));
+			era_change = era_change.saturating_add(change);
+			GlobalStabilityFee::put(new_stability_fee);
+			Self::deposit_event(RawEvent::GlobalStabilityFeeAdjusted(market_price, new_stability_fee));
+		}
+		StabilityFeeControllerEra::<T>::put((era_start, era_change));
+	}
+
 	fn submit_unsigned_liquidation_tx(currency_id: CurrencyId, who: T::AccountId) {
 		let call = Call::<T>::liquidate(currency_id, who.clone());
 		if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
@@ -478,7 +1044,7 @@ impl<T: Trait> Module<T> {
 	}
 
 	fn _offchain_worker() -> Result<(), OffchainErr> {
-		let collateral_currency_ids = T::CollateralCurrencyIds::get();
+		let collateral_currency_ids = Self::collateral_currency_ids();
 		if collateral_currency_ids.len().is_zero() {
 			return Ok(());
 		}
@@ -493,30 +1059,38 @@ impl<T: Trait> Module<T> {
 		let mut lock = StorageLock::<'_, Time>::with_deadline(&OFFCHAIN_WORKER_LOCK, lock_expiration);
 		let mut guard = lock.try_lock().map_err(|_| OffchainErr::OffchainLock)?;
 
-		let collateral_currency_ids = T::CollateralCurrencyIds::get();
+		let collateral_currency_ids = Self::collateral_currency_ids();
 		let to_be_continue = StorageValueRef::persistent(&OFFCHAIN_WORKER_DATA);
 
-		// get to_be_continue record
-		let (collateral_position, start_key) =
-			if let Some(Some((last_collateral_position, maybe_last_iterator_previous_key))) =
-				to_be_continue.get::<(u32, Option<Vec<u8>>)>()
-			{
-				(last_collateral_position, maybe_last_iterator_previous_key)
-			} else {
+		// get to_be_continue record, the collateral type of which may have been
+		// retired since it was recorded
+		let (collateral_position, start_key) = match to_be_continue.get::<(CurrencyId, Option<Vec<u8>>)>() {
+			Some(Some((last_currency_id, maybe_last_iterator_previous_key))) => {
+				match collateral_currency_ids.iter().position(|id| *id == last_currency_id) {
+					Some(position) => (position, maybe_last_iterator_previous_key),
+					None => (0, None),
+				}
+			}
+			Some(None) => (0, None),
+			None => {
 				let random_seed = sp_io::offchain::random_seed();
 				let mut rng = RandomNumberGenerator::<BlakeTwo256>::new(BlakeTwo256::hash(&random_seed[..]));
 				(
-					rng.pick_u32(collateral_currency_ids.len().saturating_sub(1) as u32),
+					rng.pick_u32(collateral_currency_ids.len().saturating_sub(1) as u32) as usize,
 					None,
 				)
-			};
+			}
+		};
 
 		// get the max iterationns config
 		let max_iterations = StorageValueRef::persistent(&OFFCHAIN_WORKER_MAX_ITERATIONS)
 			.get::<u32>()
 			.unwrap_or(Some(DEFAULT_MAX_ITERATIONS));
 
-		let currency_id = collateral_currency_ids[(collateral_position as usize)];
+		let currency_id = match collateral_currency_ids.get(collateral_position) {
+			Some(currency_id) => *currency_id,
+			None => collateral_currency_ids[0],
+		};
 		let is_shutdown = T::EmergencyShutdown::is_shutdown();
 
 		debug::debug!(target: "cdp-engine offchain worker", "max iterations is {:?}", max_iterations);
@@ -527,7 +1101,7 @@ impl<T: Trait> Module<T> {
 			start_key,
 		);
 		while let Some((who, Position { collateral, debit })) = map_iterator.next() {
-			if !is_shutdown && Self::is_cdp_unsafe(currency_id, collateral, debit) {
+			if !is_shutdown && Self::is_position_unsafe(&who, currency_id, collateral, debit) {
 				// liquidate unsafe CDPs before emergency shutdown occurs
 				Self::submit_unsigned_liquidation_tx(currency_id, who);
 			} else if is_shutdown && !debit.is_zero() {
@@ -542,15 +1116,12 @@ impl<T: Trait> Module<T> {
 		// if iteration for map storage finished, clear to be continue record
 		// otherwise, update to be continue record
 		if map_iterator.finished {
-			let next_collateral_position =
-				if collateral_position < collateral_currency_ids.len().saturating_sub(1) as u32 {
-					collateral_position + 1
-				} else {
-					0
-				};
-			to_be_continue.set(&(next_collateral_position, Option::<Vec<u8>>::None));
+			let next_currency_id = collateral_currency_ids
+				.get(collateral_position + 1)
+				.unwrap_or(&collateral_currency_ids[0]);
+			to_be_continue.set(&(*next_currency_id, Option::<Vec<u8>>::None));
 		} else {
-			to_be_continue.set(&(collateral_position, Some(map_iterator.map_iterator.previous_key)));
+			to_be_continue.set(&(currency_id, Some(map_iterator.map_iterator.previous_key)));
 		}
 
 		// Consume the guard but **do not** unlock the underlying lock.
@@ -617,16 +1188,233 @@ impl<T: Trait> Module<T> {
 		Ratio::checked_from_rational(locked_collateral_value, debit_value).unwrap_or_else(Rate::max_value)
 	}
 
+	/// Get all registered collateral types, including the paused ones.
+	pub fn collateral_currency_ids() -> Vec<CurrencyId> {
+		CollateralTypes::iter().map(|(currency_id, _)| currency_id).collect()
+	}
+
+	/// Check if `currency_id` is a registered collateral type.
+	pub fn is_collateral(currency_id: CurrencyId) -> bool {
+		CollateralTypes::contains_key(currency_id)
+	}
+
+	/// Check if `currency_id` is a registered collateral type which is not
+	/// paused.
+	pub fn is_active_collateral(currency_id: CurrencyId) -> bool {
+		Self::collateral_types(currency_id) == Some(CollateralStatus::Active)
+	}
+
+	/// Get the total debit value of all CDPs under `currency_id`.
+	pub fn get_total_debit_value(currency_id: CurrencyId) -> Balance {
+		Self::get_debit_value(currency_id, <LoansOf<T>>::total_positions(currency_id).debit)
+	}
+
+	/// Get the utilisation of `maximum_total_debit_value` of `currency_id`,
+	/// `None` if the hard cap is zero.
+	pub fn get_debit_ceiling_utilisation(currency_id: CurrencyId) -> Option<Ratio> {
+		Ratio::checked_from_rational(
+			Self::get_total_debit_value(currency_id),
+			Self::maximum_total_debit_value(currency_id),
+		)
+	}
+
+	/// Get the price of the collateral in stable currency.
+	pub fn get_collateral_price(currency_id: CurrencyId) -> Option<Price> {
+		T::PriceSource::get_relative_price(currency_id, T::GetStableCurrencyId::get())
+	}
+
+	/// Get the price of the collateral at which the CDP reaches the
+	/// liquidation ratio.
+	pub fn get_liquidation_price(
+		currency_id: CurrencyId,
+		collateral_balance: Balance,
+		debit_balance: Balance,
+	) -> Option<Price> {
+		if collateral_balance.is_zero() || debit_balance.is_zero() {
+			return None;
+		}
+
+		let debit_value = Self::get_debit_value(currency_id, debit_balance);
+		let liquidation_value = Self::get_liquidation_ratio(currency_id).saturating_mul_int(debit_value);
+		Price::checked_from_rational(liquidation_value, collateral_balance)
+	}
+
+	/// Get how much more debit value can be issued for the CDP before it
+	/// reaches the required collateral ratio or the liquidation ratio.
+	pub fn get_available_debit_value(
+		currency_id: CurrencyId,
+		collateral_balance: Balance,
+		debit_balance: Balance,
+		price: Price,
+	) -> Balance {
+		let collateral_value = price.saturating_mul_int(collateral_balance);
+		let debit_value = Self::get_debit_value(currency_id, debit_balance);
+		let minimum_collateral_ratio = Self::required_collateral_ratio(currency_id)
+			.unwrap_or_default()
+			.max(Self::get_liquidation_ratio(currency_id));
+
+		minimum_collateral_ratio
+			.reciprocal()
+			.map(|ratio| ratio.saturating_mul_int(collateral_value))
+			.unwrap_or(collateral_value)
+			.saturating_sub(debit_value)
+	}
+
+	/// Get the origin recorded in the risk management params history from
+	/// the dispatch origin.
+	fn risk_params_update_origin(origin: T::Origin) -> RiskParamsUpdateOrigin<T::AccountId> {
+		let raw_origin: Result<system::RawOrigin<T::AccountId>, T::Origin> = origin.into();
+		match raw_origin {
+			Ok(system::RawOrigin::Root) => RiskParamsUpdateOrigin::Root,
+			Ok(system::RawOrigin::Signed(who)) => RiskParamsUpdateOrigin::Signed(who),
+			Ok(system::RawOrigin::None) => RiskParamsUpdateOrigin::Other,
+			Err(origin) => T::RiskParamsUpdateOriginConvertor::convert(origin),
+		}
+	}
+
+	/// Append the current risk management params of `currency_id` to its
+	/// history, and drop the oldest records beyond `MaxRiskParamsHistory`.
+	fn record_risk_params(currency_id: CurrencyId, origin: RiskParamsUpdateOrigin<T::AccountId>, retired: bool) {
+		let record = RiskParamsRecord {
+			block_number: <system::Module<T>>::block_number(),
+			origin,
+			params: Self::collateral_params(currency_id),
+			partial_liquidation_buffer: Self::partial_liquidation_buffer(currency_id),
+			retired,
+		};
+
+		RiskParamsHistory::<T>::mutate(currency_id, |history| {
+			history.push(record);
+			let max_history = T::MaxRiskParamsHistory::get() as usize;
+			if history.len() > max_history {
+				history.drain(..history.len() - max_history);
+			}
+		});
+	}
+
+	/// Append the updated global stability fee to its history, and drop the
+	/// oldest records beyond `MaxRiskParamsHistory`.
+	fn record_global_stability_fee(origin: RiskParamsUpdateOrigin<T::AccountId>, global_stability_fee: Rate) {
+		let record = GlobalStabilityFeeRecord {
+			block_number: <system::Module<T>>::block_number(),
+			origin,
+			global_stability_fee,
+		};
+
+		GlobalStabilityFeeHistory::<T>::mutate(|history| {
+			history.push(record);
+			let max_history = T::MaxRiskParamsHistory::get() as usize;
+			if history.len() > max_history {
+				history.drain(..history.len() - max_history);
+			}
+		});
+	}
+
+	/// Ensure the origin is _None_ or _Signed_ by a keeper, return the keeper
+	/// if it's signed.
+	fn ensure_keeper_or_none(origin: T::Origin) -> Result<Option<T::AccountId>, DispatchError> {
+		let origin: Result<system::RawOrigin<T::AccountId>, T::Origin> = origin.into();
+		match origin {
+			Ok(system::RawOrigin::None) => Ok(None),
+			Ok(system::RawOrigin::Signed(who)) => {
+				ensure!(Keepers::<T>::contains_key(&who), Error::<T>::NotKeeper);
+				Ok(Some(who))
+			}
+			_ => Err(BadOrigin.into()),
+		}
+	}
+
+	/// Reward the keeper with `KeeperRewardRate` of the liquidation penalty
+	/// value, the reward is paid out of the free surplus of the CDP treasury.
+	/// The keeper is not rewarded if the surplus is not enough, e.g. the
+	/// penalty is still in collateral auctions.
+	fn reward_keeper(keeper: &T::AccountId, penalty_value: Balance) {
+		let reward_amount = Self::keeper_reward_rate().saturating_mul_int(penalty_value);
+
+		if !reward_amount.is_zero() && <T as Trait>::CDPTreasury::withdraw_surplus(keeper, reward_amount).is_ok() {
+			Self::deposit_event(RawEvent::KeeperRewarded(keeper.clone(), reward_amount));
+		}
+	}
+
+	/// Return the result of the liquidation or settlement of the CDP, unless
+	/// the call is signed by a keeper, whose deposit is slashed instead of
+	/// failing. So that invalid calls are not free for keepers, while the
+	/// keepers losing the race to a CDP processed in the same block fail
+	/// without being slashed.
+	fn slash_keeper_if_failed(
+		maybe_keeper: Option<T::AccountId>,
+		currency_id: CurrencyId,
+		who: T::AccountId,
+		result: DispatchResult,
+	) -> DispatchResult {
+		match (maybe_keeper, result) {
+			(Some(_), Err(e)) if Self::processed_cdps().contains(&(currency_id, who)) => Err(e),
+			(Some(keeper), Err(_)) => Self::slash_keeper(&keeper),
+			(_, Ok(())) => {
+				Self::note_processed_cdp(currency_id, who);
+				Ok(())
+			}
+			(None, result) => result,
+		}
+	}
+
+	/// Note the CDP processed in current block, all positions in the
+	/// cross-margin vault are processed together.
+	fn note_processed_cdp(currency_id: CurrencyId, who: T::AccountId) {
+		let currency_ids = if Self::is_cross_margin_collateral(&who, currency_id) {
+			Self::cross_margin_liquidation_order()
+		} else {
+			vec![currency_id]
+		};
+		ProcessedCDPs::<T>::mutate(|processed_cdps| {
+			processed_cdps.extend(currency_ids.into_iter().map(|currency_id| (currency_id, who.clone())))
+		});
+	}
+
+	/// The weight of `liquidate`, the liquidation of a cross-margin vault
+	/// sells every collateral type in it.
+	fn liquidate_weight(currency_id: &CurrencyId, who: &T::AccountId) -> Weight {
+		if Self::is_cross_margin_collateral(who, *currency_id) {
+			T::WeightInfo::liquidate_cross_margin_vault(Self::cross_margin_liquidation_order().len() as u32)
+		} else {
+			T::WeightInfo::liquidate_by_dex()
+		}
+	}
+
+	/// Slash `KeeperSlashAmount` of the deposit of the keeper to the CDP
+	/// treasury as surplus, the keeper is deregistered once its deposit is
+	/// used up.
+	fn slash_keeper(keeper: &T::AccountId) -> DispatchResult {
+		let deposit = Self::keepers(keeper).ok_or(Error::<T>::NotKeeper)?;
+		let slash_amount = T::KeeperSlashAmount::get().min(deposit);
+		<T as Trait>::Currency::unreserve(T::GetStableCurrencyId::get(), keeper, slash_amount);
+		<T as Trait>::CDPTreasury::deposit_surplus(keeper, slash_amount)?;
+
+		let remaining_deposit = deposit.saturating_sub(slash_amount);
+		if remaining_deposit.is_zero() {
+			Keepers::<T>::remove(keeper);
+			Self::deposit_event(RawEvent::KeeperDeregistered(keeper.clone()));
+		} else {
+			Keepers::<T>::insert(keeper, remaining_deposit);
+		}
+
+		Self::deposit_event(RawEvent::KeeperSlashed(keeper.clone(), slash_amount));
+		Ok(())
+	}
+
 	pub fn adjust_position(
 		who: &T::AccountId,
 		currency_id: CurrencyId,
 		collateral_adjustment: Amount,
 		debit_adjustment: Amount,
 	) -> DispatchResult {
-		ensure!(
-			T::CollateralCurrencyIds::get().contains(&currency_id),
-			Error::<T>::InvalidCollateralType,
-		);
+		ensure!(Self::is_collateral(currency_id), Error::<T>::InvalidCollateralType);
+		if collateral_adjustment.is_positive() || debit_adjustment.is_positive() {
+			ensure!(
+				Self::is_active_collateral(currency_id),
+				Error::<T>::CollateralTypePaused,
+			);
+		}
 		<LoansOf<T>>::adjust_position(who, currency_id, collateral_adjustment, debit_adjustment)?;
 		Ok(())
 	}
@@ -651,8 +1439,214 @@ impl<T: Trait> Module<T> {
 		Ok(())
 	}
 
-	// liquidate unsafe cdp
-	pub fn liquidate_unsafe_cdp(who: T::AccountId, currency_id: CurrencyId) -> DispatchResult {
+	// close cdp has debit by swapping collateral to get enough stable coin with
+	// DEX, the remain collateral will be refunded to the CDP owner
+	pub fn close_cdp_has_debit_by_dex(
+		who: T::AccountId,
+		currency_id: CurrencyId,
+		max_collateral_amount: Balance,
+	) -> DispatchResult {
+		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
+		ensure!(!debit.is_zero(), Error::<T>::NoDebitValue);
+		ensure!(
+			!Self::is_position_unsafe(&who, currency_id, collateral, debit),
+			Error::<T>::MustBeSafe
+		);
+
+		// the rest of the cross-margin vault must be still valid without this cdp,
+		// as the collateral refunded to `who` may back the debits of other positions
+		if Self::is_cross_margin_collateral(&who, currency_id) {
+			Self::check_cross_margin_vault_valid(&who, currency_id, Zero::zero(), Zero::zero())?;
+		}
+
+		// confiscate all collateral and debit of the cdp to cdp treasury
+		<LoansOf<T>>::confiscate_collateral_and_debit(&who, currency_id, collateral, debit)?;
+
+		// swap exact stable to repay the debit with DEX, supply at most
+		// `max_collateral_amount` collateral
+		let debit_value = Self::get_debit_value(currency_id, debit);
+		let collateral_supply = collateral.min(max_collateral_amount);
+		let actual_supply_collateral = <T as Trait>::CDPTreasury::swap_collateral_not_in_auction_with_exact_stable(
+			currency_id,
+			debit_value,
+			collateral_supply,
+			None,
+		)?;
+
+		// refund remain collateral to CDP owner
+		let refund_collateral_amount = collateral
+			.checked_sub(actual_supply_collateral)
+			.expect("swap succecced means collateral >= actual_supply_collateral; qed");
+		<T as Trait>::CDPTreasury::withdraw_collateral(&who, currency_id, refund_collateral_amount)?;
+
+		Self::deposit_event(RawEvent::CloseCDPInDebitByDEX(
+			currency_id,
+			who,
+			actual_supply_collateral,
+			refund_collateral_amount,
+			debit_value,
+		));
+		Ok(())
+	}
+
+	// expand cdp by issuing debit and swapping it to collateral with DEX, so that
+	// the collateral ratio decreases to about `target_collateral_ratio`, the swap
+	// must increase the collateral by at least `min_increase_collateral`
+	pub fn expand_position_collateral(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		target_collateral_ratio: Ratio,
+		min_increase_collateral: Balance,
+	) -> DispatchResult {
+		ensure!(Self::is_collateral(currency_id), Error::<T>::InvalidCollateralType);
+		ensure!(
+			Self::is_active_collateral(currency_id),
+			Error::<T>::CollateralTypePaused,
+		);
+		let increase_debit_balance = Self::get_expand_debit_balance(who, currency_id, target_collateral_ratio)
+			.ok_or(Error::<T>::InvalidTargetCollateralRatio)?;
+		let increase_debit_value = Self::get_debit_value(currency_id, increase_debit_balance);
+
+		// issue stable coin with debit backed to loans module, and swap it to
+		// collateral with DEX in limit of price impact
+		let loans_account = <LoansOf<T>>::account_id();
+		let stable_currency_id = T::GetStableCurrencyId::get();
+		let price_impact_limit = Some(T::MaxSlippageSwapWithDEX::get());
+		<T as Trait>::CDPTreasury::issue_debit(&loans_account, increase_debit_value, true)?;
+		let path = T::DEX::get_best_path_with_exact_supply(
+			stable_currency_id,
+			currency_id,
+			increase_debit_value,
+			price_impact_limit,
+		)
+		.unwrap_or_else(|| vec![stable_currency_id, currency_id]);
+		let increase_collateral = T::DEX::swap_with_exact_supply(
+			&loans_account,
+			&path,
+			increase_debit_value,
+			min_increase_collateral,
+			price_impact_limit,
+		)?;
+
+		// update the position and ensure it passes the risk check
+		<LoansOf<T>>::update_loan(
+			who,
+			currency_id,
+			<LoansOf<T>>::amount_try_from_balance(increase_collateral)?,
+			<LoansOf<T>>::amount_try_from_balance(increase_debit_balance)?,
+		)?;
+
+		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, who);
+		Self::check_position_valid(who, currency_id, collateral, debit)?;
+		Self::check_debit_cap(currency_id, <LoansOf<T>>::total_positions(currency_id).debit)?;
+
+		Self::deposit_event(RawEvent::ExpandCDPCollateralByDEX(
+			currency_id,
+			who.clone(),
+			increase_collateral,
+			increase_debit_value,
+		));
+		Ok(())
+	}
+
+	/// Get the debit to issue so that the collateral ratio of the CDP
+	/// decreases to `target_collateral_ratio` after the issued stable coin
+	/// is swapped to collateral at the feed price.
+	///
+	/// `(collateral_value + x) / (debit_value + x) = target_ratio` gives
+	/// `x = (collateral_value - target_ratio * debit_value) / (target_ratio -
+	/// 1)`, `None` if the target cannot be reached.
+	pub fn get_expand_debit_balance(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		target_collateral_ratio: Ratio,
+	) -> Option<Balance> {
+		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, who);
+		let collateral_value = Self::get_collateral_price(currency_id)?.saturating_mul_int(collateral);
+		let debit_value = Self::get_debit_value(currency_id, debit);
+
+		if target_collateral_ratio <= Ratio::one() {
+			return None;
+		}
+		let increase_debit_value = target_collateral_ratio
+			.saturating_sub(Ratio::one())
+			.reciprocal()?
+			.saturating_mul_int(collateral_value.checked_sub(target_collateral_ratio.saturating_mul_int(debit_value))?);
+		let increase_debit_balance = Self::get_debit_exchange_rate(currency_id)
+			.reciprocal()?
+			.saturating_mul_int(increase_debit_value);
+
+		if increase_debit_balance.is_zero() {
+			None
+		} else {
+			Some(increase_debit_balance)
+		}
+	}
+
+	/// Get the collateral and debit to confiscate so that the remaining CDP
+	/// is restored to the liquidation ratio plus the partial liquidation
+	/// buffer, `None` means the CDP should be fully liquidated.
+	///
+	/// The confiscated collateral covers the closed debit value with the
+	/// liquidation penalty and the max slippage of swapping with DEX, so
+	/// `collateral_value - k * x >= target_ratio * (debit_value - x)` gives
+	/// the closed debit value `x`, where `k = (1 + penalty) * (1 + slippage)`.
+	pub fn get_partial_liquidation_amounts(
+		currency_id: CurrencyId,
+		collateral: Balance,
+		debit: Balance,
+	) -> Option<(Balance, Balance)> {
+		let buffer = Self::partial_liquidation_buffer(currency_id)?;
+		let price = Self::get_collateral_price(currency_id)?;
+		let target_ratio = Self::get_liquidation_ratio(currency_id).saturating_add(buffer);
+		let confiscate_rate = Rate::one()
+			.saturating_add(Self::get_liquidation_penalty(currency_id))
+			.saturating_mul(Ratio::one().saturating_add(T::MaxSlippageSwapWithDEX::get()));
+
+		// the CDP cannot be restored if the confiscated collateral is worth more
+		// than the target ratio of the closed debit value
+		if target_ratio <= confiscate_rate {
+			return None;
+		}
+
+		let collateral_value = price.saturating_mul_int(collateral);
+		let debit_value = Self::get_debit_value(currency_id, debit);
+		let shortfall_value = target_ratio
+			.saturating_mul_int(debit_value)
+			.saturating_sub(collateral_value);
+		let close_debit_value = target_ratio
+			.saturating_sub(confiscate_rate)
+			.reciprocal()?
+			.saturating_mul_int(shortfall_value);
+		let debit_decrease = Self::get_debit_exchange_rate(currency_id)
+			.reciprocal()?
+			.saturating_mul_int(close_debit_value);
+		if debit_decrease.is_zero() || debit_decrease >= debit {
+			return None;
+		}
+
+		// the remaining debit value must not be dust
+		let remain_debit_value = debit_value.saturating_sub(Self::get_debit_value(currency_id, debit_decrease));
+		if remain_debit_value < T::MinimumDebitValue::get() {
+			return None;
+		}
+
+		let confiscate_collateral_value =
+			confiscate_rate.saturating_mul_int(Self::get_debit_value(currency_id, debit_decrease));
+		let collateral_confiscate = price.reciprocal()?.saturating_mul_int(confiscate_collateral_value);
+		if collateral_confiscate >= collateral {
+			return None;
+		}
+
+		Some((collateral_confiscate, debit_decrease))
+	}
+
+	// liquidate unsafe cdp, return the liquidation penalty value
+	pub fn liquidate_unsafe_cdp(who: T::AccountId, currency_id: CurrencyId) -> Result<Balance, DispatchError> {
+		if Self::is_cross_margin_collateral(&who, currency_id) {
+			return Self::liquidate_cross_margin_vault(who);
+		}
+
 		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
 
 		// ensure the cdp is unsafe
@@ -661,53 +1655,165 @@ impl<T: Trait> Module<T> {
 			Error::<T>::MustBeUnsafe
 		);
 
-		// confiscate all collateral and debit of unsafe cdp to cdp treasury
-		<LoansOf<T>>::confiscate_collateral_and_debit(&who, currency_id, collateral, debit)?;
+		let bad_debt_value = if let Some((collateral_confiscate, debit_decrease)) =
+			Self::get_partial_liquidation_amounts(currency_id, collateral, debit)
+		{
+			let (bad_debt_value, liquidation_strategy) =
+				Self::confiscate_and_liquidate(&who, currency_id, collateral_confiscate, debit_decrease)?;
+
+			Self::deposit_event(RawEvent::PartialLiquidateUnsafeCDP(
+				currency_id,
+				who,
+				collateral_confiscate,
+				bad_debt_value,
+				liquidation_strategy,
+			));
+			bad_debt_value
+		} else {
+			// confiscate all collateral and debit of unsafe cdp to cdp treasury
+			let (bad_debt_value, liquidation_strategy) =
+				Self::confiscate_and_liquidate(&who, currency_id, collateral, debit)?;
+
+			Self::deposit_event(RawEvent::LiquidateUnsafeCDP(
+				currency_id,
+				who,
+				collateral,
+				bad_debt_value,
+				liquidation_strategy,
+			));
+			bad_debt_value
+		};
+
+		Ok(Self::get_liquidation_penalty(currency_id).saturating_mul_int(bad_debt_value))
+	}
+
+	// liquidate all debit of the unsafe cross-margin vault, and sell the collaterals
+	// in the liquidation order until the debit value with penalty is covered.
+	// return the liquidation penalty value
+	fn liquidate_cross_margin_vault(who: T::AccountId) -> Result<Balance, DispatchError> {
+		ensure!(Self::is_cross_margin_vault_unsafe(&who), Error::<T>::MustBeUnsafe);
+
+		let liquidation_order = Self::cross_margin_liquidation_order();
+		let mut bad_debt_value: Balance = Zero::zero();
+		let mut penalty_value: Balance = Zero::zero();
+
+		// confiscate all debit of the vault to cdp treasury
+		for currency_id in liquidation_order.iter() {
+			let debit = <LoansOf<T>>::positions(currency_id, &who).debit;
+			if !debit.is_zero() {
+				<LoansOf<T>>::confiscate_collateral_and_debit(&who, *currency_id, Zero::zero(), debit)?;
+
+				let debit_value = Self::get_debit_value(*currency_id, debit);
+				bad_debt_value = bad_debt_value.saturating_add(debit_value);
+				penalty_value = penalty_value
+					.saturating_add(Self::get_liquidation_penalty(*currency_id).saturating_mul_int(debit_value));
+			}
+		}
+
+		let mut remain_target = bad_debt_value.saturating_add(penalty_value);
+		for currency_id in liquidation_order {
+			if remain_target.is_zero() {
+				break;
+			}
+
+			let collateral = <LoansOf<T>>::positions(currency_id, &who).collateral;
+			if collateral.is_zero() {
+				continue;
+			}
+
+			// the collateral is sold for at most its value at the feed price, the rest
+			// of the target is left to the following collaterals
+			let collateral_value = Self::get_collateral_price(currency_id)
+				.ok_or(Error::<T>::InvalidFeedPrice)?
+				.saturating_mul_int(collateral);
+			let target_stable_amount = remain_target.min(collateral_value);
+
+			<LoansOf<T>>::confiscate_collateral_and_debit(&who, currency_id, collateral, Zero::zero())?;
+			let liquidation_strategy = Self::sell_collateral(&who, currency_id, collateral, target_stable_amount)?;
+			remain_target = remain_target.saturating_sub(target_stable_amount);
+
+			Self::deposit_event(RawEvent::LiquidateCrossMarginCollateral(
+				who.clone(),
+				currency_id,
+				collateral,
+				target_stable_amount,
+				liquidation_strategy,
+			));
+		}
+
+		Self::deposit_event(RawEvent::LiquidateCrossMarginVault(who, bad_debt_value));
+		Ok(penalty_value)
+	}
+
+	// confiscate collateral and debit of unsafe cdp to cdp treasury and sell the
+	// collateral for the debit value with penalty
+	fn confiscate_and_liquidate(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		collateral: Balance,
+		debit: Balance,
+	) -> Result<(Balance, LiquidationStrategy), DispatchError> {
+		<LoansOf<T>>::confiscate_collateral_and_debit(who, currency_id, collateral, debit)?;
 
 		let bad_debt_value = Self::get_debit_value(currency_id, debit);
 		let target_stable_amount = Self::get_liquidation_penalty(currency_id).saturating_mul_acc_int(bad_debt_value);
+		let liquidation_strategy = Self::sell_collateral(who, currency_id, collateral, target_stable_amount)?;
+
+		Ok((bad_debt_value, liquidation_strategy))
+	}
 
+	// sell the confiscated collateral in cdp treasury for the target amount of
+	// stable currency, the remain collateral will be refunded to `who`
+	fn sell_collateral(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		collateral: Balance,
+		target_stable_amount: Balance,
+	) -> Result<LiquidationStrategy, DispatchError> {
 		// try use collateral to swap enough native token in DEX when the price impact
 		// is below the limit, otherwise create collateral auctions.
-		let liquidation_strategy = (|| -> Result<LiquidationStrategy, DispatchError> {
-			// swap exact stable with DEX in limit of price impact
-			if let Ok(actual_supply_collateral) =
-				<T as Trait>::CDPTreasury::swap_collateral_not_in_auction_with_exact_stable(
-					currency_id,
-					target_stable_amount,
-					collateral,
-					Some(T::MaxSlippageSwapWithDEX::get()),
-				) {
-				// refund remain collateral to CDP owner
-				let refund_collateral_amount = collateral
-					.checked_sub(actual_supply_collateral)
-					.expect("swap succecced means collateral >= actual_supply_collateral; qed");
-
-				<T as Trait>::CDPTreasury::withdraw_collateral(&who, currency_id, refund_collateral_amount)?;
-
-				return Ok(LiquidationStrategy::Exchange);
-			}
-
-			// create collateral auctions by cdp treasury
-			<T as Trait>::CDPTreasury::create_collateral_auctions(
+		// swap exact stable with DEX in limit of price impact
+		if let Ok(actual_supply_collateral) =
+			<T as Trait>::CDPTreasury::swap_collateral_not_in_auction_with_exact_stable(
 				currency_id,
-				collateral,
 				target_stable_amount,
-				who.clone(),
-				true,
-			)?;
+				collateral,
+				Some(T::MaxSlippageSwapWithDEX::get()),
+			) {
+			// refund remain collateral to CDP owner
+			let refund_collateral_amount = collateral
+				.checked_sub(actual_supply_collateral)
+				.expect("swap succecced means collateral >= actual_supply_collateral; qed");
 
-			Ok(LiquidationStrategy::Auction)
-		})()?;
+			<T as Trait>::CDPTreasury::withdraw_collateral(who, currency_id, refund_collateral_amount)?;
 
-		Self::deposit_event(RawEvent::LiquidateUnsafeCDP(
+			return Ok(LiquidationStrategy::Exchange);
+		}
+
+		// create collateral auctions by cdp treasury
+		<T as Trait>::CDPTreasury::create_collateral_auctions(
 			currency_id,
-			who,
 			collateral,
-			bad_debt_value,
-			liquidation_strategy,
-		));
-		Ok(())
+			target_stable_amount,
+			who.clone(),
+			true,
+		)?;
+
+		Ok(LiquidationStrategy::Auction)
+	}
+}
+
+impl<T: Trait> CollateralRegistry<CurrencyId> for Module<T> {
+	fn collateral_currency_ids() -> Vec<CurrencyId> {
+		Self::collateral_currency_ids()
+	}
+
+	fn is_collateral(currency_id: CurrencyId) -> bool {
+		Self::is_collateral(currency_id)
+	}
+
+	fn is_active_collateral(currency_id: CurrencyId) -> bool {
+		Self::is_active_collateral(currency_id)
 	}
 }
 
@@ -717,6 +1823,174 @@ impl<T: Trait> RiskManager<T::AccountId, CurrencyId, Balance, Balance> for Modul
 	}
 
 	fn check_position_valid(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		collateral_balance: Balance,
+		debit_balance: Balance,
+	) -> DispatchResult {
+		if Self::is_cross_margin_collateral(who, currency_id) {
+			Self::check_cross_margin_vault_valid(who, currency_id, collateral_balance, debit_balance)
+		} else {
+			Self::check_isolated_position_valid(currency_id, collateral_balance, debit_balance)
+		}
+	}
+
+	fn check_debit_cap(currency_id: CurrencyId, total_debit_balance: Balance) -> DispatchResult {
+		let hard_cap = Self::maximum_total_debit_value(currency_id);
+		let total_debit_value = Self::get_debit_value(currency_id, total_debit_balance);
+
+		ensure!(total_debit_value <= hard_cap, Error::<T>::ExceedDebitValueHardCap,);
+
+		Ok(())
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Check if the position of `who` under `currency_id` is in its
+	/// cross-margin vault.
+	pub fn is_cross_margin_collateral(who: &T::AccountId, currency_id: CurrencyId) -> bool {
+		Self::is_cross_margin(who) && Self::cross_margin_liquidation_order().contains(&currency_id)
+	}
+
+	/// Enable or disable the cross-margin mode of `who`, every position must
+	/// be valid on its own to leave the cross-margin vault.
+	pub fn set_cross_margin(who: &T::AccountId, enabled: bool) -> DispatchResult {
+		if enabled {
+			CrossMarginAccounts::<T>::insert(who, true);
+		} else if Self::is_cross_margin(who) {
+			for currency_id in Self::cross_margin_liquidation_order() {
+				let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, who);
+				Self::check_isolated_position_valid(currency_id, collateral, debit)?;
+			}
+			CrossMarginAccounts::<T>::remove(who);
+		}
+
+		Self::deposit_event(RawEvent::CrossMarginModeUpdated(who.clone(), enabled));
+		Ok(())
+	}
+
+	/// Check if the position is unsafe, the position in the cross-margin vault
+	/// is unsafe if the vault is unsafe.
+	pub fn is_position_unsafe(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		collateral_balance: Balance,
+		debit_balance: Balance,
+	) -> bool {
+		if Self::is_cross_margin_collateral(who, currency_id) {
+			Self::is_cross_margin_vault_unsafe(who)
+		} else {
+			Self::is_cdp_unsafe(currency_id, collateral_balance, debit_balance)
+		}
+	}
+
+	/// Check if the cross-margin vault of `who` is unsafe, which means the sum
+	/// of every collateral value divided by its liquidation ratio is below the
+	/// total debit value.
+	pub fn is_cross_margin_vault_unsafe(who: &T::AccountId) -> bool {
+		let positions = Self::get_cross_margin_vault_positions(who, None);
+		let debit_value = Self::get_cross_margin_vault_debit_value(&positions);
+
+		!debit_value.is_zero()
+			&& Self::get_cross_margin_vault_collateral_value(&positions, |currency_id| {
+				Self::get_liquidation_ratio(currency_id)
+			})
+			.map_or(false, |liquidation_value| liquidation_value < debit_value)
+	}
+
+	/// Get the positions in the cross-margin vault of `who`, the position under
+	/// `adjusted.0` is replaced by `adjusted.1` if it's specified.
+	fn get_cross_margin_vault_positions(
+		who: &T::AccountId,
+		adjusted: Option<(CurrencyId, Position)>,
+	) -> Vec<(CurrencyId, Position)> {
+		Self::cross_margin_liquidation_order()
+			.into_iter()
+			.map(|currency_id| match adjusted {
+				Some((adjusted_currency_id, position)) if adjusted_currency_id == currency_id => {
+					(currency_id, position)
+				}
+				_ => (currency_id, <LoansOf<T>>::positions(currency_id, who)),
+			})
+			.collect()
+	}
+
+	fn get_cross_margin_vault_debit_value(positions: &[(CurrencyId, Position)]) -> Balance {
+		positions
+			.iter()
+			.fold(Zero::zero(), |total: Balance, (currency_id, position)| {
+				total.saturating_add(Self::get_debit_value(*currency_id, position.debit))
+			})
+	}
+
+	/// Get the sum of every collateral value divided by its collateral ratio.
+	fn get_cross_margin_vault_collateral_value(
+		positions: &[(CurrencyId, Position)],
+		collateral_ratio: impl Fn(CurrencyId) -> Ratio,
+	) -> Option<Balance> {
+		let mut total: Balance = Zero::zero();
+		for (currency_id, position) in positions {
+			if position.collateral.is_zero() {
+				continue;
+			}
+
+			let collateral_value = Self::get_collateral_price(*currency_id)?.saturating_mul_int(position.collateral);
+			let weighted_value = collateral_ratio(*currency_id)
+				.reciprocal()
+				.map_or(collateral_value, |ratio| ratio.saturating_mul_int(collateral_value));
+			total = total.saturating_add(weighted_value);
+		}
+		Some(total)
+	}
+
+	fn check_cross_margin_vault_valid(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		collateral_balance: Balance,
+		debit_balance: Balance,
+	) -> DispatchResult {
+		let positions = Self::get_cross_margin_vault_positions(
+			who,
+			Some((
+				currency_id,
+				Position {
+					collateral: collateral_balance,
+					debit: debit_balance,
+				},
+			)),
+		);
+		let debit_value = Self::get_cross_margin_vault_debit_value(&positions);
+
+		if !debit_value.is_zero() {
+			// check the required collateral ratio
+			let required_value = Self::get_cross_margin_vault_collateral_value(&positions, |currency_id| {
+				Self::required_collateral_ratio(currency_id)
+					.unwrap_or_default()
+					.max(Self::get_liquidation_ratio(currency_id))
+			})
+			.ok_or(Error::<T>::InvalidFeedPrice)?;
+			ensure!(required_value >= debit_value, Error::<T>::BelowRequiredCollateralRatio);
+
+			// check the liquidation ratio
+			let liquidation_value = Self::get_cross_margin_vault_collateral_value(&positions, |currency_id| {
+				Self::get_liquidation_ratio(currency_id)
+			})
+			.ok_or(Error::<T>::InvalidFeedPrice)?;
+			ensure!(liquidation_value >= debit_value, Error::<T>::BelowLiquidationRatio);
+		}
+
+		// check the minimum_debit_value
+		if !debit_balance.is_zero() {
+			ensure!(
+				Self::get_debit_value(currency_id, debit_balance) >= T::MinimumDebitValue::get(),
+				Error::<T>::RemainDebitValueTooSmall,
+			);
+		}
+
+		Ok(())
+	}
+
+	fn check_isolated_position_valid(
 		currency_id: CurrencyId,
 		collateral_balance: Balance,
 		debit_balance: Balance,
@@ -751,15 +2025,6 @@ impl<T: Trait> RiskManager<T::AccountId, CurrencyId, Balance, Balance> for Modul
 
 		Ok(())
 	}
-
-	fn check_debit_cap(currency_id: CurrencyId, total_debit_balance: Balance) -> DispatchResult {
-		let hard_cap = Self::maximum_total_debit_value(currency_id);
-		let total_debit_value = Self::get_debit_value(currency_id, total_debit_balance);
-
-		ensure!(total_debit_value <= hard_cap, Error::<T>::ExceedDebitValueHardCap,);
-
-		Ok(())
-	}
 }
 
 #[allow(deprecated)]
@@ -770,7 +2035,9 @@ impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
 		match call {
 			Call::liquidate(currency_id, who) => {
 				let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
-				if !Self::is_cdp_unsafe(*currency_id, collateral, debit) || T::EmergencyShutdown::is_shutdown() {
+				if !Self::is_position_unsafe(who, *currency_id, collateral, debit)
+					|| T::EmergencyShutdown::is_shutdown()
+				{
 					return InvalidTransaction::Stale.into();
 				}
 
@@ -55,6 +55,7 @@ impl_outer_origin! {
 impl_outer_dispatch! {
 	pub enum Call for Runtime where origin: Origin {
 		cdp_engine::CDPEngineModule,
+		dex::DEXModule,
 	}
 }
 
@@ -163,6 +164,8 @@ impl PriceProvider<CurrencyId> for MockPriceSource {
 		match (base, quote) {
 			(AUSD, BTC) => RELATIVE_PRICE.with(|v| *v.borrow_mut()),
 			(BTC, AUSD) => RELATIVE_PRICE.with(|v| *v.borrow_mut()),
+			(AUSD, DOT) => Some(Price::one()),
+			(DOT, AUSD) => Some(Price::one()),
 			_ => None,
 		}
 	}
@@ -176,6 +179,33 @@ impl PriceProvider<CurrencyId> for MockPriceSource {
 	fn unlock_price(_currency_id: CurrencyId) {}
 }
 
+thread_local! {
+	static STABLE_CURRENCY_PRICE: RefCell<Option<Price>> = RefCell::new(Some(Price::one()));
+}
+
+pub struct MockStableCurrencyPriceSource;
+impl MockStableCurrencyPriceSource {
+	pub fn set_price(price: Option<Price>) {
+		STABLE_CURRENCY_PRICE.with(|v| *v.borrow_mut() = price);
+	}
+}
+impl DataProvider<CurrencyId, Price> for MockStableCurrencyPriceSource {
+	fn get(currency_id: &CurrencyId) -> Option<Price> {
+		match currency_id {
+			&AUSD => STABLE_CURRENCY_PRICE.with(|v| *v.borrow_mut()),
+			_ => None,
+		}
+	}
+}
+
+thread_local! {
+	static TOTAL_COLLATERAL_IN_AUCTION: RefCell<Balance> = RefCell::new(0);
+}
+
+pub fn mock_total_collateral_in_auction(amount: Balance) {
+	TOTAL_COLLATERAL_IN_AUCTION.with(|v| *v.borrow_mut() = amount)
+}
+
 pub struct MockAuctionManager;
 impl AuctionManager<AccountId> for MockAuctionManager {
 	type Balance = Balance;
@@ -191,6 +221,15 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		Ok(())
 	}
 
+	fn new_dutch_collateral_auction(
+		_refund_recipient: &AccountId,
+		_currency_id: Self::CurrencyId,
+		_amount: Self::Balance,
+		_target: Self::Balance,
+	) -> DispatchResult {
+		Ok(())
+	}
+
 	fn new_debit_auction(_amount: Self::Balance, _fix: Self::Balance) -> DispatchResult {
 		Ok(())
 	}
@@ -212,7 +251,7 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 	}
 
 	fn get_total_collateral_in_auction(_id: Self::CurrencyId) -> Self::Balance {
-		Default::default()
+		TOTAL_COLLATERAL_IN_AUCTION.with(|v| *v.borrow())
 	}
 
 	fn get_total_surplus_in_auction() -> Self::Balance {
@@ -230,11 +269,17 @@ impl cdp_treasury::Trait for Runtime {
 	type Event = TestEvent;
 	type Currency = Currencies;
 	type GetStableCurrencyId = GetStableCurrencyId;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type AuctionManagerHandler = MockAuctionManager;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = DEXModule;
+	type CollateralRegistry = CDPEngineModule;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type SurplusBuybackInterval = ();
+	type MaxSurplusBuybackSlippage = ();
+	type StakingRewardPool = ();
+	type PriceSource = ();
 	type WeightInfo = ();
 }
 pub type CDPTreasuryModule = cdp_treasury::Module<Runtime>;
@@ -243,18 +288,34 @@ parameter_types! {
 	pub const DEXModuleId: ModuleId = ModuleId(*b"aca/dexm");
 	pub const GetExchangeFee: (u32, u32) = (0, 100);
 	pub const TradingPathLimit: usize = 3;
+	pub const TradingPathCandidatesLimit: usize = 3;
+	pub const TWAPWindow: BlockNumber = 10;
+	pub const LimitOrderDeposit: Balance = 1;
+	pub const MaxLimitOrdersPerAccount: u32 = 10;
+	pub const ProtocolFeeReceiver: AccountId = 10;
 	pub EnabledTradingPairs : Vec<TradingPair> = vec![TradingPair::new(AUSD, BTC), TradingPair::new(AUSD, DOT)];
 }
 
 impl dex::Trait for Runtime {
 	type Event = TestEvent;
 	type Currency = Currencies;
-	type EnabledTradingPairs = EnabledTradingPairs;
 	type GetExchangeFee = GetExchangeFee;
 	type TradingPathLimit = TradingPathLimit;
+	type TradingPathCandidatesLimit = TradingPathCandidatesLimit;
 	type ModuleId = DEXModuleId;
 	type DEXIncentives = ();
 	type WeightInfo = ();
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type ProtocolFeeReceiver = ProtocolFeeReceiver;
+	type TWAPWindow = TWAPWindow;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type LimitOrderDeposit = LimitOrderDeposit;
+	type MaxLimitOrdersPerAccount = MaxLimitOrdersPerAccount;
+	type UnsignedPriority = UnsignedPriority;
+	type Call = Call;
+	type LegacyEnabledTradingPairs = EnabledTradingPairs;
+	type StableSwapRateProvider = ();
+	type FlashSwapCallFilter = ();
 }
 pub type DEXModule = dex::Module<Runtime>;
 
@@ -273,6 +334,13 @@ impl EmergencyShutdown for MockEmergencyShutdown {
 	}
 }
 
+pub struct MockRiskParamsUpdateOriginConvertor;
+impl Convert<Origin, RiskParamsUpdateOrigin<AccountId>> for MockRiskParamsUpdateOriginConvertor {
+	fn convert(_origin: Origin) -> RiskParamsUpdateOrigin<AccountId> {
+		RiskParamsUpdateOrigin::Other
+	}
+}
+
 ord_parameter_types! {
 	pub const One: AccountId = 1;
 }
@@ -282,26 +350,36 @@ parameter_types! {
 	pub DefaultDebitExchangeRate: ExchangeRate = ExchangeRate::one();
 	pub DefaultLiquidationPenalty: Rate = Rate::saturating_from_rational(10, 100);
 	pub const MinimumDebitValue: Balance = 2;
+	pub const KeeperDeposit: Balance = 10;
+	pub const KeeperSlashAmount: Balance = 2;
+	pub const MaxRiskParamsHistory: u32 = 3;
 	pub MaxSlippageSwapWithDEX: Ratio = Ratio::saturating_from_rational(50, 100);
 	pub const UnsignedPriority: u64 = 1 << 20;
-	pub CollateralCurrencyIds: Vec<CurrencyId> = vec![BTC, DOT];
+	pub LegacyCollateralCurrencyIds: Vec<CurrencyId> = vec![BTC, DOT];
 }
 
 impl Trait for Runtime {
 	type Event = TestEvent;
 	type PriceSource = MockPriceSource;
-	type CollateralCurrencyIds = CollateralCurrencyIds;
+	type StableCurrencyPriceSource = MockStableCurrencyPriceSource;
 	type DefaultLiquidationRatio = DefaultLiquidationRatio;
 	type DefaultDebitExchangeRate = DefaultDebitExchangeRate;
 	type DefaultLiquidationPenalty = DefaultLiquidationPenalty;
 	type MinimumDebitValue = MinimumDebitValue;
 	type GetStableCurrencyId = GetStableCurrencyId;
 	type CDPTreasury = CDPTreasuryModule;
+	type AuctionManagerHandler = MockAuctionManager;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
 	type DEX = DEXModule;
 	type UnsignedPriority = UnsignedPriority;
 	type EmergencyShutdown = MockEmergencyShutdown;
+	type Currency = Currencies;
+	type KeeperDeposit = KeeperDeposit;
+	type KeeperSlashAmount = KeeperSlashAmount;
+	type MaxRiskParamsHistory = MaxRiskParamsHistory;
+	type RiskParamsUpdateOriginConvertor = MockRiskParamsUpdateOriginConvertor;
+	type LegacyCollateralCurrencyIds = LegacyCollateralCurrencyIds;
 	type WeightInfo = ();
 }
 pub type CDPEngineModule = Module<Runtime>;
@@ -348,6 +426,19 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		dex::GenesisConfig {
+			initial_enabled_trading_pairs: EnabledTradingPairs::get(),
+		}
+		.assimilate_storage::<Runtime>(&mut t)
+		.unwrap();
+
+		GenesisConfig {
+			global_stability_fee: Default::default(),
+			collaterals_params: vec![(BTC, None, None, None, None, 0), (DOT, None, None, None, None, 0)],
+		}
+		.assimilate_storage::<Runtime>(&mut t)
+		.unwrap();
+
 		t.into()
 	}
 }
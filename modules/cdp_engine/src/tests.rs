@@ -3,9 +3,13 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok, traits::OnFinalize};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{OnFinalize, OnInitialize, OnRuntimeUpgrade},
+};
 use mock::*;
-use orml_traits::MultiCurrency;
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
+use sp_core::offchain::{testing, OffchainExt, TransactionPoolExt};
 use sp_runtime::traits::BadOrigin;
 
 #[test]
@@ -121,6 +125,14 @@ fn set_global_params_work() {
 			CDPEngineModule::global_stability_fee(),
 			Rate::saturating_from_rational(1, 10000)
 		);
+		assert_eq!(
+			CDPEngineModule::global_stability_fee_history(),
+			vec![GlobalStabilityFeeRecord {
+				block_number: 1,
+				origin: RiskParamsUpdateOrigin::Signed(1),
+				global_stability_fee: Rate::saturating_from_rational(1, 10000),
+			}]
+		);
 	});
 }
 
@@ -248,6 +260,69 @@ fn calculate_collateral_ratio_work() {
 	});
 }
 
+#[test]
+fn get_liquidation_price_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_eq!(CDPEngineModule::get_liquidation_price(BTC, 0, 50), None);
+		assert_eq!(CDPEngineModule::get_liquidation_price(BTC, 100, 0), None);
+		assert_eq!(
+			CDPEngineModule::get_liquidation_price(BTC, 100, 50),
+			Some(Price::saturating_from_rational(3, 4))
+		);
+	});
+}
+
+#[test]
+fn get_available_debit_value_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_eq!(
+			CDPEngineModule::get_available_debit_value(BTC, 100, 50, Price::saturating_from_rational(1, 1)),
+			5
+		);
+		assert_eq!(
+			CDPEngineModule::get_available_debit_value(BTC, 100, 50, Price::saturating_from_rational(2, 1)),
+			61
+		);
+		assert_eq!(
+			CDPEngineModule::get_available_debit_value(BTC, 100, 60, Price::saturating_from_rational(1, 1)),
+			0
+		);
+
+		// the liquidation ratio is used without the required collateral ratio
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(None),
+			Change::NoChange,
+		));
+		assert_eq!(
+			CDPEngineModule::get_available_debit_value(BTC, 100, 50, Price::saturating_from_rational(1, 1)),
+			16
+		);
+	});
+}
+
 #[test]
 fn check_debit_cap_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -268,6 +343,70 @@ fn check_debit_cap_work() {
 	});
 }
 
+#[test]
+fn get_debit_ceiling_utilisation_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(CDPEngineModule::get_debit_ceiling_utilisation(BTC), None);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_eq!(CDPEngineModule::get_total_debit_value(BTC), 0);
+		assert_eq!(CDPEngineModule::get_debit_ceiling_utilisation(BTC), Some(Ratio::zero()));
+
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		assert_eq!(CDPEngineModule::get_total_debit_value(BTC), 50);
+		assert_eq!(
+			CDPEngineModule::get_debit_ceiling_utilisation(BTC),
+			Some(Ratio::saturating_from_rational(50, 10000))
+		);
+	});
+}
+
+#[test]
+fn risk_params_history_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(CDPEngineModule::risk_params_history(BTC), vec![]);
+
+		for (block_number, maximum_total_debit_value) in vec![(1, 1000), (2, 2000), (3, 3000), (4, 4000)] {
+			System::set_block_number(block_number);
+			assert_ok!(CDPEngineModule::set_collateral_params(
+				Origin::signed(1),
+				BTC,
+				Change::NoChange,
+				Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+				Change::NoChange,
+				Change::NoChange,
+				Change::NewValue(maximum_total_debit_value),
+			));
+		}
+
+		let history = CDPEngineModule::risk_params_history(BTC);
+		assert_eq!(history.len(), 3);
+		assert_eq!(
+			history
+				.iter()
+				.map(|record| (record.block_number, record.params.maximum_total_debit_value))
+				.collect::<Vec<_>>(),
+			vec![(2, 2000), (3, 3000), (4, 4000)]
+		);
+		assert!(history
+			.iter()
+			.all(|record| record.origin == RiskParamsUpdateOrigin::Signed(1)));
+		assert_eq!(
+			history[2].params.liquidation_ratio,
+			Some(Ratio::saturating_from_rational(3, 2))
+		);
+		assert_eq!(history[2].params, CDPEngineModule::collateral_params(BTC));
+		assert_eq!(CDPEngineModule::risk_params_history(DOT), vec![]);
+	});
+}
+
 #[test]
 fn check_position_valid_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -283,12 +422,12 @@ fn check_position_valid_work() {
 
 		MockPriceSource::set_relative_price(None);
 		assert_noop!(
-			CDPEngineModule::check_position_valid(BTC, 100, 50),
+			CDPEngineModule::check_position_valid(&ALICE, BTC, 100, 50),
 			Error::<Runtime>::InvalidFeedPrice
 		);
 		MockPriceSource::set_relative_price(Some(Price::one()));
 
-		assert_ok!(CDPEngineModule::check_position_valid(BTC, 100, 50));
+		assert_ok!(CDPEngineModule::check_position_valid(&ALICE, BTC, 100, 50));
 	});
 }
 
@@ -305,7 +444,7 @@ fn check_position_valid_failed_when_remain_debit_value_too_small() {
 			Change::NewValue(10000),
 		));
 		assert_noop!(
-			CDPEngineModule::check_position_valid(BTC, 2, 1),
+			CDPEngineModule::check_position_valid(&ALICE, BTC, 2, 1),
 			Error::<Runtime>::RemainDebitValueTooSmall,
 		);
 	});
@@ -324,7 +463,7 @@ fn check_position_valid_ratio_below_liquidate_ratio() {
 			Change::NewValue(10000),
 		));
 		assert_noop!(
-			CDPEngineModule::check_position_valid(BTC, 91, 50),
+			CDPEngineModule::check_position_valid(&ALICE, BTC, 91, 50),
 			Error::<Runtime>::BelowLiquidationRatio,
 		);
 	});
@@ -343,7 +482,7 @@ fn check_position_valid_ratio_below_required_ratio() {
 			Change::NewValue(10000),
 		));
 		assert_noop!(
-			CDPEngineModule::check_position_valid(BTC, 89, 50),
+			CDPEngineModule::check_position_valid(&ALICE, BTC, 89, 50),
 			Error::<Runtime>::BelowRequiredCollateralRatio
 		);
 	});
@@ -459,6 +598,217 @@ fn liquidate_unsafe_cdp_by_collateral_auction() {
 	});
 }
 
+#[test]
+fn set_partial_liquidation_buffer_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPEngineModule::set_partial_liquidation_buffer(
+				Origin::signed(5),
+				BTC,
+				Some(Ratio::saturating_from_rational(1, 2))
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::set_partial_liquidation_buffer(
+				Origin::signed(1),
+				LDOT,
+				Some(Ratio::saturating_from_rational(1, 2))
+			),
+			Error::<Runtime>::InvalidCollateralType
+		);
+
+		assert_ok!(CDPEngineModule::set_partial_liquidation_buffer(
+			Origin::signed(1),
+			BTC,
+			Some(Ratio::saturating_from_rational(1, 2))
+		));
+		let update_partial_liquidation_buffer_event = TestEvent::cdp_engine(RawEvent::PartialLiquidationBufferUpdated(
+			BTC,
+			Some(Ratio::saturating_from_rational(1, 2)),
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == update_partial_liquidation_buffer_event));
+		assert_eq!(
+			CDPEngineModule::partial_liquidation_buffer(BTC),
+			Some(Ratio::saturating_from_rational(1, 2))
+		);
+
+		assert_ok!(CDPEngineModule::set_partial_liquidation_buffer(
+			Origin::signed(1),
+			BTC,
+			None
+		));
+		assert_eq!(CDPEngineModule::partial_liquidation_buffer(BTC), None);
+		assert_eq!(
+			CDPEngineModule::risk_params_history(BTC)
+				.into_iter()
+				.map(|record| record.partial_liquidation_buffer)
+				.collect::<Vec<_>>(),
+			vec![Some(Ratio::saturating_from_rational(1, 2)), None]
+		);
+	});
+}
+
+#[test]
+fn get_partial_liquidation_amounts_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_eq!(CDPEngineModule::get_partial_liquidation_amounts(BTC, 100, 50), None);
+
+		assert_ok!(CDPEngineModule::set_partial_liquidation_buffer(
+			Origin::signed(1),
+			BTC,
+			Some(Ratio::saturating_from_rational(1, 2))
+		));
+		assert_eq!(
+			CDPEngineModule::get_partial_liquidation_amounts(BTC, 100, 50),
+			Some((79, 44))
+		);
+
+		// the closed debit would exceed the debit of the CDP
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_rational(1, 2)));
+		assert_eq!(CDPEngineModule::get_partial_liquidation_amounts(BTC, 100, 50), None);
+
+		// the remaining debit value would be dust
+		MockPriceSource::set_relative_price(Some(Price::one()));
+		assert_eq!(CDPEngineModule::get_partial_liquidation_amounts(BTC, 91, 50), None);
+
+		// the confiscated collateral is worth more than the target ratio of the closed debit value
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(6, 5))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_eq!(CDPEngineModule::get_partial_liquidation_amounts(BTC, 100, 90), None);
+
+		MockPriceSource::set_relative_price(None);
+		assert_eq!(CDPEngineModule::get_partial_liquidation_amounts(BTC, 100, 50), None);
+	});
+}
+
+#[test]
+fn partial_liquidate_unsafe_cdp_by_collateral_auction() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::set_partial_liquidation_buffer(
+			Origin::signed(1),
+			BTC,
+			Some(Ratio::saturating_from_rational(1, 2))
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
+
+		let partial_liquidate_unsafe_cdp_event = TestEvent::cdp_engine(RawEvent::PartialLiquidateUnsafeCDP(
+			BTC,
+			ALICE,
+			79,
+			44,
+			LiquidationStrategy::Auction,
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == partial_liquidate_unsafe_cdp_event));
+
+		assert_eq!(CDPTreasuryModule::debit_pool(), 44);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 6);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 21);
+		assert_eq!(CDPEngineModule::is_cdp_unsafe(BTC, 21, 6), false);
+	});
+}
+
+#[test]
+fn partial_liquidate_unsafe_cdp_by_swap() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			AUSD,
+			BTC,
+			1000,
+			100,
+			false
+		));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::set_partial_liquidation_buffer(
+			Origin::signed(1),
+			BTC,
+			Some(Ratio::saturating_from_rational(1, 2))
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
+
+		let partial_liquidate_unsafe_cdp_event = TestEvent::cdp_engine(RawEvent::PartialLiquidateUnsafeCDP(
+			BTC,
+			ALICE,
+			79,
+			44,
+			LiquidationStrategy::Exchange,
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == partial_liquidate_unsafe_cdp_event));
+
+		assert_eq!(DEXModule::get_liquidity(BTC, AUSD), (106, 948));
+		assert_eq!(CDPTreasuryModule::debit_pool(), 44);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 973);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 6);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 21);
+	});
+}
+
 #[test]
 fn on_finalize_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -580,3 +930,944 @@ fn settle_cdp_has_debit_work() {
 		);
 	});
 }
+
+#[test]
+fn close_cdp_has_debit_by_dex_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			AUSD,
+			BTC,
+			1000,
+			100,
+			false
+		));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 0));
+		assert_noop!(
+			CDPEngineModule::close_cdp_has_debit_by_dex(ALICE, BTC, 100),
+			Error::<Runtime>::NoDebitValue,
+		);
+
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 0, 50));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_noop!(
+			CDPEngineModule::close_cdp_has_debit_by_dex(ALICE, BTC, 100),
+			Error::<Runtime>::MustBeSafe,
+		);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+
+		// selling 5 BTC is not enough to repay 50 aUSD
+		assert_noop!(
+			with_transaction_result(|| CDPEngineModule::close_cdp_has_debit_by_dex(ALICE, BTC, 5)),
+			dex::Error::<Runtime>::ExcessiveSupplyAmount,
+		);
+
+		assert_ok!(CDPEngineModule::close_cdp_has_debit_by_dex(ALICE, BTC, 10));
+		let close_cdp_in_debit_by_dex_event =
+			TestEvent::cdp_engine(RawEvent::CloseCDPInDebitByDEX(BTC, ALICE, 6, 94, 50));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == close_cdp_in_debit_by_dex_event));
+
+		assert_eq!(DEXModule::get_liquidity(BTC, AUSD), (106, 950));
+		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 50);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 994);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+	});
+}
+
+#[test]
+fn expand_position_collateral_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			AUSD,
+			BTC,
+			100,
+			100,
+			false
+		));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(30),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 20));
+		assert_noop!(
+			CDPEngineModule::expand_position_collateral(&ALICE, LDOT, Ratio::saturating_from_rational(3, 1), 0),
+			Error::<Runtime>::InvalidCollateralType,
+		);
+
+		assert_eq!(
+			CDPEngineModule::get_expand_debit_balance(&ALICE, BTC, Ratio::saturating_from_rational(3, 1)),
+			Some(20)
+		);
+		assert_eq!(
+			CDPEngineModule::get_expand_debit_balance(&ALICE, BTC, Ratio::saturating_from_rational(6, 1)),
+			None
+		);
+		assert_eq!(
+			CDPEngineModule::get_expand_debit_balance(&ALICE, BTC, Ratio::one()),
+			None
+		);
+		assert_noop!(
+			CDPEngineModule::expand_position_collateral(&ALICE, BTC, Ratio::saturating_from_rational(6, 1), 0),
+			Error::<Runtime>::InvalidTargetCollateralRatio,
+		);
+
+		// below the required collateral ratio after the slippage
+		assert_noop!(
+			with_transaction_result(|| {
+				CDPEngineModule::expand_position_collateral(&ALICE, BTC, Ratio::saturating_from_rational(2, 1), 0)
+			}),
+			Error::<Runtime>::BelowRequiredCollateralRatio,
+		);
+
+		// exceed the debit value hard cap
+		assert_noop!(
+			with_transaction_result(|| {
+				CDPEngineModule::expand_position_collateral(&ALICE, BTC, Ratio::saturating_from_rational(3, 1), 0)
+			}),
+			Error::<Runtime>::ExceedDebitValueHardCap,
+		);
+
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(10000),
+		));
+
+		// the swap gets less collateral than the minimum
+		assert_noop!(
+			with_transaction_result(|| {
+				CDPEngineModule::expand_position_collateral(&ALICE, BTC, Ratio::saturating_from_rational(3, 1), 17)
+			}),
+			dex::Error::<Runtime>::InsufficientTargetAmount,
+		);
+		assert_ok!(CDPEngineModule::expand_position_collateral(
+			&ALICE,
+			BTC,
+			Ratio::saturating_from_rational(3, 1),
+			16
+		));
+
+		let expand_cdp_collateral_by_dex_event =
+			TestEvent::cdp_engine(RawEvent::ExpandCDPCollateralByDEX(BTC, ALICE, 16, 20));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == expand_cdp_collateral_by_dex_event));
+
+		assert_eq!(DEXModule::get_liquidity(BTC, AUSD), (84, 120));
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 20);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 40);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 116);
+		assert_eq!(LoansModule::total_positions(BTC).collateral, 116);
+	});
+}
+
+#[test]
+fn register_and_deregister_keeper_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPEngineModule::deregister_keeper(Origin::signed(CAROL)),
+			Error::<Runtime>::NotKeeper
+		);
+
+		assert_ok!(CDPEngineModule::register_keeper(Origin::signed(CAROL)));
+		let keeper_registered_event = TestEvent::cdp_engine(RawEvent::KeeperRegistered(CAROL, 10));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == keeper_registered_event));
+		assert_eq!(CDPEngineModule::keepers(CAROL), Some(10));
+		assert_eq!(Currencies::free_balance(AUSD, &CAROL), 990);
+		assert_eq!(Currencies::reserved_balance(AUSD, &CAROL), 10);
+		assert_noop!(
+			CDPEngineModule::register_keeper(Origin::signed(CAROL)),
+			Error::<Runtime>::AlreadyKeeper
+		);
+
+		assert_ok!(CDPEngineModule::deregister_keeper(Origin::signed(CAROL)));
+		let keeper_deregistered_event = TestEvent::cdp_engine(RawEvent::KeeperDeregistered(CAROL));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == keeper_deregistered_event));
+		assert_eq!(CDPEngineModule::keepers(CAROL), None);
+		assert_eq!(Currencies::free_balance(AUSD, &CAROL), 1000);
+		assert_eq!(Currencies::reserved_balance(AUSD, &CAROL), 0);
+	});
+}
+
+#[test]
+fn set_keeper_reward_rate_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPEngineModule::set_keeper_reward_rate(Origin::signed(5), Rate::saturating_from_rational(1, 2)),
+			BadOrigin
+		);
+		assert_ok!(CDPEngineModule::set_keeper_reward_rate(
+			Origin::signed(1),
+			Rate::saturating_from_rational(1, 2)
+		));
+		let keeper_reward_rate_updated_event =
+			TestEvent::cdp_engine(RawEvent::KeeperRewardRateUpdated(Rate::saturating_from_rational(1, 2)));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == keeper_reward_rate_updated_event));
+		assert_eq!(
+			CDPEngineModule::keeper_reward_rate(),
+			Rate::saturating_from_rational(1, 2)
+		);
+		assert_noop!(
+			CDPEngineModule::set_keeper_reward_rate(Origin::signed(1), Rate::saturating_from_rational(11, 10)),
+			Error::<Runtime>::InvalidKeeperRewardRate
+		);
+	});
+}
+
+#[test]
+fn liquidate_by_keeper_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::set_keeper_reward_rate(
+			Origin::signed(1),
+			Rate::saturating_from_rational(1, 2)
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		assert_ok!(CDPEngineModule::adjust_position(&BOB, BTC, 100, 50));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+
+		assert_noop!(CDPEngineModule::liquidate(Origin::root(), BTC, ALICE), BadOrigin);
+		assert_noop!(
+			CDPEngineModule::liquidate(Origin::signed(CAROL), BTC, ALICE),
+			Error::<Runtime>::NotKeeper
+		);
+		assert_ok!(CDPEngineModule::register_keeper(Origin::signed(CAROL)));
+
+		// the penalty is still in the collateral auction, no free surplus to reward
+		assert_ok!(CDPEngineModule::liquidate(Origin::signed(CAROL), BTC, BOB));
+		assert_eq!(LoansModule::positions(BTC, BOB).debit, 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
+		assert_eq!(Currencies::free_balance(AUSD, &CAROL), 990);
+
+		// the reward is paid out of the surplus
+		assert_ok!(CDPTreasuryModule::on_system_surplus(200));
+		assert_ok!(CDPEngineModule::liquidate(Origin::signed(CAROL), BTC, ALICE));
+		let keeper_rewarded_event = TestEvent::cdp_engine(RawEvent::KeeperRewarded(CAROL, 5));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == keeper_rewarded_event));
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 100);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 195);
+		assert_eq!(Currencies::free_balance(AUSD, &CAROL), 995);
+
+		// the keeper losing the race to the liquidation in the same block is not slashed
+		assert_noop!(
+			CDPEngineModule::liquidate(Origin::signed(CAROL), BTC, ALICE),
+			Error::<Runtime>::MustBeUnsafe
+		);
+		assert_eq!(CDPEngineModule::processed_cdps(), vec![(BTC, BOB), (BTC, ALICE)]);
+
+		// the deposit is slashed for the invalid liquidation in the later block
+		CDPEngineModule::on_finalize(1);
+		assert_eq!(CDPEngineModule::processed_cdps(), vec![]);
+		System::set_block_number(2);
+		assert_ok!(CDPEngineModule::liquidate(Origin::signed(CAROL), BTC, ALICE));
+		let keeper_slashed_event = TestEvent::cdp_engine(RawEvent::KeeperSlashed(CAROL, 2));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == keeper_slashed_event));
+		assert_eq!(CDPEngineModule::keepers(CAROL), Some(8));
+		assert_eq!(Currencies::reserved_balance(AUSD, &CAROL), 8);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 197);
+
+		// unsigned liquidation fails as usual
+		assert_noop!(
+			CDPEngineModule::liquidate(Origin::none(), BTC, ALICE),
+			Error::<Runtime>::MustBeUnsafe
+		);
+	});
+}
+
+#[test]
+fn settle_by_keeper_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::set_keeper_reward_rate(
+			Origin::signed(1),
+			Rate::saturating_from_rational(1, 2)
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		assert_ok!(CDPEngineModule::register_keeper(Origin::signed(CAROL)));
+		mock_shutdown();
+
+		// there is no liquidation penalty to reward after shutdown
+		assert_ok!(CDPEngineModule::settle(Origin::signed(CAROL), BTC, ALICE));
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
+		assert_eq!(Currencies::free_balance(AUSD, &CAROL), 990);
+		assert_eq!(Currencies::reserved_balance(AUSD, &CAROL), 10);
+
+		// the keeper losing the race to the settlement in the same block is not slashed
+		assert_noop!(
+			CDPEngineModule::settle(Origin::signed(CAROL), BTC, ALICE),
+			Error::<Runtime>::NoDebitValue
+		);
+
+		// the deposit is slashed for the invalid settlement in the later block
+		CDPEngineModule::on_finalize(1);
+		System::set_block_number(2);
+		assert_ok!(CDPEngineModule::settle(Origin::signed(CAROL), BTC, ALICE));
+		let keeper_slashed_event = TestEvent::cdp_engine(RawEvent::KeeperSlashed(CAROL, 2));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == keeper_slashed_event));
+		assert_eq!(CDPEngineModule::keepers(CAROL), Some(8));
+		assert_eq!(Currencies::reserved_balance(AUSD, &CAROL), 8);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 2);
+	});
+}
+
+#[test]
+fn set_cross_margin_liquidation_order_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPEngineModule::set_cross_margin_liquidation_order(Origin::signed(5), vec![BTC, DOT]),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::set_cross_margin_liquidation_order(Origin::signed(1), vec![BTC, LDOT]),
+			Error::<Runtime>::InvalidLiquidationOrder
+		);
+		assert_noop!(
+			CDPEngineModule::set_cross_margin_liquidation_order(Origin::signed(1), vec![BTC, DOT, BTC]),
+			Error::<Runtime>::InvalidLiquidationOrder
+		);
+		assert_ok!(CDPEngineModule::set_cross_margin_liquidation_order(
+			Origin::signed(1),
+			vec![DOT, BTC]
+		));
+
+		let update_liquidation_order_event =
+			TestEvent::cdp_engine(RawEvent::CrossMarginLiquidationOrderUpdated(vec![DOT, BTC]));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == update_liquidation_order_event));
+		assert_eq!(CDPEngineModule::cross_margin_liquidation_order(), vec![DOT, BTC]);
+	});
+}
+
+#[test]
+fn check_cross_margin_position_valid_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		for currency_id in vec![BTC, DOT] {
+			assert_ok!(CDPEngineModule::set_collateral_params(
+				Origin::signed(1),
+				currency_id,
+				Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+				Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+				Change::NewValue(10000),
+			));
+		}
+		assert_ok!(CDPEngineModule::set_cross_margin_liquidation_order(
+			Origin::signed(1),
+			vec![BTC]
+		));
+		assert_ok!(CDPEngineModule::set_cross_margin(&ALICE, true));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		assert_eq!(CDPEngineModule::is_cross_margin_collateral(&ALICE, BTC), true);
+		assert_eq!(CDPEngineModule::is_cross_margin_collateral(&ALICE, DOT), false);
+		assert_eq!(CDPEngineModule::is_cross_margin_collateral(&BOB, BTC), false);
+
+		// DOT is not in the cross-margin vault
+		assert_noop!(
+			CDPEngineModule::check_position_valid(&ALICE, DOT, 0, 5),
+			Error::<Runtime>::BelowRequiredCollateralRatio
+		);
+
+		assert_ok!(CDPEngineModule::set_cross_margin_liquidation_order(
+			Origin::signed(1),
+			vec![BTC, DOT]
+		));
+		assert_ok!(CDPEngineModule::check_position_valid(&ALICE, DOT, 0, 5));
+		assert_noop!(
+			CDPEngineModule::check_position_valid(&BOB, DOT, 0, 5),
+			Error::<Runtime>::BelowRequiredCollateralRatio
+		);
+		assert_noop!(
+			CDPEngineModule::check_position_valid(&ALICE, DOT, 0, 6),
+			Error::<Runtime>::BelowRequiredCollateralRatio
+		);
+		assert_noop!(
+			CDPEngineModule::check_position_valid(&ALICE, DOT, 0, 1),
+			Error::<Runtime>::RemainDebitValueTooSmall
+		);
+
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, DOT, 0, 5));
+		assert_noop!(
+			CDPEngineModule::set_cross_margin(&ALICE, false),
+			Error::<Runtime>::BelowRequiredCollateralRatio
+		);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, DOT, 10, 0));
+		assert_ok!(CDPEngineModule::set_cross_margin(&ALICE, false));
+		assert_eq!(CDPEngineModule::is_cross_margin(&ALICE), false);
+	});
+}
+
+#[test]
+fn close_cdp_has_debit_by_dex_in_cross_margin_vault_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			AUSD,
+			BTC,
+			1000,
+			100,
+			false
+		));
+		for currency_id in vec![BTC, DOT] {
+			assert_ok!(CDPEngineModule::set_collateral_params(
+				Origin::signed(1),
+				currency_id,
+				Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+				Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+				Change::NewValue(10000),
+			));
+		}
+		assert_ok!(CDPEngineModule::set_cross_margin_liquidation_order(
+			Origin::signed(1),
+			vec![BTC, DOT]
+		));
+		assert_ok!(CDPEngineModule::set_cross_margin(&ALICE, true));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, DOT, 0, 5));
+
+		// the debit of DOT is backed by the collateral of BTC
+		assert_noop!(
+			CDPEngineModule::close_cdp_has_debit_by_dex(ALICE, BTC, 10),
+			Error::<Runtime>::BelowRequiredCollateralRatio,
+		);
+
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, DOT, 0, -5));
+		assert_ok!(CDPEngineModule::close_cdp_has_debit_by_dex(ALICE, BTC, 10));
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+	});
+}
+
+#[test]
+fn liquidate_cross_margin_vault_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		for currency_id in vec![BTC, DOT] {
+			assert_ok!(CDPEngineModule::set_collateral_params(
+				Origin::signed(1),
+				currency_id,
+				Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+				Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+				Change::NewValue(10000),
+			));
+		}
+		assert_ok!(CDPEngineModule::set_cross_margin_liquidation_order(
+			Origin::signed(1),
+			vec![BTC, DOT]
+		));
+		assert_ok!(CDPEngineModule::set_cross_margin(&ALICE, true));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 40));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, DOT, 100, 40));
+		assert_eq!(CDPEngineModule::is_cross_margin_vault_unsafe(&ALICE), false);
+		assert_noop!(
+			CDPEngineModule::liquidate_unsafe_cdp(ALICE, DOT),
+			Error::<Runtime>::MustBeUnsafe,
+		);
+
+		for currency_id in vec![BTC, DOT] {
+			assert_ok!(CDPEngineModule::set_collateral_params(
+				Origin::signed(1),
+				currency_id,
+				Change::NoChange,
+				Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+				Change::NoChange,
+				Change::NoChange,
+				Change::NoChange,
+			));
+		}
+		assert_eq!(CDPEngineModule::is_cross_margin_vault_unsafe(&ALICE), true);
+		assert_eq!(CDPEngineModule::is_position_unsafe(&ALICE, DOT, 100, 40), true);
+
+		// the penalty is 20% of the total debit value
+		assert_eq!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, DOT), Ok(16));
+
+		let liquidate_collateral_event = TestEvent::cdp_engine(RawEvent::LiquidateCrossMarginCollateral(
+			ALICE,
+			BTC,
+			100,
+			96,
+			LiquidationStrategy::Auction,
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == liquidate_collateral_event));
+		let liquidate_vault_event = TestEvent::cdp_engine(RawEvent::LiquidateCrossMarginVault(ALICE, 80));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == liquidate_vault_event));
+
+		// the BTC collateral covers the target, the DOT collateral is left in the vault
+		assert_eq!(CDPTreasuryModule::debit_pool(), 80);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+		assert_eq!(LoansModule::positions(DOT, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(DOT, ALICE).collateral, 100);
+	});
+}
+
+fn stability_fee_controller_params() -> StabilityFeeControllerParams<BlockNumber> {
+	StabilityFeeControllerParams {
+		target_price: Price::one(),
+		price_tolerance: Price::saturating_from_rational(1, 100),
+		min_stability_fee: Rate::zero(),
+		max_stability_fee: Rate::saturating_from_rational(3, 1000),
+		step: Rate::saturating_from_rational(1, 1000),
+		max_change_per_era: Rate::saturating_from_rational(2, 1000),
+		era_length: 10,
+	}
+}
+
+#[test]
+fn set_stability_fee_controller_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPEngineModule::set_stability_fee_controller(Origin::signed(5), Some(stability_fee_controller_params())),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::set_stability_fee_controller(
+				Origin::signed(1),
+				Some(StabilityFeeControllerParams {
+					min_stability_fee: Rate::saturating_from_rational(5, 1000),
+					..stability_fee_controller_params()
+				})
+			),
+			Error::<Runtime>::InvalidStabilityFeeControllerParams
+		);
+		assert_noop!(
+			CDPEngineModule::set_stability_fee_controller(
+				Origin::signed(1),
+				Some(StabilityFeeControllerParams {
+					era_length: 0,
+					..stability_fee_controller_params()
+				})
+			),
+			Error::<Runtime>::InvalidStabilityFeeControllerParams
+		);
+
+		assert_ok!(CDPEngineModule::set_stability_fee_controller(
+			Origin::signed(1),
+			Some(stability_fee_controller_params())
+		));
+		let update_controller_event = TestEvent::cdp_engine(RawEvent::StabilityFeeControllerUpdated(Some(
+			stability_fee_controller_params(),
+		)));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == update_controller_event));
+		assert_eq!(
+			CDPEngineModule::stability_fee_controller(),
+			Some(stability_fee_controller_params())
+		);
+
+		assert_ok!(CDPEngineModule::set_stability_fee_controller(Origin::signed(1), None));
+		assert_eq!(CDPEngineModule::stability_fee_controller(), None);
+	});
+}
+
+#[test]
+fn stability_fee_controller_adjust_global_stability_fee_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		MockStableCurrencyPriceSource::set_price(Some(Price::saturating_from_rational(95, 100)));
+
+		// the controller is disabled
+		CDPEngineModule::on_initialize(1);
+		assert_eq!(CDPEngineModule::global_stability_fee(), Rate::zero());
+
+		assert_ok!(CDPEngineModule::set_stability_fee_controller(
+			Origin::signed(1),
+			Some(stability_fee_controller_params())
+		));
+
+		// the market price is within the tolerance
+		MockStableCurrencyPriceSource::set_price(Some(Price::saturating_from_rational(995, 1000)));
+		CDPEngineModule::on_initialize(1);
+		assert_eq!(CDPEngineModule::global_stability_fee(), Rate::zero());
+
+		// raise the stability fee when stablecoin is below the peg
+		MockStableCurrencyPriceSource::set_price(Some(Price::saturating_from_rational(95, 100)));
+		CDPEngineModule::on_initialize(2);
+		assert_eq!(
+			CDPEngineModule::global_stability_fee(),
+			Rate::saturating_from_rational(1, 1000)
+		);
+		let adjust_event = TestEvent::cdp_engine(RawEvent::GlobalStabilityFeeAdjusted(
+			Price::saturating_from_rational(95, 100),
+			Rate::saturating_from_rational(1, 1000),
+		));
+		assert!(System::events().iter().any(|record| record.event == adjust_event));
+
+		CDPEngineModule::on_initialize(3);
+		assert_eq!(
+			CDPEngineModule::global_stability_fee(),
+			Rate::saturating_from_rational(2, 1000)
+		);
+
+		// the change in this era reaches the cap
+		CDPEngineModule::on_initialize(4);
+		assert_eq!(
+			CDPEngineModule::global_stability_fee(),
+			Rate::saturating_from_rational(2, 1000)
+		);
+		assert_eq!(
+			CDPEngineModule::stability_fee_controller_era(),
+			(0, Rate::saturating_from_rational(2, 1000))
+		);
+
+		// a new era starts, the stability fee reaches the upper bound
+		CDPEngineModule::on_initialize(10);
+		assert_eq!(
+			CDPEngineModule::global_stability_fee(),
+			Rate::saturating_from_rational(3, 1000)
+		);
+		CDPEngineModule::on_initialize(11);
+		assert_eq!(
+			CDPEngineModule::global_stability_fee(),
+			Rate::saturating_from_rational(3, 1000)
+		);
+
+		// lower the stability fee when stablecoin is above the peg
+		MockStableCurrencyPriceSource::set_price(Some(Price::saturating_from_rational(105, 100)));
+		CDPEngineModule::on_initialize(12);
+		assert_eq!(
+			CDPEngineModule::global_stability_fee(),
+			Rate::saturating_from_rational(2, 1000)
+		);
+		CDPEngineModule::on_initialize(13);
+		assert_eq!(
+			CDPEngineModule::global_stability_fee(),
+			Rate::saturating_from_rational(2, 1000)
+		);
+
+		// no adjustment without the market price or after shutdown
+		MockStableCurrencyPriceSource::set_price(None);
+		CDPEngineModule::on_initialize(20);
+		assert_eq!(
+			CDPEngineModule::global_stability_fee(),
+			Rate::saturating_from_rational(2, 1000)
+		);
+		MockStableCurrencyPriceSource::set_price(Some(Price::saturating_from_rational(105, 100)));
+		mock_shutdown();
+		CDPEngineModule::on_initialize(20);
+		assert_eq!(
+			CDPEngineModule::global_stability_fee(),
+			Rate::saturating_from_rational(2, 1000)
+		);
+
+		// the adjustments are recorded in the history once per era
+		assert_eq!(
+			CDPEngineModule::global_stability_fee_history()
+				.into_iter()
+				.map(|record| (record.origin, record.global_stability_fee))
+				.collect::<Vec<_>>(),
+			vec![(
+				RiskParamsUpdateOrigin::StabilityFeeController,
+				Rate::saturating_from_rational(2, 1000)
+			)]
+		);
+	});
+}
+
+#[test]
+fn register_collateral_type_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPEngineModule::register_collateral_type(Origin::signed(5), LDOT),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::register_collateral_type(Origin::signed(1), AUSD),
+			Error::<Runtime>::InvalidCollateralType
+		);
+		assert_noop!(
+			CDPEngineModule::register_collateral_type(Origin::signed(1), BTC),
+			Error::<Runtime>::CollateralTypeAlreadyRegistered
+		);
+		assert_eq!(CDPEngineModule::is_collateral(LDOT), false);
+
+		assert_ok!(CDPEngineModule::register_collateral_type(Origin::signed(1), LDOT));
+		let register_event = TestEvent::cdp_engine(RawEvent::CollateralTypeRegistered(LDOT));
+		assert!(System::events().iter().any(|record| record.event == register_event));
+		assert_eq!(CDPEngineModule::is_collateral(LDOT), true);
+		assert_eq!(CDPEngineModule::is_active_collateral(LDOT), true);
+
+		let mut collateral_currency_ids = CDPEngineModule::collateral_currency_ids();
+		collateral_currency_ids.sort();
+		let mut expected_currency_ids = vec![BTC, DOT, LDOT];
+		expected_currency_ids.sort();
+		assert_eq!(collateral_currency_ids, expected_currency_ids);
+	});
+}
+
+#[test]
+fn pause_and_resume_collateral_type_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+
+		assert_noop!(
+			CDPEngineModule::pause_collateral_type(Origin::signed(5), BTC),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::pause_collateral_type(Origin::signed(1), LDOT),
+			Error::<Runtime>::InvalidCollateralType
+		);
+		assert_ok!(CDPEngineModule::pause_collateral_type(Origin::signed(1), BTC));
+		let pause_event = TestEvent::cdp_engine(RawEvent::CollateralTypePaused(BTC));
+		assert!(System::events().iter().any(|record| record.event == pause_event));
+		assert_eq!(CDPEngineModule::is_collateral(BTC), true);
+		assert_eq!(CDPEngineModule::is_active_collateral(BTC), false);
+
+		assert_noop!(
+			CDPEngineModule::adjust_position(&ALICE, BTC, 10, 0),
+			Error::<Runtime>::CollateralTypePaused,
+		);
+		assert_noop!(
+			CDPEngineModule::adjust_position(&ALICE, BTC, 0, 10),
+			Error::<Runtime>::CollateralTypePaused,
+		);
+		assert_noop!(
+			CDPEngineModule::expand_position_collateral(&ALICE, BTC, Ratio::saturating_from_rational(2, 1), 0),
+			Error::<Runtime>::CollateralTypePaused,
+		);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, -10, -20));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 90);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 30);
+
+		assert_noop!(
+			CDPEngineModule::resume_collateral_type(Origin::signed(5), BTC),
+			BadOrigin
+		);
+		assert_ok!(CDPEngineModule::resume_collateral_type(Origin::signed(1), BTC));
+		let resume_event = TestEvent::cdp_engine(RawEvent::CollateralTypeResumed(BTC));
+		assert!(System::events().iter().any(|record| record.event == resume_event));
+		assert_eq!(CDPEngineModule::is_active_collateral(BTC), true);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 10, 10));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 40);
+	});
+}
+
+#[test]
+fn on_runtime_upgrade_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(CDPEngineModule::on_runtime_upgrade(), 0);
+
+		CollateralTypes::remove(BTC);
+		CollateralTypes::remove(DOT);
+		CollateralTypesMigrated::put(false);
+		assert_eq!(CDPEngineModule::is_collateral(BTC), false);
+
+		CDPEngineModule::on_runtime_upgrade();
+		assert_eq!(CDPEngineModule::collateral_types(BTC), Some(CollateralStatus::Active));
+		assert_eq!(CDPEngineModule::collateral_types(DOT), Some(CollateralStatus::Active));
+		assert_eq!(CollateralTypesMigrated::get(), true);
+	});
+}
+
+#[test]
+fn retire_collateral_type_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::set_partial_liquidation_buffer(
+			Origin::signed(1),
+			BTC,
+			Some(Ratio::saturating_from_rational(1, 10))
+		));
+		assert_ok!(CDPEngineModule::set_cross_margin_liquidation_order(
+			Origin::signed(1),
+			vec![BTC, DOT]
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+
+		assert_noop!(
+			CDPEngineModule::retire_collateral_type(Origin::signed(5), BTC),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::retire_collateral_type(Origin::signed(1), LDOT),
+			Error::<Runtime>::InvalidCollateralType
+		);
+		assert_noop!(
+			CDPEngineModule::retire_collateral_type(Origin::signed(1), BTC),
+			Error::<Runtime>::CollateralTypeInUse
+		);
+
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, -100, -50));
+		mock_total_collateral_in_auction(10);
+		assert_noop!(
+			CDPEngineModule::retire_collateral_type(Origin::signed(1), BTC),
+			Error::<Runtime>::CollateralTypeInUse
+		);
+
+		mock_total_collateral_in_auction(0);
+		assert_ok!(CDPEngineModule::retire_collateral_type(Origin::signed(1), BTC));
+		let retire_event = TestEvent::cdp_engine(RawEvent::CollateralTypeRetired(BTC));
+		assert!(System::events().iter().any(|record| record.event == retire_event));
+		assert_eq!(CDPEngineModule::is_collateral(BTC), false);
+		assert_eq!(CDPEngineModule::collateral_currency_ids(), vec![DOT]);
+		assert_eq!(CDPEngineModule::collateral_params(BTC), RiskManagementParams::default());
+		assert_eq!(CDPEngineModule::partial_liquidation_buffer(BTC), None);
+		assert_eq!(CDPEngineModule::cross_margin_liquidation_order(), vec![DOT]);
+		let history = CDPEngineModule::risk_params_history(BTC);
+		assert_eq!(history.len(), 3);
+		assert_eq!(
+			history[2],
+			RiskParamsRecord {
+				block_number: 1,
+				origin: RiskParamsUpdateOrigin::Signed(1),
+				params: RiskManagementParams::default(),
+				partial_liquidation_buffer: None,
+				retired: true,
+			}
+		);
+
+		assert_noop!(
+			CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50),
+			Error::<Runtime>::InvalidCollateralType,
+		);
+		assert_noop!(
+			CDPEngineModule::retire_collateral_type(Origin::signed(1), BTC),
+			Error::<Runtime>::InvalidCollateralType
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_resumes_after_collateral_type_retired() {
+	let (offchain, _) = testing::TestOffchainExt::new();
+	let (pool, _) = testing::TestTransactionPoolExt::new();
+	let mut ext = ExtBuilder::default().build();
+	ext.register_extension(OffchainExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		let to_be_continue = StorageValueRef::persistent(&OFFCHAIN_WORKER_DATA);
+		to_be_continue.set(&(BTC, Some(vec![1u8, 2, 3])));
+		assert_ok!(CDPEngineModule::retire_collateral_type(Origin::signed(1), BTC));
+		assert_eq!(CDPEngineModule::collateral_currency_ids(), vec![DOT]);
+
+		// the cursor of the retired collateral type falls back to the first one
+		assert_ok!(CDPEngineModule::_offchain_worker());
+		assert_eq!(
+			to_be_continue.get::<(CurrencyId, Option<Vec<u8>>)>(),
+			Some(Some((DOT, None)))
+		);
+	});
+}
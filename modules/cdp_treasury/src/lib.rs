@@ -6,9 +6,14 @@
 //! CDPs, and handle excessive surplus or debits timely in order to keep the
 //! system healthy with low risk. It's the only entry for issuing/burning stable
 //! coin for whole system.
+//!
+//! The surplus exceeding the buffer can be used to buy back native currency on
+//! DEX periodically, which is burned or sent to the staking reward pool
+//! according to the surplus handling strategy picked by governance.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Decode, Encode};
 use frame_support::{
 	decl_error, decl_event, decl_module, decl_storage, ensure,
 	traits::{EnsureOrigin, Get},
@@ -19,10 +24,11 @@ use orml_traits::{MultiCurrency, MultiCurrencyExtended};
 use orml_utilities::with_transaction_result;
 use primitives::{Balance, CurrencyId};
 use sp_runtime::{
-	traits::{AccountIdConversion, One, Zero},
-	DispatchError, DispatchResult, FixedPointNumber, ModuleId,
+	traits::{AccountIdConversion, One, Saturating, Zero},
+	DispatchError, DispatchResult, FixedPointNumber, ModuleId, RuntimeDebug,
 };
-use support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, Ratio};
+use sp_std::{prelude::*, vec};
+use support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, CollateralRegistry, DEXManager, PriceProvider, Ratio};
 
 mod benchmarking;
 mod default_weight;
@@ -34,6 +40,45 @@ pub trait WeightInfo {
 	fn auction_debit() -> Weight;
 	fn auction_collateral() -> Weight;
 	fn set_collateral_auction_maximum_size() -> Weight;
+	fn set_collateral_auction_type() -> Weight;
+	fn set_surplus_handling_strategy() -> Weight;
+	fn set_surplus_buffer_size() -> Weight;
+	fn buyback_with_surplus() -> Weight;
+}
+
+/// The type of auction to sell the collateral under specific collateral type
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum CollateralAuctionType {
+	/// English auction, bid on increasing price and then on decreasing
+	/// collateral amount after the target is reached
+	English,
+	/// Dutch auction, sell at descending price and can be taken partially at
+	/// any time
+	Dutch,
+}
+
+impl Default for CollateralAuctionType {
+	fn default() -> Self {
+		CollateralAuctionType::English
+	}
+}
+
+/// The strategy to handle the surplus exceeding the surplus buffer
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum SurplusStrategy {
+	/// Keep the surplus in the surplus pool, which is sold by surplus auctions
+	Auction,
+	/// Buy back native currency with the surplus on DEX and burn it
+	BuybackAndBurn,
+	/// Buy back native currency with the surplus on DEX and send it to the
+	/// staking reward pool
+	BuybackToStakingRewardPool,
+}
+
+impl Default for SurplusStrategy {
+	fn default() -> Self {
+		SurplusStrategy::Auction
+	}
 }
 
 pub trait Trait: system::Trait {
@@ -49,6 +94,9 @@ pub trait Trait: system::Trait {
 	/// Stablecoin currency id
 	type GetStableCurrencyId: Get<CurrencyId>;
 
+	/// Native currency id, which is bought back with the surplus
+	type GetNativeCurrencyId: Get<CurrencyId>;
+
 	/// Auction manager creates different types of auction to handle system
 	/// surplus and debit, and confiscated collateral assets
 	type AuctionManagerHandler: AuctionManager<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
@@ -57,6 +105,9 @@ pub trait Trait: system::Trait {
 	/// currency
 	type DEX: DEXManager<Self::AccountId, CurrencyId, Balance>;
 
+	/// The registry of valid collateral currency types
+	type CollateralRegistry: CollateralRegistry<CurrencyId>;
+
 	/// The cap of lots number when create collateral auction on a liquidation
 	/// or to create debit/surplus auction on block end.
 	/// If set to 0, does not work.
@@ -66,6 +117,21 @@ pub trait Trait: system::Trait {
 	/// liquidation.
 	type ModuleId: Get<ModuleId>;
 
+	/// The interval of blocks to buy back native currency with the surplus.
+	/// If set to 0, does not work.
+	type SurplusBuybackInterval: Get<Self::BlockNumber>;
+
+	/// The max slippage of buying back native currency against the price from
+	/// `PriceSource`, also used as the price impact limit of the swap on DEX
+	type MaxSurplusBuybackSlippage: Get<Ratio>;
+
+	/// The price source to derive the minimum native currency amount of the
+	/// buyback
+	type PriceSource: PriceProvider<CurrencyId>;
+
+	/// The staking reward pool to receive the native currency bought back
+	type StakingRewardPool: Get<Self::AccountId>;
+
 	/// Weight information for the extrinsics in this module.
 	type WeightInfo: WeightInfo;
 }
@@ -75,6 +141,16 @@ decl_event!(
 		/// The fixed size for collateral auction under specific collateral type
 		/// updated. \[collateral_type, new_size\]
 		CollateralAuctionMaximumSizeUpdated(CurrencyId, Balance),
+		/// The type of collateral auction under specific collateral type
+		/// updated. \[collateral_type, new_auction_type\]
+		CollateralAuctionTypeUpdated(CurrencyId, CollateralAuctionType),
+		/// The surplus handling strategy updated. \[new_strategy\]
+		SurplusHandlingStrategyUpdated(SurplusStrategy),
+		/// The surplus buffer size updated. \[new_size\]
+		SurplusBufferSizeUpdated(Balance),
+		/// Native currency bought back with the surplus. \[strategy,
+		/// surplus_amount, native_amount\]
+		SurplusBoughtBack(SurplusStrategy, Balance, Balance),
 	}
 );
 
@@ -89,6 +165,8 @@ decl_error! {
 		DebitPoolOverflow,
 		/// The debit pool of CDP treasury is not enough
 		DebitPoolNotEnough,
+		/// Invalid collateral type
+		InvalidCollateralType,
 	}
 }
 
@@ -97,9 +175,19 @@ decl_storage! {
 		/// The maximum amount of collateral amount for sale per collateral auction
 		pub CollateralAuctionMaximumSize get(fn collateral_auction_maximum_size): map hasher(twox_64_concat) CurrencyId => Balance;
 
+		/// The type of auction to sell the collateral under specific collateral type
+		pub CollateralAuctionTypes get(fn collateral_auction_type): map hasher(twox_64_concat) CurrencyId => CollateralAuctionType;
+
 		/// Current total debit value of system. It's not same as debit in CDP engine,
 		/// it is the bad debt of the system.
 		pub DebitPool get(fn debit_pool): Balance;
+
+		/// The strategy to handle the surplus exceeding `SurplusBufferSize`
+		pub SurplusHandlingStrategy get(fn surplus_handling_strategy): SurplusStrategy;
+
+		/// The amount of surplus kept in the surplus pool, only the surplus exceeding it is
+		/// used to buy back native currency
+		pub SurplusBufferSize get(fn surplus_buffer_size): Balance;
 	}
 
 	add_extra_genesis {
@@ -127,6 +215,12 @@ decl_module! {
 		/// The CDP treasury's module id, keep surplus and collateral assets from liquidation.
 		const ModuleId: ModuleId = T::ModuleId::get();
 
+		/// The interval of blocks to buy back native currency with the surplus.
+		const SurplusBuybackInterval: T::BlockNumber = T::SurplusBuybackInterval::get();
+
+		/// The price impact limit when swapping the surplus to native currency on DEX.
+		const MaxSurplusBuybackSlippage: Ratio = T::MaxSurplusBuybackSlippage::get();
+
 		#[weight = T::WeightInfo::auction_surplus()]
 		pub fn auction_surplus(origin, amount: Balance) {
 			with_transaction_result(|| {
@@ -174,25 +268,118 @@ decl_module! {
 		///
 		/// # <weight>
 		/// - Complexity: `O(1)`
-		/// - Db reads: 0
+		/// - Db reads: 1
 		/// - Db writes: 1
 		/// -------------------
-		/// Base Weight: 24.64 µs
+		/// Base Weight: 27.91 µs
 		/// # </weight>
 		#[weight = (T::WeightInfo::set_collateral_auction_maximum_size(), DispatchClass::Operational)]
 		pub fn set_collateral_auction_maximum_size(origin, currency_id: CurrencyId, size: Balance) {
 			with_transaction_result(|| {
 				T::UpdateOrigin::ensure_origin(origin)?;
+				ensure!(
+					T::CollateralRegistry::is_collateral(currency_id),
+					Error::<T>::InvalidCollateralType,
+				);
 				CollateralAuctionMaximumSize::insert(currency_id, size);
 				Self::deposit_event(Event::CollateralAuctionMaximumSizeUpdated(currency_id, size));
 				Ok(())
 			})?;
 		}
 
+		/// Update the type of collateral auction under specific collateral type
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type
+		/// - `auction_type`: English or Dutch auction
+		///
+		/// # <weight>
+		/// - Complexity: `O(1)`
+		/// - Db reads: 1
+		/// - Db writes: 1
+		/// -------------------
+		/// Base Weight: 28.36 µs
+		/// # </weight>
+		#[weight = (T::WeightInfo::set_collateral_auction_type(), DispatchClass::Operational)]
+		pub fn set_collateral_auction_type(origin, currency_id: CurrencyId, auction_type: CollateralAuctionType) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin)?;
+				ensure!(
+					T::CollateralRegistry::is_collateral(currency_id),
+					Error::<T>::InvalidCollateralType,
+				);
+				CollateralAuctionTypes::insert(currency_id, auction_type);
+				Self::deposit_event(Event::CollateralAuctionTypeUpdated(currency_id, auction_type));
+				Ok(())
+			})?;
+		}
+
+		/// Update the strategy to handle the surplus exceeding the surplus buffer
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `strategy`: keep the surplus for auctions, or buy back native currency
+		///   to burn or to send to the staking reward pool
+		///
+		/// # <weight>
+		/// - Complexity: `O(1)`
+		/// - Db reads: 0
+		/// - Db writes: 1
+		/// -------------------
+		/// Base Weight: 14.12 µs
+		/// # </weight>
+		#[weight = (T::WeightInfo::set_surplus_handling_strategy(), DispatchClass::Operational)]
+		pub fn set_surplus_handling_strategy(origin, strategy: SurplusStrategy) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin)?;
+				SurplusHandlingStrategy::put(strategy);
+				Self::deposit_event(Event::SurplusHandlingStrategyUpdated(strategy));
+				Ok(())
+			})?;
+		}
+
+		/// Update the amount of surplus kept in the surplus pool
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `size`: surplus buffer size
+		///
+		/// # <weight>
+		/// - Complexity: `O(1)`
+		/// - Db reads: 0
+		/// - Db writes: 1
+		/// -------------------
+		/// Base Weight: 13.87 µs
+		/// # </weight>
+		#[weight = (T::WeightInfo::set_surplus_buffer_size(), DispatchClass::Operational)]
+		pub fn set_surplus_buffer_size(origin, #[compact] size: Balance) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin)?;
+				SurplusBufferSize::put(size);
+				Self::deposit_event(Event::SurplusBufferSizeUpdated(size));
+				Ok(())
+			})?;
+		}
+
+		/// `on_initialize` to return the weight of the buyback in `on_finalize`.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			if Self::is_buyback_block(now) && Self::surplus_handling_strategy() != SurplusStrategy::Auction {
+				T::WeightInfo::buyback_with_surplus().saturating_add(T::DEX::get_best_path_weight())
+			} else {
+				0
+			}
+		}
+
 		/// Handle excessive surplus or debits of system when block end
-		fn on_finalize(_now: T::BlockNumber) {
+		fn on_finalize(now: T::BlockNumber) {
 			// offset the same amount between debit pool and surplus pool
 			Self::offset_surplus_and_debit();
+
+			// buy back native currency with the surplus exceeding the buffer periodically
+			if Self::is_buyback_block(now) {
+				Self::buyback_with_surplus();
+			}
 		}
 	}
 }
@@ -219,6 +406,76 @@ impl<T: Trait> Module<T> {
 			.saturating_sub(T::AuctionManagerHandler::get_total_collateral_in_auction(currency_id))
 	}
 
+	fn is_buyback_block(now: T::BlockNumber) -> bool {
+		let interval = T::SurplusBuybackInterval::get();
+		!interval.is_zero() && (now % interval).is_zero()
+	}
+
+	/// Swap the surplus exceeding the buffer to native currency on DEX, and
+	/// then burn it or send it to the staking reward pool according to the
+	/// surplus handling strategy. The native currency got must not be less
+	/// than the amount at the price from `PriceSource` minus the max slippage.
+	/// Nothing happens if the price is unavailable or the swap fails.
+	fn buyback_with_surplus() {
+		let strategy = Self::surplus_handling_strategy();
+		if strategy == SurplusStrategy::Auction {
+			return;
+		}
+
+		// the surplus in auction and the buffer are kept in the surplus pool
+		let supply_amount = Self::surplus_pool()
+			.saturating_sub(T::AuctionManagerHandler::get_total_surplus_in_auction())
+			.saturating_sub(Self::surplus_buffer_size());
+		if supply_amount.is_zero() {
+			return;
+		}
+
+		let stable_currency_id = T::GetStableCurrencyId::get();
+		let native_currency_id = T::GetNativeCurrencyId::get();
+		let min_target_amount = match T::PriceSource::get_relative_price(stable_currency_id, native_currency_id) {
+			Some(price) => price
+				.saturating_mul(Ratio::one().saturating_sub(T::MaxSurplusBuybackSlippage::get()))
+				.saturating_mul_int(supply_amount),
+			None => return,
+		};
+		if min_target_amount.is_zero() {
+			return;
+		}
+
+		let path = T::DEX::get_best_path_with_exact_supply(
+			stable_currency_id,
+			native_currency_id,
+			supply_amount,
+			Some(T::MaxSurplusBuybackSlippage::get()),
+		)
+		.unwrap_or_else(|| vec![stable_currency_id, native_currency_id]);
+		let _ = with_transaction_result(|| -> DispatchResult {
+			let native_amount = T::DEX::swap_with_exact_supply(
+				&Self::account_id(),
+				&path,
+				supply_amount,
+				min_target_amount,
+				Some(T::MaxSurplusBuybackSlippage::get()),
+			)?;
+
+			match strategy {
+				SurplusStrategy::BuybackAndBurn => {
+					T::Currency::withdraw(native_currency_id, &Self::account_id(), native_amount)?
+				}
+				SurplusStrategy::BuybackToStakingRewardPool => T::Currency::transfer(
+					native_currency_id,
+					&Self::account_id(),
+					&T::StakingRewardPool::get(),
+					native_amount,
+				)?,
+				SurplusStrategy::Auction => {}
+			}
+
+			Self::deposit_event(Event::SurplusBoughtBack(strategy, supply_amount, native_amount));
+			Ok(())
+		});
+	}
+
 	fn offset_surplus_and_debit() {
 		let offset_amount = sp_std::cmp::min(Self::debit_pool(), Self::surplus_pool());
 
@@ -285,6 +542,16 @@ impl<T: Trait> CDPTreasury<T::AccountId> for Module<T> {
 		T::Currency::transfer(T::GetStableCurrencyId::get(), from, &Self::account_id(), surplus)
 	}
 
+	fn withdraw_surplus(to: &T::AccountId, surplus: Self::Balance) -> DispatchResult {
+		// the surplus in auction is kept for the winners, and the surplus to
+		// offset the debit pool is not free either
+		let free_surplus = Self::surplus_pool()
+			.saturating_sub(T::AuctionManagerHandler::get_total_surplus_in_auction())
+			.saturating_sub(Self::debit_pool());
+		ensure!(surplus <= free_surplus, Error::<T>::SurplusPoolNotEnough);
+		T::Currency::transfer(T::GetStableCurrencyId::get(), &Self::account_id(), to, surplus)
+	}
+
 	fn deposit_collateral(from: &T::AccountId, currency_id: Self::CurrencyId, amount: Self::Balance) -> DispatchResult {
 		T::Currency::transfer(currency_id, from, &Self::account_id(), amount)
 	}
@@ -309,9 +576,14 @@ impl<T: Trait> CDPTreasuryExtended<T::AccountId> for Module<T> {
 			Error::<T>::CollateralNotEnough,
 		);
 
+		let stable_currency_id = T::GetStableCurrencyId::get();
+		<system::Module<T>>::register_extra_weight_unchecked(T::DEX::get_best_path_weight(), DispatchClass::Mandatory);
+		let path =
+			T::DEX::get_best_path_with_exact_supply(currency_id, stable_currency_id, supply_amount, price_impact_limit)
+				.unwrap_or_else(|| vec![currency_id, stable_currency_id]);
 		T::DEX::swap_with_exact_supply(
 			&Self::account_id(),
-			&[currency_id, T::GetStableCurrencyId::get()],
+			&path,
 			supply_amount,
 			min_target_amount,
 			price_impact_limit,
@@ -331,9 +603,14 @@ impl<T: Trait> CDPTreasuryExtended<T::AccountId> for Module<T> {
 			Error::<T>::CollateralNotEnough,
 		);
 
+		let stable_currency_id = T::GetStableCurrencyId::get();
+		<system::Module<T>>::register_extra_weight_unchecked(T::DEX::get_best_path_weight(), DispatchClass::Mandatory);
+		let path =
+			T::DEX::get_best_path_with_exact_target(currency_id, stable_currency_id, target_amount, price_impact_limit)
+				.unwrap_or_else(|| vec![currency_id, stable_currency_id]);
 		T::DEX::swap_with_exact_target(
 			&Self::account_id(),
-			&[currency_id, T::GetStableCurrencyId::get()],
+			&path,
 			target_amount,
 			max_supply_amount,
 			price_impact_limit,
@@ -352,6 +629,16 @@ impl<T: Trait> CDPTreasuryExtended<T::AccountId> for Module<T> {
 			Error::<T>::CollateralNotEnough,
 		);
 
+		// dutch auction can be taken partially, so it does not need to be split
+		if Self::collateral_auction_type(currency_id) == CollateralAuctionType::Dutch {
+			return T::AuctionManagerHandler::new_dutch_collateral_auction(
+				&refund_receiver,
+				currency_id,
+				amount,
+				target,
+			);
+		}
+
 		let mut unhandled_collateral_amount = amount;
 		let mut unhandled_target = target;
 		let collateral_auction_maximum_size = Self::collateral_auction_maximum_size(currency_id);
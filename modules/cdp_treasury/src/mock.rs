@@ -3,12 +3,17 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{impl_outer_event, impl_outer_origin, ord_parameter_types, parameter_types};
-use frame_system::EnsureSignedBy;
+use frame_support::{impl_outer_dispatch, impl_outer_event, impl_outer_origin, ord_parameter_types, parameter_types};
+use frame_system::{offchain::SendTransactionTypes, EnsureSignedBy};
 use primitives::{TokenSymbol, TradingPair};
 use sp_core::H256;
-use sp_runtime::{testing::Header, traits::IdentityLookup, Perbill};
+use sp_runtime::{
+	testing::{Header, TestXt},
+	traits::IdentityLookup,
+	Perbill,
+};
 use sp_std::cell::RefCell;
+use support::Price;
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
@@ -20,6 +25,7 @@ pub const BOB: AccountId = 1;
 pub const ACA: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
 pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
 pub const BTC: CurrencyId = CurrencyId::Token(TokenSymbol::XBTC);
+pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Runtime;
@@ -32,6 +38,12 @@ impl_outer_origin! {
 	pub enum Origin for Runtime {}
 }
 
+impl_outer_dispatch! {
+	pub enum Call for Runtime where origin: Origin {
+		dex::DEXModule,
+	}
+}
+
 impl_outer_event! {
 	pub enum TestEvent for Runtime {
 		frame_system<T>,
@@ -54,7 +66,7 @@ impl frame_system::Trait for Runtime {
 	type Origin = Origin;
 	type Index = u64;
 	type BlockNumber = BlockNumber;
-	type Call = ();
+	type Call = Call;
 	type Hash = H256;
 	type Hashing = ::sp_runtime::traits::BlakeTwo256;
 	type AccountId = AccountId;
@@ -122,24 +134,53 @@ parameter_types! {
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
 	pub const GetExchangeFee: (u32, u32) = (0, 100);
 	pub const TradingPathLimit: usize = 3;
-	pub EnabledTradingPairs : Vec<TradingPair> = vec![TradingPair::new(AUSD, BTC)];
+	pub const TradingPathCandidatesLimit: usize = 3;
+	pub const TWAPWindow: BlockNumber = 10;
+	pub const LimitOrderDeposit: Balance = 1;
+	pub const MaxLimitOrdersPerAccount: u32 = 10;
+	pub const UnsignedPriority: u64 = 1 << 20;
+	pub const ProtocolFeeReceiver: AccountId = 10;
+	pub EnabledTradingPairs : Vec<TradingPair> = vec![TradingPair::new(AUSD, BTC), TradingPair::new(AUSD, ACA)];
 	pub const DEXModuleId: ModuleId = ModuleId(*b"aca/dexm");
 }
 
 impl dex::Trait for Runtime {
 	type Event = TestEvent;
 	type Currency = Currencies;
-	type EnabledTradingPairs = EnabledTradingPairs;
 	type GetExchangeFee = GetExchangeFee;
 	type TradingPathLimit = TradingPathLimit;
+	type TradingPathCandidatesLimit = TradingPathCandidatesLimit;
 	type ModuleId = DEXModuleId;
 	type DEXIncentives = ();
 	type WeightInfo = ();
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type ProtocolFeeReceiver = ProtocolFeeReceiver;
+	type TWAPWindow = TWAPWindow;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type LimitOrderDeposit = LimitOrderDeposit;
+	type MaxLimitOrdersPerAccount = MaxLimitOrdersPerAccount;
+	type UnsignedPriority = UnsignedPriority;
+	type Call = Call;
+	type LegacyEnabledTradingPairs = EnabledTradingPairs;
+	type StableSwapRateProvider = ();
+	type FlashSwapCallFilter = ();
 }
 pub type DEXModule = dex::Module<Runtime>;
 
+/// An extrinsic type used for tests.
+pub type Extrinsic = TestXt<Call, ()>;
+
+impl<LocalCall> SendTransactionTypes<LocalCall> for Runtime
+where
+	Call: From<LocalCall>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = Extrinsic;
+}
+
 thread_local! {
 	pub static TOTAL_COLLATERAL_AUCTION: RefCell<u32> = RefCell::new(0);
+	pub static TOTAL_DUTCH_COLLATERAL_AUCTION: RefCell<u32> = RefCell::new(0);
 	pub static TOTAL_COLLATERAL_IN_AUCTION: RefCell<Balance> = RefCell::new(0);
 	pub static TOTAL_DEBIT_AUCTION: RefCell<u32> = RefCell::new(0);
 	pub static TOTAL_SURPLUS_AUCTION: RefCell<u32> = RefCell::new(0);
@@ -162,6 +203,17 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		Ok(())
 	}
 
+	fn new_dutch_collateral_auction(
+		_refund_recipient: &AccountId,
+		_currency_id: Self::CurrencyId,
+		amount: Self::Balance,
+		_target: Self::Balance,
+	) -> DispatchResult {
+		TOTAL_DUTCH_COLLATERAL_AUCTION.with(|v| *v.borrow_mut() += 1);
+		TOTAL_COLLATERAL_IN_AUCTION.with(|v| *v.borrow_mut() += amount);
+		Ok(())
+	}
+
 	fn new_debit_auction(_amount: Self::Balance, _fix: Self::Balance) -> DispatchResult {
 		TOTAL_DEBIT_AUCTION.with(|v| *v.borrow_mut() += 1);
 		Ok(())
@@ -193,6 +245,21 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 	}
 }
 
+pub struct MockCollateralRegistry;
+impl CollateralRegistry<CurrencyId> for MockCollateralRegistry {
+	fn collateral_currency_ids() -> Vec<CurrencyId> {
+		vec![BTC, DOT]
+	}
+
+	fn is_collateral(currency_id: CurrencyId) -> bool {
+		Self::collateral_currency_ids().contains(&currency_id)
+	}
+
+	fn is_active_collateral(currency_id: CurrencyId) -> bool {
+		Self::is_collateral(currency_id)
+	}
+}
+
 ord_parameter_types! {
 	pub const One: AccountId = 1;
 	pub const MaxAuctionsCount: u32 = 5;
@@ -200,21 +267,54 @@ ord_parameter_types! {
 
 parameter_types! {
 	pub const CDPTreasuryModuleId: ModuleId = ModuleId(*b"aca/cdpt");
+	pub const SurplusBuybackInterval: BlockNumber = 10;
+	pub MaxSurplusBuybackSlippage: Ratio = Ratio::saturating_from_rational(1, 10);
+	pub const StakingRewardPool: AccountId = 20;
 }
 
 thread_local! {
 	static IS_SHUTDOWN: RefCell<bool> = RefCell::new(false);
+	static RELATIVE_PRICE: RefCell<Option<Price>> = RefCell::new(Some(Price::one()));
+}
+
+pub struct MockPriceSource;
+impl MockPriceSource {
+	pub fn set_relative_price(price: Option<Price>) {
+		RELATIVE_PRICE.with(|v| *v.borrow_mut() = price);
+	}
+}
+impl PriceProvider<CurrencyId> for MockPriceSource {
+	fn get_relative_price(base: CurrencyId, quote: CurrencyId) -> Option<Price> {
+		match (base, quote) {
+			(AUSD, ACA) => RELATIVE_PRICE.with(|v| *v.borrow_mut()),
+			_ => None,
+		}
+	}
+
+	fn get_price(_currency_id: CurrencyId) -> Option<Price> {
+		Some(Price::one())
+	}
+
+	fn lock_price(_currency_id: CurrencyId) {}
+
+	fn unlock_price(_currency_id: CurrencyId) {}
 }
 
 impl Trait for Runtime {
 	type Event = TestEvent;
 	type Currency = Currencies;
 	type GetStableCurrencyId = GetStableCurrencyId;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type AuctionManagerHandler = MockAuctionManager;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = DEXModule;
+	type CollateralRegistry = MockCollateralRegistry;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type SurplusBuybackInterval = SurplusBuybackInterval;
+	type MaxSurplusBuybackSlippage = MaxSurplusBuybackSlippage;
+	type StakingRewardPool = StakingRewardPool;
+	type PriceSource = MockPriceSource;
 	type WeightInfo = ();
 }
 pub type CDPTreasuryModule = Module<Runtime>;
@@ -248,6 +348,12 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		dex::GenesisConfig {
+			initial_enabled_trading_pairs: EnabledTradingPairs::get(),
+		}
+		.assimilate_storage::<Runtime>(&mut t)
+		.unwrap();
+
 		t.into()
 	}
 }
@@ -3,9 +3,13 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok, traits::OnFinalize};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{OnFinalize, OnInitialize},
+};
 use mock::*;
 use sp_runtime::traits::BadOrigin;
+use support::Price;
 
 #[test]
 fn surplus_pool_work() {
@@ -121,6 +125,22 @@ fn deposit_surplus_work() {
 	});
 }
 
+#[test]
+fn withdraw_surplus_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPTreasuryModule::on_system_surplus(300));
+		assert_ok!(CDPTreasuryModule::on_system_debit(100));
+		assert_eq!(Currencies::free_balance(AUSD, &BOB), 1000);
+		assert_noop!(
+			CDPTreasuryModule::withdraw_surplus(&BOB, 201),
+			Error::<Runtime>::SurplusPoolNotEnough
+		);
+		assert_ok!(CDPTreasuryModule::withdraw_surplus(&BOB, 200));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 100);
+		assert_eq!(Currencies::free_balance(AUSD, &BOB), 1200);
+	});
+}
+
 #[test]
 fn deposit_collateral_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -275,6 +295,19 @@ fn create_collateral_auctions_work() {
 		));
 		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 11);
 		assert_eq!(TOTAL_COLLATERAL_IN_AUCTION.with(|v| *v.borrow_mut()), 4200);
+
+		// dutch collateral auction will not be split
+		assert_ok!(CDPTreasuryModule::set_collateral_auction_type(
+			Origin::signed(1),
+			BTC,
+			CollateralAuctionType::Dutch
+		));
+		assert_ok!(CDPTreasuryModule::create_collateral_auctions(
+			BTC, 2000, 1000, ALICE, true
+		));
+		assert_eq!(TOTAL_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 11);
+		assert_eq!(TOTAL_DUTCH_COLLATERAL_AUCTION.with(|v| *v.borrow_mut()), 1);
+		assert_eq!(TOTAL_COLLATERAL_IN_AUCTION.with(|v| *v.borrow_mut()), 6200);
 	});
 }
 
@@ -317,6 +350,10 @@ fn set_collateral_auction_maximum_size_work() {
 			CDPTreasuryModule::set_collateral_auction_maximum_size(Origin::signed(5), BTC, 200),
 			BadOrigin
 		);
+		assert_noop!(
+			CDPTreasuryModule::set_collateral_auction_maximum_size(Origin::signed(1), ACA, 200),
+			Error::<Runtime>::InvalidCollateralType
+		);
 		assert_ok!(CDPTreasuryModule::set_collateral_auction_maximum_size(
 			Origin::signed(1),
 			BTC,
@@ -330,3 +367,202 @@ fn set_collateral_auction_maximum_size_work() {
 			.any(|record| record.event == update_collateral_auction_maximum_size_event));
 	});
 }
+
+#[test]
+fn set_collateral_auction_type_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(
+			CDPTreasuryModule::collateral_auction_type(BTC),
+			CollateralAuctionType::English
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_collateral_auction_type(Origin::signed(5), BTC, CollateralAuctionType::Dutch),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_collateral_auction_type(Origin::signed(1), ACA, CollateralAuctionType::Dutch),
+			Error::<Runtime>::InvalidCollateralType
+		);
+		assert_ok!(CDPTreasuryModule::set_collateral_auction_type(
+			Origin::signed(1),
+			BTC,
+			CollateralAuctionType::Dutch
+		));
+		assert_eq!(
+			CDPTreasuryModule::collateral_auction_type(BTC),
+			CollateralAuctionType::Dutch
+		);
+
+		let update_collateral_auction_type_event =
+			TestEvent::cdp_treasury(Event::CollateralAuctionTypeUpdated(BTC, CollateralAuctionType::Dutch));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == update_collateral_auction_type_event));
+	});
+}
+
+#[test]
+fn set_surplus_handling_strategy_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(CDPTreasuryModule::surplus_handling_strategy(), SurplusStrategy::Auction);
+		assert_noop!(
+			CDPTreasuryModule::set_surplus_handling_strategy(Origin::signed(5), SurplusStrategy::BuybackAndBurn),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_surplus_handling_strategy(
+			Origin::signed(1),
+			SurplusStrategy::BuybackAndBurn
+		));
+		assert_eq!(
+			CDPTreasuryModule::surplus_handling_strategy(),
+			SurplusStrategy::BuybackAndBurn
+		);
+
+		let update_surplus_handling_strategy_event =
+			TestEvent::cdp_treasury(Event::SurplusHandlingStrategyUpdated(SurplusStrategy::BuybackAndBurn));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == update_surplus_handling_strategy_event));
+	});
+}
+
+#[test]
+fn set_surplus_buffer_size_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(CDPTreasuryModule::surplus_buffer_size(), 0);
+		assert_noop!(
+			CDPTreasuryModule::set_surplus_buffer_size(Origin::signed(5), 200),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_surplus_buffer_size(Origin::signed(1), 200));
+		assert_eq!(CDPTreasuryModule::surplus_buffer_size(), 200);
+
+		let update_surplus_buffer_size_event = TestEvent::cdp_treasury(Event::SurplusBufferSizeUpdated(200));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == update_surplus_buffer_size_event));
+	});
+}
+
+#[test]
+fn buyback_with_surplus_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Currencies::deposit(ACA, &ALICE, 1000));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			ACA,
+			AUSD,
+			1000,
+			1000,
+			false
+		));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(300));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 300);
+		assert_eq!(Currencies::total_issuance(ACA), 1000);
+
+		// the surplus is kept for auctions by default
+		assert_eq!(CDPTreasuryModule::on_initialize(20), 0);
+		CDPTreasuryModule::on_finalize(20);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 300);
+		assert_eq!(DEXModule::get_liquidity_pool(ACA, AUSD), (1000, 1000));
+
+		assert_ok!(CDPTreasuryModule::set_surplus_buffer_size(Origin::signed(1), 200));
+		assert_ok!(CDPTreasuryModule::set_surplus_handling_strategy(
+			Origin::signed(1),
+			SurplusStrategy::BuybackAndBurn
+		));
+
+		// only buy back at the interval, and the weight is reserved in advance
+		assert_eq!(CDPTreasuryModule::on_initialize(15), 0);
+		assert_eq!(
+			CDPTreasuryModule::on_initialize(20),
+			<() as WeightInfo>::buyback_with_surplus() + DEXModule::get_best_path_weight()
+		);
+		CDPTreasuryModule::on_finalize(15);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 300);
+
+		CDPTreasuryModule::on_finalize(20);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 200);
+		assert_eq!(DEXModule::get_liquidity_pool(ACA, AUSD), (910, 1100));
+		assert_eq!(Currencies::free_balance(ACA, &CDPTreasuryModule::account_id()), 0);
+		assert_eq!(Currencies::total_issuance(ACA), 910);
+
+		let buyback_event = TestEvent::cdp_treasury(Event::SurplusBoughtBack(SurplusStrategy::BuybackAndBurn, 100, 90));
+		assert!(System::events().iter().any(|record| record.event == buyback_event));
+
+		// exceed the price impact limit
+		assert_ok!(CDPTreasuryModule::on_system_surplus(300));
+		CDPTreasuryModule::on_finalize(30);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 500);
+		assert_eq!(DEXModule::get_liquidity_pool(ACA, AUSD), (910, 1100));
+
+		assert_ok!(CDPTreasuryModule::set_surplus_buffer_size(Origin::signed(1), 450));
+		assert_ok!(CDPTreasuryModule::set_surplus_handling_strategy(
+			Origin::signed(1),
+			SurplusStrategy::BuybackToStakingRewardPool
+		));
+
+		// the swap gives less than the price from the price source allows
+		CDPTreasuryModule::on_finalize(40);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 500);
+		assert_eq!(DEXModule::get_liquidity_pool(ACA, AUSD), (910, 1100));
+
+		// no buyback without price
+		MockPriceSource::set_relative_price(None);
+		CDPTreasuryModule::on_finalize(40);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 500);
+
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_rational(910, 1100)));
+		CDPTreasuryModule::on_finalize(40);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 450);
+		assert_eq!(DEXModule::get_liquidity_pool(ACA, AUSD), (871, 1150));
+		assert_eq!(Currencies::free_balance(ACA, &StakingRewardPool::get()), 39);
+		assert_eq!(Currencies::total_issuance(ACA), 910);
+	});
+}
+
+#[test]
+fn buyback_with_surplus_by_best_path() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Currencies::deposit(AUSD, &ALICE, 10000));
+		assert_ok!(Currencies::deposit(BTC, &ALICE, 20000));
+		assert_ok!(Currencies::deposit(ACA, &ALICE, 10000));
+		assert_ok!(DEXModule::enable_trading_pair(Origin::signed(1), BTC, ACA));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			BTC,
+			10000,
+			10000,
+			false
+		));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			BTC,
+			ACA,
+			10000,
+			10000,
+			false
+		));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(100));
+		assert_ok!(CDPTreasuryModule::set_surplus_handling_strategy(
+			Origin::signed(1),
+			SurplusStrategy::BuybackAndBurn
+		));
+
+		// there's no liquidity for AUSD-ACA, buy back through BTC
+		CDPTreasuryModule::on_finalize(20);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(DEXModule::get_liquidity_pool(AUSD, BTC), (10100, 9901));
+		assert_eq!(DEXModule::get_liquidity_pool(BTC, ACA), (10099, 9902));
+		assert_eq!(Currencies::total_issuance(ACA), 9902);
+
+		let buyback_event = TestEvent::cdp_treasury(Event::SurplusBoughtBack(SurplusStrategy::BuybackAndBurn, 100, 98));
+		assert!(System::events().iter().any(|record| record.event == buyback_event));
+	});
+}
@@ -0,0 +1,188 @@
+//! RPC interface for the dex module.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use module_dex_rpc_runtime_api::{LiquidityPoolInfo, LiquidityPosition, SwapQuote};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
+};
+use std::{
+	convert::{TryFrom, TryInto},
+	sync::Arc,
+};
+
+pub use self::gen_client::Client as DexClient;
+pub use module_dex_rpc_runtime_api::DexApi as DexRuntimeApi;
+
+#[rpc]
+pub trait DexApi<BlockHash, AccountId, CurrencyId, Balance> {
+	#[rpc(name = "dex_getSwapTargetAmounts")]
+	fn get_swap_target_amounts(
+		&self,
+		path: Vec<CurrencyId>,
+		supply_amount: NumberOrHex,
+		at: Option<BlockHash>,
+	) -> Result<Option<SwapQuote<Balance>>>;
+
+	#[rpc(name = "dex_getSwapSupplyAmounts")]
+	fn get_swap_supply_amounts(
+		&self,
+		path: Vec<CurrencyId>,
+		target_amount: NumberOrHex,
+		at: Option<BlockHash>,
+	) -> Result<Option<SwapQuote<Balance>>>;
+
+	#[rpc(name = "dex_getLiquidityPool")]
+	fn get_liquidity_pool(
+		&self,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		at: Option<BlockHash>,
+	) -> Result<LiquidityPoolInfo<Balance>>;
+
+	#[rpc(name = "dex_getLiquidityPosition")]
+	fn get_liquidity_position(
+		&self,
+		who: AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		at: Option<BlockHash>,
+	) -> Result<Option<LiquidityPosition<Balance>>>;
+}
+
+/// A struct that implements the [`DexApi`].
+pub struct Dex<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Dex<C, B> {
+	/// Create new `Dex` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Dex {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+pub enum Error {
+	RuntimeError,
+	InvalidParams,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::InvalidParams => 2,
+		}
+	}
+}
+
+fn try_into_balance<Balance: TryFrom<NumberOrHex>>(amount: NumberOrHex) -> Result<Balance> {
+	amount.try_into().map_err(|_| RpcError {
+		code: ErrorCode::ServerError(Error::InvalidParams.into()),
+		message: "Amount doesn't fit in Balance type.".into(),
+		data: None,
+	})
+}
+
+impl<C, Block, AccountId, CurrencyId, Balance> DexApi<<Block as BlockT>::Hash, AccountId, CurrencyId, Balance>
+	for Dex<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: DexRuntimeApi<Block, AccountId, CurrencyId, Balance>,
+	AccountId: Codec,
+	CurrencyId: Codec,
+	Balance: Codec + MaybeDisplay + MaybeFromStr + TryFrom<NumberOrHex>,
+{
+	fn get_swap_target_amounts(
+		&self,
+		path: Vec<CurrencyId>,
+		supply_amount: NumberOrHex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<SwapQuote<Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+		let supply_amount = try_into_balance(supply_amount)?;
+
+		api.get_swap_target_amounts(&at, path, supply_amount)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get swap target amounts.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn get_swap_supply_amounts(
+		&self,
+		path: Vec<CurrencyId>,
+		target_amount: NumberOrHex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<SwapQuote<Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+		let target_amount = try_into_balance(target_amount)?;
+
+		api.get_swap_supply_amounts(&at, path, target_amount)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get swap supply amounts.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn get_liquidity_pool(
+		&self,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<LiquidityPoolInfo<Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.get_liquidity_pool(&at, currency_id_a, currency_id_b)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get liquidity pool.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+
+	fn get_liquidity_position(
+		&self,
+		who: AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<LiquidityPosition<Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		));
+
+		api.get_liquidity_position(&at, who, currency_id_a, currency_id_b)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get liquidity position.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
+}
@@ -8,21 +8,43 @@
 //! liquidation by auction when the liquidity is sufficient. And providing
 //! market making liquidity for DEX will also receive stable currency as
 //! additional reward for its participation in the CDP liquidation.
+//!
+//! Traders can also place limit orders which are executed by the offchain
+//! worker once the swap along the trading path meets the limit.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{decl_error, decl_event, decl_module, decl_storage, ensure, traits::Get, weights::Weight};
-use frame_system::{self as system, ensure_signed};
-use orml_traits::{MultiCurrency, MultiCurrencyExtended};
-use orml_utilities::with_transaction_result;
+use codec::{Decode, Encode};
+use frame_support::{
+	debug, decl_error, decl_event, decl_module, decl_storage,
+	dispatch::PostDispatchInfo,
+	ensure,
+	traits::{EnsureOrigin, Filter, Get},
+	weights::{DispatchClass, GetDispatchInfo, Weight},
+	IterableStorageDoubleMap, IterableStorageMap, Parameter,
+};
+use frame_system::{
+	self as system, ensure_none, ensure_signed,
+	offchain::{SendTransactionTypes, SubmitTransaction},
+};
+use orml_traits::{MultiCurrency, MultiCurrencyExtended, MultiReservableCurrency};
+use orml_utilities::{with_transaction_result, IterableStorageMapExtended, OffchainErr};
 use primitives::{Balance, CurrencyId, TradingPair};
 use sp_core::U256;
 use sp_runtime::{
-	traits::{AccountIdConversion, UniqueSaturatedInto, Zero},
-	DispatchError, DispatchResult, FixedPointNumber, ModuleId,
+	offchain::{
+		storage::StorageValueRef,
+		storage_lock::{StorageLock, Time},
+		Duration,
+	},
+	traits::{AccountIdConversion, CheckedDiv, CheckedMul, Dispatchable, One, Saturating, UniqueSaturatedInto, Zero},
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
+	},
+	DispatchError, DispatchResult, FixedPointNumber, ModuleId, RuntimeDebug,
 };
-use sp_std::{convert::TryInto, prelude::*, vec};
-use support::{DEXIncentives, DEXManager, Price, Ratio};
+use sp_std::{convert::TryInto, marker::PhantomData, prelude::*, vec};
+use support::{DEXIncentives, DEXManager, ExchangeRate, Price, PriceProvider, Ratio};
 
 mod default_weight;
 mod mock;
@@ -31,28 +53,160 @@ mod tests;
 pub trait WeightInfo {
 	fn add_liquidity(deposit: bool) -> Weight;
 	fn remove_liquidity(by_withdraw: bool) -> Weight;
+	fn add_liquidity_single_token(deposit: bool) -> Weight;
 	fn swap_with_exact_supply() -> Weight;
 	fn swap_with_exact_target() -> Weight;
+	fn swap_with_exact_supply_by_best_path() -> Weight;
+	fn swap_with_exact_target_by_best_path() -> Weight;
+	fn enable_trading_pair() -> Weight;
+	fn disable_trading_pair() -> Weight;
+	fn list_trading_pair() -> Weight;
+	fn add_provision() -> Weight;
+	fn end_provisioning() -> Weight;
+	fn claim_dex_share() -> Weight;
+	fn cancel_provisioning() -> Weight;
+	fn refund_provision() -> Weight;
+	fn set_exchange_fee() -> Weight;
+	fn set_protocol_fee_share() -> Weight;
+	fn place_limit_order() -> Weight;
+	fn cancel_limit_order() -> Weight;
+	fn execute_limit_order() -> Weight;
+	fn expire_limit_order() -> Weight;
+	fn flash_swap() -> Weight;
+	fn set_pool_type() -> Weight;
+}
+
+const OFFCHAIN_WORKER_DATA: &[u8] = b"acala/dex/data/";
+const OFFCHAIN_WORKER_LOCK: &[u8] = b"acala/dex/lock/";
+const OFFCHAIN_WORKER_MAX_ITERATIONS: &[u8] = b"acala/dex/max-iterations/";
+const LOCK_DURATION: u64 = 100;
+const DEFAULT_MAX_ITERATIONS: u32 = 1000;
+
+/// The maximum amplification coefficient of the StableSwap pool.
+pub const MAX_AMPLIFICATION: u128 = 1_000_000;
+/// The maximum rounds of Newton's method in StableSwap calculation.
+const STABLE_SWAP_MAX_ITERATIONS: u32 = 255;
+/// The maximum difference between the normalized pools, in per ten thousand of
+/// the larger one, to switch the pool type without moving the price.
+const POOL_TYPE_SWITCH_IMBALANCE_LIMIT: u128 = 10;
+
+/// Id of the limit order
+pub type OrderId = u64;
+
+/// The limit order to swap exact supply amount along the trading path, once
+/// the target amount is not less than `min_target_amount`.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct LimitOrder<AccountId, BlockNumber> {
+	/// The owner of the order, the supply amount is reserved from it.
+	pub owner: AccountId,
+	/// The trading path of the swap.
+	pub path: Vec<CurrencyId>,
+	/// The exact supply amount.
+	pub supply_amount: Balance,
+	/// The minimum target amount to execute the order.
+	pub min_target_amount: Balance,
+	/// The order can not be executed after this block number.
+	pub expiry: BlockNumber,
+	/// The deposit of native currency reserved from the owner.
+	pub deposit: Balance,
+}
+
+/// Parameters of the trading pair in provisioning status
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub struct ProvisioningParameters<Balance, BlockNumber> {
+	/// The minimum contribution of (currency_0, currency_1) for every
+	/// provision.
+	pub min_contribution: (Balance, Balance),
+	/// The target provision of (currency_0, currency_1) to enable the trading
+	/// pair.
+	pub target_provision: (Balance, Balance),
+	/// The accumulated provision of (currency_0, currency_1).
+	pub accumulated_provision: (Balance, Balance),
+	/// The provisioning can not end before this block number.
+	pub not_before: BlockNumber,
+}
+
+/// Status of the trading pair
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum TradingPairStatus<Balance, BlockNumber> {
+	/// The trading pair is not enabled, liquidity can only be removed.
+	NotEnabled,
+	/// The trading pair is collecting provision, it will be enabled once the
+	/// target provision is reached.
+	Provisioning(ProvisioningParameters<Balance, BlockNumber>),
+	/// The trading pair is enabled for adding liquidity and trading.
+	Enabled,
+}
+
+impl<Balance, BlockNumber> Default for TradingPairStatus<Balance, BlockNumber> {
+	fn default() -> Self {
+		Self::NotEnabled
+	}
+}
+
+/// The invariant of the liquidity pool.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum PoolType {
+	/// Constant product invariant: x * y = k.
+	ConstantProduct,
+	/// StableSwap invariant with the amplification coefficient, for the
+	/// currencies which are pegged 1:1.
+	StableSwap(u128),
+}
+
+impl Default for PoolType {
+	fn default() -> Self {
+		Self::ConstantProduct
+	}
+}
+
+/// Provide the rate multipliers of the currencies in StableSwap pools, which
+/// normalize the pools to the same unit, e.g. for currencies with different
+/// decimals or with a drifting exchange rate like DOT/LDOT.
+pub trait StableSwapRateProvider {
+	/// The rate multiplier of `currency_id` in the StableSwap pool of
+	/// `trading_pair`.
+	fn get_rate_multiplier(trading_pair: TradingPair, currency_id: CurrencyId) -> ExchangeRate;
+}
+
+impl StableSwapRateProvider for () {
+	fn get_rate_multiplier(_trading_pair: TradingPair, _currency_id: CurrencyId) -> ExchangeRate {
+		ExchangeRate::one()
+	}
+}
+
+/// Cumulative prices of the trading pair, accumulated by block.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default)]
+pub struct CumulativePrices<BlockNumber> {
+	/// The cumulative price of currency_0 in currency_1.
+	pub price_0: Price,
+	/// The cumulative price of currency_1 in currency_0.
+	pub price_1: Price,
+	/// The block number when the cumulative prices were last updated.
+	pub block_number: BlockNumber,
 }
 
-pub trait Trait: system::Trait {
+pub trait Trait: SendTransactionTypes<Call<Self>> + system::Trait {
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
 	/// Currency for transfer currencies
-	type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+	type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>
+		+ MultiReservableCurrency<Self::AccountId>;
 
-	/// Allowed trading pair list.
-	type EnabledTradingPairs: Get<Vec<TradingPair>>;
-
-	/// Trading fee rate
-	/// The first item of the tuple is the numerator of the fee rate, second
-	/// item is the denominator, fee_rate = numerator / denominator,
+	/// Default trading fee rate, used by trading pairs without their own fee
+	/// rate. The first item of the tuple is the numerator of the fee rate,
+	/// second item is the denominator, fee_rate = numerator / denominator,
 	/// use (u32, u32) over `Rate` type to minimize internal division operation.
 	type GetExchangeFee: Get<(u32, u32)>;
 
 	/// The limit for length of trading path
 	type TradingPathLimit: Get<usize>;
 
+	/// The limit for the number of trading paths searched and compared when
+	/// finding the best trading path, which bounds the cost of the swaps
+	/// without a specified path
+	type TradingPathCandidatesLimit: Get<usize>;
+
 	/// The DEX's module id, keep all assets in DEX.
 	type ModuleId: Get<ModuleId>;
 
@@ -61,13 +215,57 @@ pub trait Trait: system::Trait {
 
 	/// DEX incentives
 	type DEXIncentives: DEXIncentives<Self::AccountId, CurrencyId, Balance>;
+
+	/// The origin which may enable, disable and list trading pairs, and
+	/// update fee rates.
+	type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The account which receives the protocol fee share of swap fees.
+	type ProtocolFeeReceiver: Get<Self::AccountId>;
+
+	/// The minimum number of blocks the TWAP is averaged over.
+	type TWAPWindow: Get<Self::BlockNumber>;
+
+	/// The native currency id
+	type GetNativeCurrencyId: Get<CurrencyId>;
+
+	/// The deposit of native currency reserved for each limit order until it
+	/// is executed, cancelled or expired
+	type LimitOrderDeposit: Get<Balance>;
+
+	/// The maximum number of pending limit orders of an account
+	type MaxLimitOrdersPerAccount: Get<u32>;
+
+	/// A configuration for base priority of unsigned transactions.
+	///
+	/// This is exposed so that it can be tuned for particular runtime, when
+	/// multiple modules send unsigned transactions.
+	type UnsignedPriority: Get<TransactionPriority>;
+
+	/// The call which is dispatched by the borrower of a flash swap.
+	type Call: Parameter
+		+ Dispatchable<Origin = <Self as frame_system::Trait>::Origin, PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo;
+
+	/// The filter of the calls which can be dispatched by the borrower of a
+	/// flash swap.
+	type FlashSwapCallFilter: Filter<<Self as Trait>::Call>;
+
+	/// The trading pairs of the runtime constant before the trading pair
+	/// registry, which are enabled once by the runtime upgrade
+	type LegacyEnabledTradingPairs: Get<Vec<TradingPair>>;
+
+	/// The rate multipliers of the currencies in StableSwap pools
+	type StableSwapRateProvider: StableSwapRateProvider;
 }
 
 decl_event!(
 	pub enum Event<T> where
 		<T as frame_system::Trait>::AccountId,
+		<T as frame_system::Trait>::BlockNumber,
 		Balance = Balance,
 		CurrencyId = CurrencyId,
+		TradingPair = TradingPair,
 	{
 		/// Add liquidity success. \[who, currency_id_0, pool_0_increment, currency_id_1, pool_1_increment, share_increment\]
 		AddLiquidity(AccountId, CurrencyId, Balance, CurrencyId, Balance, Balance),
@@ -75,6 +273,40 @@ decl_event!(
 		RemoveLiquidity(AccountId, CurrencyId, Balance, CurrencyId, Balance, Balance),
 		/// Use supply currency to swap target currency. \[trader, trading_path, supply_currency_amount, target_currency_amount\]
 		Swap(AccountId, Vec<CurrencyId>, Balance, Balance),
+		/// Enable the trading pair. \[trading_pair\]
+		EnableTradingPair(TradingPair),
+		/// Disable the trading pair. \[trading_pair\]
+		DisableTradingPair(TradingPair),
+		/// List the trading pair for provisioning. \[trading_pair\]
+		ListTradingPair(TradingPair),
+		/// Add provision to the provisioning trading pair. \[who, currency_id_0, contribution_0, currency_id_1, contribution_1\]
+		AddProvision(AccountId, CurrencyId, Balance, CurrencyId, Balance),
+		/// The provisioning trading pair is enabled. \[trading_pair, pool_0_amount, pool_1_amount, total_share_amount\]
+		ProvisioningToEnabled(TradingPair, Balance, Balance, Balance),
+		/// Claim the shares of the provision after the trading pair is enabled. \[who, trading_pair, share_amount\]
+		ClaimDexShare(AccountId, TradingPair, Balance),
+		/// Cancel the provisioning of the trading pair. \[trading_pair\]
+		ProvisioningCancelled(TradingPair),
+		/// Refund the provision of the cancelled provisioning. \[who, currency_id_0, contribution_0, currency_id_1, contribution_1\]
+		RefundProvision(AccountId, CurrencyId, Balance, CurrencyId, Balance),
+		/// Charge the swap fee on a hop of the trading path. \[supply_currency_id, target_currency_id, fee_amount, protocol_fee_amount\]
+		SwapFee(CurrencyId, CurrencyId, Balance, Balance),
+		/// The exchange fee rate of the trading pair is updated, `None` means the default rate. \[trading_pair, fee_rate\]
+		ExchangeFeeUpdated(TradingPair, Option<(u32, u32)>),
+		/// The pool type of the trading pair is updated. \[trading_pair, pool_type\]
+		PoolTypeUpdated(TradingPair, PoolType),
+		/// The protocol fee share of swap fees is updated. \[protocol_fee_share\]
+		ProtocolFeeShareUpdated(Option<Ratio>),
+		/// Place the limit order. \[order_id, owner, trading_path, supply_amount, min_target_amount, expiry\]
+		LimitOrderPlaced(OrderId, AccountId, Vec<CurrencyId>, Balance, Balance, BlockNumber),
+		/// Cancel the limit order. \[order_id, owner\]
+		LimitOrderCancelled(OrderId, AccountId),
+		/// Execute the limit order. \[order_id, owner, target_amount\]
+		LimitOrderExecuted(OrderId, AccountId, Balance),
+		/// The limit order is expired. \[order_id, owner\]
+		LimitOrderExpired(OrderId, AccountId),
+		/// Flash swap success. \[borrower, currency_id, amount, fee_amount\]
+		FlashSwap(AccountId, CurrencyId, Balance, Balance),
 	}
 );
 
@@ -101,6 +333,56 @@ decl_error! {
 		ZeroSupplyAmount,
 		/// The target amount is zero
 		ZeroTargetAmount,
+		/// There's no available trading path between the currencies
+		NoAvailableTradingPath,
+		/// The trading pair must be enabled
+		MustBeEnabled,
+		/// The trading pair must be not enabled
+		MustBeNotEnabled,
+		/// The trading pair must be provisioning
+		MustBeProvisioning,
+		/// The trading pair can not be listed for provisioning
+		NotAllowedList,
+		/// The contribution of provision is invalid
+		InvalidContributionIncrement,
+		/// The provisioning can not end yet
+		UnqualifiedProvision,
+		/// The provisioning of the trading pair has not ended successfully
+		ProvisioningNotEnded,
+		/// The provisioning of the trading pair has not been cancelled
+		ProvisioningNotCancelled,
+		/// The account has no provision in the trading pair
+		NoProvision,
+		/// The exchange fee rate is invalid
+		InvalidExchangeFee,
+		/// The protocol fee share is invalid
+		InvalidProtocolFeeShare,
+		/// The limit order does not exist
+		LimitOrderNotFound,
+		/// The caller is not the owner of the limit order
+		NoPermission,
+		/// The expiry of the limit order is invalid
+		InvalidExpiry,
+		/// The limit order is expired
+		LimitOrderExpired,
+		/// The limit order is not expired yet
+		LimitOrderNotExpired,
+		/// The account has too many pending limit orders
+		TooManyLimitOrders,
+		/// The share increment is less than min_share_increment
+		InsufficientShareIncrement,
+		/// The flash swap amount is zero
+		ZeroFlashSwapAmount,
+		/// The amplification coefficient is invalid
+		InvalidAmplification,
+		/// The operation is not supported by the pool type
+		NotSupportedPoolType,
+		/// The pool type can only be switched when the liquidity pool is empty or balanced
+		PoolNotBalanced,
+		/// The liquidity of the trading pair is lent by a flash swap in progress
+		FlashSwapInProgress,
+		/// The call is not allowed to be dispatched by the borrower of a flash swap
+		FlashSwapCallNotAllowed,
 	}
 }
 
@@ -109,6 +391,75 @@ decl_storage! {
 		/// Liquidity pool for specific pair(a tuple consisting of two sorted CurrencyIds).
 		/// (CurrencyId_0, CurrencyId_1) -> (Amount_0, Amount_1)
 		LiquidityPool get(fn liquidity_pool): map hasher(twox_64_concat) TradingPair => (Balance, Balance);
+
+		/// Status for trading pairs.
+		/// TradingPair -> TradingPairStatus
+		TradingPairStatuses get(fn trading_pair_statuses): map hasher(twox_64_concat) TradingPair => TradingPairStatus<Balance, T::BlockNumber>;
+
+		/// The enabled trading pairs, cached from `TradingPairStatuses` so that
+		/// the trading path search reads them at once.
+		EnabledTradingPairs get(fn enabled_trading_pairs): Vec<TradingPair>;
+
+		/// Provision of the provisioning trading pair contributed by the account.
+		/// TradingPair, AccountId -> (Amount_0, Amount_1)
+		ProvisioningPool get(fn provisioning_pool): double_map hasher(twox_64_concat) TradingPair, hasher(twox_64_concat) T::AccountId => (Balance, Balance);
+
+		/// Exchange rates of (currency_0, currency_1) to the shares of the trading pair, set when
+		/// the provisioning ends and used by contributors to claim their shares.
+		/// TradingPair -> (ExchangeRate_0, ExchangeRate_1)
+		InitialShareExchangeRates get(fn initial_share_exchange_rates): map hasher(twox_64_concat) TradingPair => Option<(ExchangeRate, ExchangeRate)>;
+
+		/// Exchange fee rate of the trading pair, `None` means the default `GetExchangeFee`.
+		/// TradingPair -> (Numerator, Denominator)
+		ExchangeFees get(fn exchange_fees): map hasher(twox_64_concat) TradingPair => Option<(u32, u32)>;
+
+		/// The invariant of the liquidity pool of the trading pair.
+		/// TradingPair -> PoolType
+		PoolTypes get(fn pool_types): map hasher(twox_64_concat) TradingPair => PoolType;
+
+		/// The share of swap fees charged as protocol fee, `None` means no protocol fee.
+		ProtocolFeeShare get(fn protocol_fee_share): Option<Ratio>;
+
+		/// Cumulative prices of the trading pair, updated before every change of the liquidity pool.
+		/// TradingPair -> CumulativePrices
+		CumulativePrice get(fn cumulative_price): map hasher(twox_64_concat) TradingPair => Option<CumulativePrices<T::BlockNumber>>;
+
+		/// Checkpoints of the cumulative prices used to calculate TWAP, a new checkpoint is made
+		/// once the newer one is older than `TWAPWindow`.
+		/// TradingPair -> (Older, Newer)
+		TWAPCheckpoints get(fn twap_checkpoints): map hasher(twox_64_concat) TradingPair => (Option<CumulativePrices<T::BlockNumber>>, Option<CumulativePrices<T::BlockNumber>>);
+
+		/// Next id of the limit order.
+		NextOrderId get(fn next_order_id): OrderId;
+
+		/// Pending limit orders.
+		/// OrderId -> LimitOrder
+		LimitOrders get(fn limit_orders): map hasher(twox_64_concat) OrderId => Option<LimitOrder<T::AccountId, T::BlockNumber>>;
+
+		/// The number of pending limit orders of the account.
+		/// AccountId -> u32
+		LimitOrderCounts get(fn limit_order_counts): map hasher(twox_64_concat) T::AccountId => u32;
+
+		/// The trading pair whose liquidity is lent by the flash swap in progress.
+		FlashSwapLockedPair get(fn flash_swap_locked_pair): Option<TradingPair>;
+
+		/// Whether the legacy trading pairs have been migrated into `TradingPairStatuses`.
+		TradingPairsMigrated build(|_: &GenesisConfig| true): bool;
+	}
+
+	add_extra_genesis {
+		config(initial_enabled_trading_pairs): Vec<TradingPair>;
+
+		build(|config: &GenesisConfig| {
+			config.initial_enabled_trading_pairs.iter().for_each(|trading_pair| {
+				TradingPairStatuses::<T>::insert(trading_pair, TradingPairStatus::Enabled);
+				EnabledTradingPairs::mutate(|trading_pairs| {
+					if !trading_pairs.contains(trading_pair) {
+						trading_pairs.push(*trading_pair);
+					}
+				});
+			});
+		})
 	}
 }
 
@@ -118,18 +469,53 @@ decl_module! {
 
 		fn deposit_event() = default;
 
-		/// Allowed trading pair list
-		const EnabledTradingPairs: Vec<TradingPair> = T::EnabledTradingPairs::get();
-
 		/// Trading fee rate
 		const GetExchangeFee: (u32, u32) = T::GetExchangeFee::get();
 
 		/// The limit for length of trading path
 		const TradingPathLimit: u32 = T::TradingPathLimit::get() as u32;
 
+		/// The limit for the number of trading paths searched and compared when
+		/// finding the best trading path
+		const TradingPathCandidatesLimit: u32 = T::TradingPathCandidatesLimit::get() as u32;
+
 		/// The DEX's module id, keep all assets in DEX.
 		const ModuleId: ModuleId = T::ModuleId::get();
 
+		/// The account which receives the protocol fee
+		const ProtocolFeeReceiver: T::AccountId = T::ProtocolFeeReceiver::get();
+
+		/// The minimum number of blocks the TWAP is averaged over
+		const TWAPWindow: T::BlockNumber = T::TWAPWindow::get();
+
+		/// The native currency id
+		const GetNativeCurrencyId: CurrencyId = T::GetNativeCurrencyId::get();
+
+		/// The deposit of native currency reserved for each limit order
+		const LimitOrderDeposit: Balance = T::LimitOrderDeposit::get();
+
+		/// The maximum number of pending limit orders of an account
+		const MaxLimitOrdersPerAccount: u32 = T::MaxLimitOrdersPerAccount::get();
+
+		fn on_runtime_upgrade() -> Weight {
+			// the trading pairs used to be a runtime constant, enable them once.
+			if TradingPairsMigrated::get() {
+				return 0;
+			}
+
+			let mut migrated: Weight = 0;
+			for trading_pair in T::LegacyEnabledTradingPairs::get() {
+				if matches!(Self::trading_pair_statuses(trading_pair), TradingPairStatus::NotEnabled) {
+					Self::set_enabled(trading_pair);
+					migrated += 1;
+				}
+			}
+			TradingPairsMigrated::put(true);
+
+			let legacy_count = T::LegacyEnabledTradingPairs::get().len() as Weight;
+			T::DbWeight::get().reads_writes(legacy_count + migrated + 1, migrated * 2 + 1)
+		}
+
 		/// Trading with DEX, swap with exact supply amount
 		///
 		/// - `path`: trading path.
@@ -167,9 +553,100 @@ decl_module! {
 			})?;
 		}
 
+		/// Trading with DEX through the trading path which gives the most target amount,
+		/// swap with exact supply amount
+		///
+		/// - `supply_currency_id`: supply currency id.
+		/// - `target_currency_id`: target currency id.
+		/// - `supply_amount`: exact supply amount.
+		/// - `min_target_amount`: acceptable minimum target amount.
+		#[weight = <T as Trait>::WeightInfo::swap_with_exact_supply_by_best_path()
+			.saturating_add(<Module<T>>::get_best_path_weight())]
+		pub fn swap_with_exact_supply_by_best_path(
+			origin,
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			#[compact] supply_amount: Balance,
+			#[compact] min_target_amount: Balance,
+		) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				let path = Self::get_best_path_with_exact_supply(
+					supply_currency_id,
+					target_currency_id,
+					supply_amount,
+					None,
+				)
+				.ok_or(Error::<T>::NoAvailableTradingPath)?;
+				let _ = Self::do_swap_with_exact_supply(&who, &path, supply_amount, min_target_amount, None)?;
+				Ok(())
+			})?;
+		}
+
+		/// Trading with DEX through the trading path which costs the least supply amount,
+		/// swap with exact target amount
+		///
+		/// - `supply_currency_id`: supply currency id.
+		/// - `target_currency_id`: target currency id.
+		/// - `target_amount`: exact target amount.
+		/// - `max_supply_amount`: acceptable maxmum supply amount.
+		#[weight = <T as Trait>::WeightInfo::swap_with_exact_target_by_best_path()
+			.saturating_add(<Module<T>>::get_best_path_weight())]
+		pub fn swap_with_exact_target_by_best_path(
+			origin,
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			#[compact] target_amount: Balance,
+			#[compact] max_supply_amount: Balance,
+		) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				let path = Self::get_best_path_with_exact_target(
+					supply_currency_id,
+					target_currency_id,
+					target_amount,
+					None,
+				)
+				.ok_or(Error::<T>::NoAvailableTradingPath)?;
+				let _ = Self::do_swap_with_exact_target(&who, &path, target_amount, max_supply_amount, None)?;
+				Ok(())
+			})?;
+		}
+
+		/// Borrow currency from the liquidity pool, dispatch the call with the borrower as the signed
+		/// origin, and then repay the borrowed amount plus the exchange fee of the trading pair. The whole
+		/// call is reverted if the repayment fails. EVM contracts can be called back by dispatching
+		/// `module_evm` calls. The call must pass `FlashSwapCallFilter`, and the liquidity pool of
+		/// the trading pair can not be operated until the borrowed amount is repaid.
+		///
+		/// - `currency_id`: the currency to borrow.
+		/// - `other_currency_id`: the other currency of the trading pair.
+		/// - `amount`: the amount to borrow.
+		/// - `call`: the call to dispatch with the borrowed amount.
+		#[weight = {
+			let dispatch_info = call.get_dispatch_info();
+			(
+				<T as Trait>::WeightInfo::flash_swap().saturating_add(dispatch_info.weight),
+				dispatch_info.class,
+			)
+		}]
+		pub fn flash_swap(
+			origin,
+			currency_id: CurrencyId,
+			other_currency_id: CurrencyId,
+			#[compact] amount: Balance,
+			call: Box<<T as Trait>::Call>,
+		) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				Self::do_flash_swap(&who, currency_id, other_currency_id, amount, *call)
+			})?;
+		}
+
 		/// Injecting liquidity to specific liquidity pool in the form of depositing currencies in trading pairs
-		/// into liquidity pool, and issue shares in proportion to the caller. Shares are temporarily not
-		/// allowed to transfer and trade, it represents the proportion of assets in liquidity pool.
+		/// into liquidity pool, and issue shares in proportion to the caller. Shares are `DEXShare` currencies
+		/// which can be transferred like other currencies, it represents the proportion of assets in liquidity
+		/// pool.
 		///
 		/// - `currency_id_a`: currency id A.
 		/// - `currency_id_b`: currency id B.
@@ -187,36 +664,771 @@ decl_module! {
 		) {
 			with_transaction_result(|| {
 				let who = ensure_signed(origin)?;
-				Self::do_add_liquidity(&who, currency_id_a, currency_id_b, max_amount_a, max_amount_b, deposit_increment_share)
+				Self::do_add_liquidity(&who, currency_id_a, currency_id_b, max_amount_a, max_amount_b, deposit_increment_share)?;
+				Ok(())
+			})?;
+		}
+
+		/// Injecting liquidity to specific liquidity pool with only one currency of the trading pair, the optimal
+		/// fraction of the amount is swapped to the other currency internally, and then the both are injected
+		/// to the liquidity pool.
+		///
+		/// - `currency_id`: the currency to supply.
+		/// - `other_currency_id`: the other currency of the trading pair.
+		/// - `amount`: the amount of currency to supply.
+		/// - `min_share_increment`: the minimum acceptable share increment.
+		/// - `deposit_increment_share`: this flag indicates whether to deposit added lp shares to obtain incentives
+		#[weight = T::WeightInfo::add_liquidity_single_token(*deposit_increment_share)]
+		pub fn add_liquidity_single_token(
+			origin,
+			currency_id: CurrencyId,
+			other_currency_id: CurrencyId,
+			#[compact] amount: Balance,
+			#[compact] min_share_increment: Balance,
+			deposit_increment_share: bool,
+		) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				Self::do_add_liquidity_single_token(
+					&who,
+					currency_id,
+					other_currency_id,
+					amount,
+					min_share_increment,
+					deposit_increment_share,
+				)
+			})?;
+		}
+
+		/// Remove liquidity from specific liquidity pool in the form of burning shares, and withdrawing currencies in trading pairs
+		/// from liquidity pool in proportion, and withdraw liquidity incentive interest.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `remove_share`: liquidity amount to remove.
+		/// - `by_withdraw`: this flag indicates whether to withdraw share which is on incentives.
+		#[weight = T::WeightInfo::remove_liquidity(*by_withdraw)]
+		pub fn remove_liquidity(
+			origin,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			#[compact] remove_share: Balance,
+			by_withdraw: bool,
+		) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				Self::do_remove_liquidity(&who, currency_id_a, currency_id_b, remove_share, by_withdraw)
 			})?;
 		}
 
-		/// Remove liquidity from specific liquidity pool in the form of burning shares, and withdrawing currencies in trading pairs
-		/// from liquidity pool in proportion, and withdraw liquidity incentive interest.
-		///
-		/// - `currency_id_a`: currency id A.
-		/// - `currency_id_b`: currency id B.
-		/// - `remove_share`: liquidity amount to remove.
-		/// - `by_withdraw`: this flag indicates whether to withdraw share which is on incentives.
-		#[weight = T::WeightInfo::remove_liquidity(*by_withdraw)]
-		pub fn remove_liquidity(
-			origin,
-			currency_id_a: CurrencyId,
-			currency_id_b: CurrencyId,
-			#[compact] remove_share: Balance,
-			by_withdraw: bool,
-		) {
-			with_transaction_result(|| {
-				let who = ensure_signed(origin)?;
-				Self::do_remove_liquidity(&who, currency_id_a, currency_id_b, remove_share, by_withdraw)
-			})?;
-		}
+		/// Enable a trading pair directly, the trading pair must be not enabled.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		#[weight = T::WeightInfo::enable_trading_pair()]
+		pub fn enable_trading_pair(origin, currency_id_a: CurrencyId, currency_id_b: CurrencyId) {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let trading_pair = TradingPair::new(currency_id_a, currency_id_b);
+			ensure!(
+				trading_pair.get_dex_share_currency_id().is_some(),
+				Error::<T>::InvalidCurrencyId
+			);
+			ensure!(
+				matches!(Self::trading_pair_statuses(trading_pair), TradingPairStatus::NotEnabled),
+				Error::<T>::MustBeNotEnabled
+			);
+
+			Self::set_enabled(trading_pair);
+			Self::deposit_event(RawEvent::EnableTradingPair(trading_pair));
+		}
+
+		/// Disable an enabled trading pair, liquidity of the trading pair can still be removed.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		#[weight = T::WeightInfo::disable_trading_pair()]
+		pub fn disable_trading_pair(origin, currency_id_a: CurrencyId, currency_id_b: CurrencyId) {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let trading_pair = TradingPair::new(currency_id_a, currency_id_b);
+			ensure!(Self::is_enabled(trading_pair), Error::<T>::MustBeEnabled);
+
+			Self::set_not_enabled(trading_pair);
+			Self::deposit_event(RawEvent::DisableTradingPair(trading_pair));
+		}
+
+		/// List a trading pair for provisioning, the trading pair must be not enabled and has no liquidity,
+		/// and the provision of the former provisioning must be all claimed or refunded.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `min_contribution_a`: minimum contribution of currency A for every provision.
+		/// - `min_contribution_b`: minimum contribution of currency B for every provision.
+		/// - `target_provision_a`: target provision of currency A to enable the trading pair.
+		/// - `target_provision_b`: target provision of currency B to enable the trading pair.
+		/// - `not_before`: the provisioning can not end before this block number.
+		#[weight = T::WeightInfo::list_trading_pair()]
+		pub fn list_trading_pair(
+			origin,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			#[compact] min_contribution_a: Balance,
+			#[compact] min_contribution_b: Balance,
+			#[compact] target_provision_a: Balance,
+			#[compact] target_provision_b: Balance,
+			#[compact] not_before: T::BlockNumber,
+		) {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let trading_pair = TradingPair::new(currency_id_a, currency_id_b);
+			let lp_share_currency_id = trading_pair
+				.get_dex_share_currency_id()
+				.ok_or(Error::<T>::InvalidCurrencyId)?;
+			ensure!(
+				matches!(Self::trading_pair_statuses(trading_pair), TradingPairStatus::NotEnabled),
+				Error::<T>::MustBeNotEnabled
+			);
+			ensure!(
+				T::Currency::total_issuance(lp_share_currency_id).is_zero()
+					&& ProvisioningPool::<T>::iter_prefix(trading_pair).next().is_none(),
+				Error::<T>::NotAllowedList
+			);
+
+			let (min_contribution, target_provision) = if currency_id_a == trading_pair.0 {
+				((min_contribution_a, min_contribution_b), (target_provision_a, target_provision_b))
+			} else {
+				((min_contribution_b, min_contribution_a), (target_provision_b, target_provision_a))
+			};
+
+			TradingPairStatuses::<T>::insert(
+				trading_pair,
+				TradingPairStatus::Provisioning(ProvisioningParameters {
+					min_contribution,
+					target_provision,
+					accumulated_provision: Default::default(),
+					not_before,
+				}),
+			);
+			InitialShareExchangeRates::remove(trading_pair);
+			Self::deposit_event(RawEvent::ListTradingPair(trading_pair));
+		}
+
+		/// Contribute provision to a provisioning trading pair.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `amount_a`: provision amount of currency A.
+		/// - `amount_b`: provision amount of currency B.
+		#[weight = T::WeightInfo::add_provision()]
+		pub fn add_provision(
+			origin,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			#[compact] amount_a: Balance,
+			#[compact] amount_b: Balance,
+		) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				Self::do_add_provision(&who, currency_id_a, currency_id_b, amount_a, amount_b)
+			})?;
+		}
+
+		/// End the provisioning of a trading pair once the target provision is reached and `not_before`
+		/// has passed, issue shares at the initial price and enable the trading pair. Contributors claim
+		/// their shares by `claim_dex_share`.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		#[weight = T::WeightInfo::end_provisioning()]
+		pub fn end_provisioning(origin, currency_id_a: CurrencyId, currency_id_b: CurrencyId) {
+			with_transaction_result(|| {
+				let _ = ensure_signed(origin)?;
+				Self::do_end_provisioning(TradingPair::new(currency_id_a, currency_id_b))
+			})?;
+		}
+
+		/// Claim the shares of the provision of `owner` after the provisioning has ended.
+		///
+		/// - `owner`: the contributor of the provision.
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		#[weight = T::WeightInfo::claim_dex_share()]
+		pub fn claim_dex_share(
+			origin,
+			owner: T::AccountId,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+		) {
+			with_transaction_result(|| {
+				let _ = ensure_signed(origin)?;
+				Self::do_claim_dex_share(&owner, TradingPair::new(currency_id_a, currency_id_b))
+			})?;
+		}
+
+		/// Cancel the provisioning of a trading pair, contributors get their provision back by
+		/// `refund_provision`.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		#[weight = T::WeightInfo::cancel_provisioning()]
+		pub fn cancel_provisioning(origin, currency_id_a: CurrencyId, currency_id_b: CurrencyId) {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let trading_pair = TradingPair::new(currency_id_a, currency_id_b);
+			ensure!(
+				matches!(Self::trading_pair_statuses(trading_pair), TradingPairStatus::Provisioning(_)),
+				Error::<T>::MustBeProvisioning
+			);
+
+			TradingPairStatuses::<T>::insert(trading_pair, TradingPairStatus::NotEnabled);
+			Self::deposit_event(RawEvent::ProvisioningCancelled(trading_pair));
+		}
+
+		/// Refund the provision of `owner` after the provisioning has been cancelled.
+		///
+		/// - `owner`: the contributor of the provision.
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		#[weight = T::WeightInfo::refund_provision()]
+		pub fn refund_provision(
+			origin,
+			owner: T::AccountId,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+		) {
+			with_transaction_result(|| {
+				let _ = ensure_signed(origin)?;
+				Self::do_refund_provision(&owner, TradingPair::new(currency_id_a, currency_id_b))
+			})?;
+		}
+
+		/// Update the exchange fee rate of a trading pair.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `fee_rate`: (numerator, denominator) of the fee rate, `None` means to use the default
+		///   `GetExchangeFee`.
+		#[weight = T::WeightInfo::set_exchange_fee()]
+		pub fn set_exchange_fee(
+			origin,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			fee_rate: Option<(u32, u32)>,
+		) {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let trading_pair = TradingPair::new(currency_id_a, currency_id_b);
+			ensure!(
+				trading_pair.get_dex_share_currency_id().is_some(),
+				Error::<T>::InvalidCurrencyId
+			);
+			if let Some((numerator, denominator)) = fee_rate {
+				ensure!(numerator < denominator, Error::<T>::InvalidExchangeFee);
+			}
+
+			ExchangeFees::insert(trading_pair, fee_rate);
+			Self::deposit_event(RawEvent::ExchangeFeeUpdated(trading_pair, fee_rate));
+		}
+
+		/// Update the invariant of the liquidity pool of a trading pair, the StableSwap invariant is
+		/// suitable for the currencies which are pegged by the rate multipliers. The liquidity pool
+		/// must be empty or balanced after normalized by the rate multipliers, so that switching
+		/// does not move the price.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `pool_type`: the pool type.
+		#[weight = T::WeightInfo::set_pool_type()]
+		pub fn set_pool_type(
+			origin,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			pool_type: PoolType,
+		) {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let trading_pair = TradingPair::new(currency_id_a, currency_id_b);
+			ensure!(
+				trading_pair.get_dex_share_currency_id().is_some(),
+				Error::<T>::InvalidCurrencyId
+			);
+			if let PoolType::StableSwap(amplification) = pool_type {
+				ensure!(
+					amplification > 0 && amplification <= MAX_AMPLIFICATION,
+					Error::<T>::InvalidAmplification
+				);
+			}
+			ensure!(Self::is_balanced_pool(trading_pair), Error::<T>::PoolNotBalanced);
+
+			PoolTypes::insert(trading_pair, pool_type);
+			Self::deposit_event(RawEvent::PoolTypeUpdated(trading_pair, pool_type));
+		}
+
+		/// Update the share of swap fees charged as protocol fee.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `protocol_fee_share`: the share of swap fee, `None` means no protocol fee.
+		#[weight = T::WeightInfo::set_protocol_fee_share()]
+		pub fn set_protocol_fee_share(origin, protocol_fee_share: Option<Ratio>) {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(share) = protocol_fee_share {
+				ensure!(share <= Ratio::one(), Error::<T>::InvalidProtocolFeeShare);
+			}
+
+			ProtocolFeeShare::set(protocol_fee_share);
+			Self::deposit_event(RawEvent::ProtocolFeeShareUpdated(protocol_fee_share));
+		}
+
+		/// Place a limit order to swap exact supply amount along the trading path, the supply amount
+		/// and `LimitOrderDeposit` of native currency will be reserved until the order is executed,
+		/// cancelled or expired. An account can have at most `MaxLimitOrdersPerAccount` pending orders.
+		///
+		/// - `path`: trading path.
+		/// - `supply_amount`: exact supply amount.
+		/// - `min_target_amount`: the minimum target amount to execute the order.
+		/// - `expiry`: the order can not be executed after this block number.
+		#[weight = <T as Trait>::WeightInfo::place_limit_order()]
+		pub fn place_limit_order(
+			origin,
+			path: Vec<CurrencyId>,
+			#[compact] supply_amount: Balance,
+			#[compact] min_target_amount: Balance,
+			expiry: T::BlockNumber,
+		) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				Self::do_place_limit_order(who, path, supply_amount, min_target_amount, expiry)?;
+				Ok(())
+			})?;
+		}
+
+		/// Cancel the limit order and unreserve the supply amount and the deposit.
+		///
+		/// The dispatch origin of this call must be the owner of the order.
+		///
+		/// - `order_id`: limit order id.
+		#[weight = <T as Trait>::WeightInfo::cancel_limit_order()]
+		pub fn cancel_limit_order(origin, order_id: OrderId) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				let order = Self::limit_orders(order_id).ok_or(Error::<T>::LimitOrderNotFound)?;
+				ensure!(order.owner == who, Error::<T>::NoPermission);
+
+				Self::remove_limit_order(order_id, &order);
+				Self::deposit_event(RawEvent::LimitOrderCancelled(order_id, who));
+				Ok(())
+			})?;
+		}
+
+		/// Execute the limit order, submitted by the offchain worker.
+		///
+		/// The dispatch origin of this call must be _None_.
+		///
+		/// - `order_id`: limit order id.
+		#[weight = (<T as Trait>::WeightInfo::execute_limit_order(), DispatchClass::Operational)]
+		pub fn execute_limit_order(origin, order_id: OrderId) {
+			with_transaction_result(|| {
+				ensure_none(origin)?;
+				Self::do_execute_limit_order(order_id)
+			})?;
+		}
+
+		/// Remove the expired limit order and unreserve the supply amount and the deposit, submitted by the
+		/// offchain worker.
+		///
+		/// The dispatch origin of this call must be _None_.
+		///
+		/// - `order_id`: limit order id.
+		#[weight = (<T as Trait>::WeightInfo::expire_limit_order(), DispatchClass::Operational)]
+		pub fn expire_limit_order(origin, order_id: OrderId) {
+			with_transaction_result(|| {
+				ensure_none(origin)?;
+				let order = Self::limit_orders(order_id).ok_or(Error::<T>::LimitOrderNotFound)?;
+				ensure!(Self::is_limit_order_expired(&order), Error::<T>::LimitOrderNotExpired);
+
+				Self::remove_limit_order(order_id, &order);
+				Self::deposit_event(RawEvent::LimitOrderExpired(order_id, order.owner));
+				Ok(())
+			})?;
+		}
+
+		/// Runs after every block. Start offchain worker to check limit orders and
+		/// submit unsigned tx to execute or expire them.
+		fn offchain_worker(now: T::BlockNumber) {
+			if let Err(e) = Self::_offchain_worker() {
+				debug::info!(
+					target: "dex offchain worker",
+					"cannot run offchain worker at {:?}: {:?}",
+					now,
+					e,
+				);
+			} else {
+				debug::debug!(
+					target: "dex offchain worker",
+					"offchain worker start at block: {:?} already done!",
+					now,
+				);
+			}
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	fn account_id() -> T::AccountId {
+		T::ModuleId::get().into_account()
+	}
+
+	fn do_place_limit_order(
+		who: T::AccountId,
+		path: Vec<CurrencyId>,
+		supply_amount: Balance,
+		min_target_amount: Balance,
+		expiry: T::BlockNumber,
+	) -> sp_std::result::Result<OrderId, DispatchError> {
+		ensure!(
+			path.len() >= 2 && path.len() <= T::TradingPathLimit::get(),
+			Error::<T>::InvalidTradingPathLength
+		);
+		ensure!(
+			path.windows(2)
+				.all(|pair| Self::is_enabled(TradingPair::new(pair[0], pair[1]))),
+			Error::<T>::TradingPairNotAllowed
+		);
+		ensure!(!supply_amount.is_zero(), Error::<T>::ZeroSupplyAmount);
+		ensure!(expiry > <system::Module<T>>::block_number(), Error::<T>::InvalidExpiry);
+		ensure!(
+			Self::limit_order_counts(&who) < T::MaxLimitOrdersPerAccount::get(),
+			Error::<T>::TooManyLimitOrders
+		);
+
+		let deposit = T::LimitOrderDeposit::get();
+		T::Currency::reserve(path[0], &who, supply_amount)?;
+		T::Currency::reserve(T::GetNativeCurrencyId::get(), &who, deposit)?;
+		LimitOrderCounts::<T>::mutate(&who, |count| *count = count.saturating_add(1));
+		let order_id = NextOrderId::mutate(|next_order_id| {
+			let order_id = *next_order_id;
+			*next_order_id = next_order_id.saturating_add(1);
+			order_id
+		});
+		LimitOrders::<T>::insert(
+			order_id,
+			LimitOrder {
+				owner: who.clone(),
+				path: path.clone(),
+				supply_amount,
+				min_target_amount,
+				expiry,
+				deposit,
+			},
+		);
+
+		Self::deposit_event(RawEvent::LimitOrderPlaced(
+			order_id,
+			who,
+			path,
+			supply_amount,
+			min_target_amount,
+			expiry,
+		));
+		Ok(order_id)
+	}
+
+	fn do_execute_limit_order(order_id: OrderId) -> DispatchResult {
+		let order = Self::limit_orders(order_id).ok_or(Error::<T>::LimitOrderNotFound)?;
+		ensure!(!Self::is_limit_order_expired(&order), Error::<T>::LimitOrderExpired);
+
+		Self::remove_limit_order(order_id, &order);
+		let target_amount = Self::do_swap_with_exact_supply(
+			&order.owner,
+			&order.path,
+			order.supply_amount,
+			order.min_target_amount,
+			None,
+		)?;
+
+		Self::deposit_event(RawEvent::LimitOrderExecuted(order_id, order.owner, target_amount));
+		Ok(())
+	}
+
+	/// Remove the limit order and unreserve the supply amount and the deposit.
+	fn remove_limit_order(order_id: OrderId, order: &LimitOrder<T::AccountId, T::BlockNumber>) {
+		let _ = T::Currency::unreserve(order.path[0], &order.owner, order.supply_amount);
+		let _ = T::Currency::unreserve(T::GetNativeCurrencyId::get(), &order.owner, order.deposit);
+		LimitOrders::<T>::remove(order_id);
+		LimitOrderCounts::<T>::mutate_exists(&order.owner, |maybe_count| {
+			*maybe_count = maybe_count
+				.and_then(|count| count.checked_sub(1))
+				.filter(|count| !count.is_zero());
+		});
+	}
+
+	fn is_limit_order_expired(order: &LimitOrder<T::AccountId, T::BlockNumber>) -> bool {
+		<system::Module<T>>::block_number() > order.expiry
+	}
+
+	/// Check whether the target amount of the swap meets the limit.
+	fn is_limit_order_executable(order: &LimitOrder<T::AccountId, T::BlockNumber>) -> bool {
+		Self::get_target_amounts(&order.path, order.supply_amount, None)
+			.map_or(false, |amounts| amounts[amounts.len() - 1] >= order.min_target_amount)
+	}
+
+	fn submit_unsigned_execute_limit_order_tx(order_id: OrderId) {
+		let call = Call::<T>::execute_limit_order(order_id);
+		if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+			debug::info!(
+				target: "dex offchain worker",
+				"submit unsigned execute limit order tx for \nOrderId {:?} \nfailed!",
+				order_id,
+			);
+		}
+	}
+
+	fn submit_unsigned_expire_limit_order_tx(order_id: OrderId) {
+		let call = Call::<T>::expire_limit_order(order_id);
+		if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+			debug::info!(
+				target: "dex offchain worker",
+				"submit unsigned expire limit order tx for \nOrderId {:?} \nfailed!",
+				order_id,
+			);
+		}
+	}
+
+	fn _offchain_worker() -> Result<(), OffchainErr> {
+		// acquire offchain worker lock
+		let lock_expiration = Duration::from_millis(LOCK_DURATION);
+		let mut lock = StorageLock::<'_, Time>::with_deadline(&OFFCHAIN_WORKER_LOCK, lock_expiration);
+		let mut guard = lock.try_lock().map_err(|_| OffchainErr::OffchainLock)?;
+
+		// get to_be_continue record,
+		// if it exsits, iterator map storage start with previous key
+		let mut to_be_continue = StorageValueRef::persistent(&OFFCHAIN_WORKER_DATA);
+		let start_key = to_be_continue.get::<Vec<u8>>().unwrap_or_default();
+
+		// get the max iterationns config
+		let max_iterations = StorageValueRef::persistent(&OFFCHAIN_WORKER_MAX_ITERATIONS)
+			.get::<u32>()
+			.unwrap_or(Some(DEFAULT_MAX_ITERATIONS));
+
+		debug::debug!(target: "dex offchain worker", "max iterations is {:?}", max_iterations);
+
+		let mut iterator = <LimitOrders<T> as IterableStorageMapExtended<_, _>>::iter(max_iterations, start_key);
+		while let Some((order_id, order)) = iterator.next() {
+			if Self::is_limit_order_expired(&order) {
+				Self::submit_unsigned_expire_limit_order_tx(order_id);
+			} else if Self::is_limit_order_executable(&order) {
+				Self::submit_unsigned_execute_limit_order_tx(order_id);
+			}
+
+			// extend offchain worker lock
+			guard.extend_lock().map_err(|_| OffchainErr::OffchainLock)?;
+		}
+
+		// if iteration for map storage finished, clear to be continue record
+		// otherwise, update to be continue record
+		if iterator.finished {
+			to_be_continue.clear();
+		} else {
+			to_be_continue.set(&iterator.storage_map_iterator.previous_key);
+		}
+
+		// Consume the guard but **do not** unlock the underlying lock.
+		guard.forget();
+
+		Ok(())
+	}
+
+	/// Get the exchange fee rate of the trading pair.
+	pub fn get_exchange_fee(trading_pair: TradingPair) -> (u32, u32) {
+		Self::exchange_fees(trading_pair).unwrap_or_else(T::GetExchangeFee::get)
+	}
+
+	fn ensure_not_lent(trading_pair: TradingPair) -> DispatchResult {
+		ensure!(
+			Self::flash_swap_locked_pair() != Some(trading_pair),
+			Error::<T>::FlashSwapInProgress
+		);
+		Ok(())
+	}
+
+	fn is_enabled(trading_pair: TradingPair) -> bool {
+		matches!(Self::trading_pair_statuses(trading_pair), TradingPairStatus::Enabled)
+	}
+
+	/// Enable the trading pair and add it to the cache of the enabled trading
+	/// pairs.
+	fn set_enabled(trading_pair: TradingPair) {
+		TradingPairStatuses::<T>::insert(trading_pair, TradingPairStatus::Enabled);
+		EnabledTradingPairs::mutate(|trading_pairs| {
+			if !trading_pairs.contains(&trading_pair) {
+				trading_pairs.push(trading_pair);
+			}
+		});
+	}
+
+	/// Disable the trading pair and remove it from the cache of the enabled
+	/// trading pairs.
+	fn set_not_enabled(trading_pair: TradingPair) {
+		TradingPairStatuses::<T>::insert(trading_pair, TradingPairStatus::NotEnabled);
+		EnabledTradingPairs::mutate(|trading_pairs| trading_pairs.retain(|pair| *pair != trading_pair));
+	}
+
+	fn do_add_provision(
+		who: &T::AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		amount_a: Balance,
+		amount_b: Balance,
+	) -> DispatchResult {
+		let trading_pair = TradingPair::new(currency_id_a, currency_id_b);
+		let mut provisioning_parameters = match Self::trading_pair_statuses(trading_pair) {
+			TradingPairStatus::Provisioning(provisioning_parameters) => provisioning_parameters,
+			_ => return Err(Error::<T>::MustBeProvisioning.into()),
+		};
+		let (contribution_0, contribution_1) = if currency_id_a == trading_pair.0 {
+			(amount_a, amount_b)
+		} else {
+			(amount_b, amount_a)
+		};
+		ensure!(
+			contribution_0 >= provisioning_parameters.min_contribution.0
+				|| contribution_1 >= provisioning_parameters.min_contribution.1,
+			Error::<T>::InvalidContributionIncrement
+		);
+
+		let module_account_id = Self::account_id();
+		T::Currency::transfer(trading_pair.0, who, &module_account_id, contribution_0)?;
+		T::Currency::transfer(trading_pair.1, who, &module_account_id, contribution_1)?;
+
+		ProvisioningPool::<T>::mutate(trading_pair, who, |(pool_0, pool_1)| {
+			*pool_0 = pool_0.saturating_add(contribution_0);
+			*pool_1 = pool_1.saturating_add(contribution_1);
+		});
+		let (accumulated_0, accumulated_1) = provisioning_parameters.accumulated_provision;
+		provisioning_parameters.accumulated_provision = (
+			accumulated_0.saturating_add(contribution_0),
+			accumulated_1.saturating_add(contribution_1),
+		);
+		TradingPairStatuses::<T>::insert(trading_pair, TradingPairStatus::Provisioning(provisioning_parameters));
+
+		Self::deposit_event(RawEvent::AddProvision(
+			who.clone(),
+			trading_pair.0,
+			contribution_0,
+			trading_pair.1,
+			contribution_1,
+		));
+		Ok(())
+	}
+
+	fn do_end_provisioning(trading_pair: TradingPair) -> DispatchResult {
+		let provisioning_parameters = match Self::trading_pair_statuses(trading_pair) {
+			TradingPairStatus::Provisioning(provisioning_parameters) => provisioning_parameters,
+			_ => return Err(Error::<T>::MustBeProvisioning.into()),
+		};
+		let (total_provision_0, total_provision_1) = provisioning_parameters.accumulated_provision;
+		ensure!(
+			<system::Module<T>>::block_number() >= provisioning_parameters.not_before
+				&& !total_provision_0.is_zero()
+				&& !total_provision_1.is_zero()
+				&& total_provision_0 >= provisioning_parameters.target_provision.0
+				&& total_provision_1 >= provisioning_parameters.target_provision.1,
+			Error::<T>::UnqualifiedProvision
+		);
+		let lp_share_currency_id = trading_pair
+			.get_dex_share_currency_id()
+			.ok_or(Error::<T>::InvalidCurrencyId)?;
+
+		// the initial price is determined by the total provision, shares are
+		// denominated in currency_0. All the shares are issued to the module account
+		// at once, and transferred to contributors when they claim.
+		let share_exchange_rate_0 = ExchangeRate::one();
+		let share_exchange_rate_1 =
+			ExchangeRate::checked_from_rational(total_provision_0, total_provision_1).unwrap_or_default();
+		let total_shares = share_exchange_rate_0
+			.saturating_mul_int(total_provision_0)
+			.saturating_add(share_exchange_rate_1.saturating_mul_int(total_provision_1));
+		T::Currency::deposit(lp_share_currency_id, &Self::account_id(), total_shares)?;
+		InitialShareExchangeRates::insert(trading_pair, (share_exchange_rate_0, share_exchange_rate_1));
+
+		Self::update_cumulative_price(trading_pair);
+		LiquidityPool::mutate(trading_pair, |(pool_0, pool_1)| {
+			*pool_0 = pool_0.saturating_add(total_provision_0);
+			*pool_1 = pool_1.saturating_add(total_provision_1);
+		});
+		Self::set_enabled(trading_pair);
+
+		Self::deposit_event(RawEvent::ProvisioningToEnabled(
+			trading_pair,
+			total_provision_0,
+			total_provision_1,
+			total_shares,
+		));
+		Ok(())
 	}
-}
 
-impl<T: Trait> Module<T> {
-	fn account_id() -> T::AccountId {
-		T::ModuleId::get().into_account()
+	fn do_claim_dex_share(who: &T::AccountId, trading_pair: TradingPair) -> DispatchResult {
+		ensure!(
+			!matches!(
+				Self::trading_pair_statuses(trading_pair),
+				TradingPairStatus::Provisioning(_)
+			),
+			Error::<T>::ProvisioningNotEnded
+		);
+		let (share_exchange_rate_0, share_exchange_rate_1) =
+			Self::initial_share_exchange_rates(trading_pair).ok_or(Error::<T>::ProvisioningNotEnded)?;
+		let lp_share_currency_id = trading_pair
+			.get_dex_share_currency_id()
+			.ok_or(Error::<T>::InvalidCurrencyId)?;
+		ensure!(
+			ProvisioningPool::<T>::contains_key(trading_pair, who),
+			Error::<T>::NoProvision
+		);
+
+		let (contribution_0, contribution_1) = ProvisioningPool::<T>::take(trading_pair, who);
+		let share_amount = share_exchange_rate_0
+			.saturating_mul_int(contribution_0)
+			.saturating_add(share_exchange_rate_1.saturating_mul_int(contribution_1));
+		T::Currency::transfer(lp_share_currency_id, &Self::account_id(), who, share_amount)?;
+
+		Self::deposit_event(RawEvent::ClaimDexShare(who.clone(), trading_pair, share_amount));
+		Ok(())
+	}
+
+	fn do_refund_provision(who: &T::AccountId, trading_pair: TradingPair) -> DispatchResult {
+		ensure!(
+			!matches!(
+				Self::trading_pair_statuses(trading_pair),
+				TradingPairStatus::Provisioning(_)
+			) && Self::initial_share_exchange_rates(trading_pair).is_none(),
+			Error::<T>::ProvisioningNotCancelled
+		);
+		ensure!(
+			ProvisioningPool::<T>::contains_key(trading_pair, who),
+			Error::<T>::NoProvision
+		);
+
+		let (contribution_0, contribution_1) = ProvisioningPool::<T>::take(trading_pair, who);
+		let module_account_id = Self::account_id();
+		T::Currency::transfer(trading_pair.0, &module_account_id, who, contribution_0)?;
+		T::Currency::transfer(trading_pair.1, &module_account_id, who, contribution_1)?;
+
+		Self::deposit_event(RawEvent::RefundProvision(
+			who.clone(),
+			trading_pair.0,
+			contribution_0,
+			trading_pair.1,
+			contribution_1,
+		));
+		Ok(())
 	}
 
 	fn do_add_liquidity(
@@ -226,25 +1438,27 @@ impl<T: Trait> Module<T> {
 		max_amount_a: Balance,
 		max_amount_b: Balance,
 		deposit_increment_share: bool,
-	) -> DispatchResult {
+	) -> sp_std::result::Result<Balance, DispatchError> {
 		let trading_pair = TradingPair::new(currency_id_a, currency_id_b);
-		ensure!(
-			T::EnabledTradingPairs::get().contains(&trading_pair),
-			Error::<T>::TradingPairNotAllowed
-		);
-
-		LiquidityPool::try_mutate(trading_pair, |(pool_0, pool_1)| -> DispatchResult {
-			let lp_share_currency_id = trading_pair
-				.get_dex_share_currency_id()
-				.ok_or(Error::<T>::InvalidCurrencyId)?;
-			let total_shares = T::Currency::total_issuance(lp_share_currency_id);
-			let (max_amount_0, max_amount_1) = if currency_id_a == trading_pair.0 {
-				(max_amount_a, max_amount_b)
-			} else {
-				(max_amount_b, max_amount_a)
-			};
-			let (pool_0_increment, pool_1_increment, share_increment): (Balance, Balance, Balance) =
-				if total_shares.is_zero() {
+		ensure!(Self::is_enabled(trading_pair), Error::<T>::TradingPairNotAllowed);
+		Self::ensure_not_lent(trading_pair)?;
+
+		Self::update_cumulative_price(trading_pair);
+		LiquidityPool::try_mutate(
+			trading_pair,
+			|(pool_0, pool_1)| -> sp_std::result::Result<Balance, DispatchError> {
+				let lp_share_currency_id = trading_pair
+					.get_dex_share_currency_id()
+					.ok_or(Error::<T>::InvalidCurrencyId)?;
+				let total_shares = T::Currency::total_issuance(lp_share_currency_id);
+				let (max_amount_0, max_amount_1) = if currency_id_a == trading_pair.0 {
+					(max_amount_a, max_amount_b)
+				} else {
+					(max_amount_b, max_amount_a)
+				};
+				let (pool_0_increment, pool_1_increment, share_increment): (Balance, Balance, Balance) = if total_shares
+					.is_zero()
+				{
 					// initialize this liquidity pool, the initial share is equal to the max value
 					// between base currency amount and other currency amount
 					let initial_share = sp_std::cmp::max(max_amount_0, max_amount_1);
@@ -271,33 +1485,181 @@ impl<T: Trait> Module<T> {
 					}
 				};
 
-			ensure!(
-				!share_increment.is_zero() && !pool_0_increment.is_zero() && !pool_1_increment.is_zero(),
-				Error::<T>::InvalidLiquidityIncrement,
-			);
-
-			let module_account_id = Self::account_id();
-			T::Currency::transfer(trading_pair.0, who, &module_account_id, pool_0_increment)?;
-			T::Currency::transfer(trading_pair.1, who, &module_account_id, pool_1_increment)?;
-			T::Currency::deposit(lp_share_currency_id, who, share_increment)?;
+				ensure!(
+					!share_increment.is_zero() && !pool_0_increment.is_zero() && !pool_1_increment.is_zero(),
+					Error::<T>::InvalidLiquidityIncrement,
+				);
+
+				let module_account_id = Self::account_id();
+				T::Currency::transfer(trading_pair.0, who, &module_account_id, pool_0_increment)?;
+				T::Currency::transfer(trading_pair.1, who, &module_account_id, pool_1_increment)?;
+				T::Currency::deposit(lp_share_currency_id, who, share_increment)?;
+
+				*pool_0 = pool_0.saturating_add(pool_0_increment);
+				*pool_1 = pool_1.saturating_add(pool_1_increment);
+
+				if deposit_increment_share {
+					T::DEXIncentives::do_deposit_dex_share(who, lp_share_currency_id, share_increment)?;
+				}
+
+				Self::deposit_event(RawEvent::AddLiquidity(
+					who.clone(),
+					trading_pair.0,
+					pool_0_increment,
+					trading_pair.1,
+					pool_1_increment,
+					share_increment,
+				));
+				Ok(share_increment)
+			},
+		)
+	}
 
-			*pool_0 = pool_0.saturating_add(pool_0_increment);
-			*pool_1 = pool_1.saturating_add(pool_1_increment);
+	fn do_flash_swap(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		other_currency_id: CurrencyId,
+		amount: Balance,
+		call: <T as Trait>::Call,
+	) -> DispatchResult {
+		let trading_pair = TradingPair::new(currency_id, other_currency_id);
+		ensure!(Self::is_enabled(trading_pair), Error::<T>::TradingPairNotAllowed);
+		ensure!(!amount.is_zero(), Error::<T>::ZeroFlashSwapAmount);
+		ensure!(
+			Self::flash_swap_locked_pair().is_none(),
+			Error::<T>::FlashSwapInProgress
+		);
+		ensure!(
+			T::FlashSwapCallFilter::filter(&call),
+			Error::<T>::FlashSwapCallNotAllowed
+		);
+		let (pool, _) = Self::get_liquidity(currency_id, other_currency_id);
+		ensure!(amount < pool, Error::<T>::InsufficientLiquidity);
+
+		// round the fee up so that any borrowed amount pays the fee
+		let (fee_numerator, fee_denominator) = Self::get_exchange_fee(trading_pair);
+		let fee_amount: Balance = U256::from(amount)
+			.saturating_mul(U256::from(fee_numerator))
+			.saturating_add(U256::from(fee_denominator.saturating_sub(1)))
+			.checked_div(U256::from(fee_denominator))
+			.and_then(|n| TryInto::<Balance>::try_into(n).ok())
+			.unwrap_or_else(Zero::zero);
+		let protocol_fee_amount = Self::protocol_fee_share()
+			.unwrap_or_else(Ratio::zero)
+			.saturating_mul_int(fee_amount);
+
+		let module_account_id = Self::account_id();
+		T::Currency::transfer(currency_id, &module_account_id, who, amount)?;
+
+		// the trading pair is locked until the borrowed amount is repaid, the call can
+		// not operate on the liquidity pool whose reserves include the lent amount.
+		FlashSwapLockedPair::put(trading_pair);
+		let dispatch_result = call
+			.dispatch(system::RawOrigin::Signed(who.clone()).into())
+			.map_err(|e| e.error);
+		FlashSwapLockedPair::kill();
+		dispatch_result?;
+
+		// repay the borrowed amount plus the fee, the pool is credited with the fee
+		// except the protocol fee share.
+		T::Currency::transfer(currency_id, who, &module_account_id, amount.saturating_add(fee_amount))?;
+		if !protocol_fee_amount.is_zero() {
+			T::Currency::transfer(
+				currency_id,
+				&module_account_id,
+				&T::ProtocolFeeReceiver::get(),
+				protocol_fee_amount,
+			)?;
+		}
 
-			if deposit_increment_share {
-				T::DEXIncentives::do_deposit_dex_share(who, lp_share_currency_id, share_increment)?;
+		Self::update_cumulative_price(trading_pair);
+		let pool_increment = fee_amount.saturating_sub(protocol_fee_amount);
+		LiquidityPool::mutate(trading_pair, |(pool_0, pool_1)| {
+			if currency_id == trading_pair.0 {
+				*pool_0 = pool_0.saturating_add(pool_increment);
+			} else {
+				*pool_1 = pool_1.saturating_add(pool_increment);
 			}
+		});
 
-			Self::deposit_event(RawEvent::AddLiquidity(
-				who.clone(),
-				trading_pair.0,
-				pool_0_increment,
-				trading_pair.1,
-				pool_1_increment,
-				share_increment,
-			));
-			Ok(())
-		})
+		Self::deposit_event(RawEvent::FlashSwap(who.clone(), currency_id, amount, fee_amount));
+		Ok(())
+	}
+
+	fn do_add_liquidity_single_token(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		other_currency_id: CurrencyId,
+		amount: Balance,
+		min_share_increment: Balance,
+		deposit_increment_share: bool,
+	) -> DispatchResult {
+		let trading_pair = TradingPair::new(currency_id, other_currency_id);
+		ensure!(Self::is_enabled(trading_pair), Error::<T>::TradingPairNotAllowed);
+
+		ensure!(
+			Self::pool_types(trading_pair) == PoolType::ConstantProduct,
+			Error::<T>::NotSupportedPoolType
+		);
+
+		let (supply_pool, _) = Self::get_liquidity(currency_id, other_currency_id);
+		ensure!(!supply_pool.is_zero(), Error::<T>::InsufficientLiquidity);
+
+		let swap_amount = Self::get_single_token_swap_amount(supply_pool, amount, Self::get_exchange_fee(trading_pair));
+		ensure!(
+			!swap_amount.is_zero() && swap_amount < amount,
+			Error::<T>::InvalidLiquidityIncrement
+		);
+
+		// the slippage of the internal swap is covered by `min_share_increment`
+		let target_amount =
+			Self::do_swap_with_exact_supply(who, &[currency_id, other_currency_id], swap_amount, Zero::zero(), None)?;
+		let share_increment = Self::do_add_liquidity(
+			who,
+			currency_id,
+			other_currency_id,
+			amount.saturating_sub(swap_amount),
+			target_amount,
+			deposit_increment_share,
+		)?;
+		ensure!(
+			share_increment >= min_share_increment,
+			Error::<T>::InsufficientShareIncrement
+		);
+		Ok(())
+	}
+
+	/// Get how much of the supply amount should be swapped to the other
+	/// currency, so that the rest and the swapped target amount are in the
+	/// proportion of the liquidity pool after swap.
+	///
+	/// s = (sqrt(R^2 * (d + r)^2 + 4 * r * d * A * R) - R * (d + r)) / (2 * r),
+	/// where R is the supply pool, A is the supply amount, d is the fee
+	/// denominator and r is the fee denominator minus the fee numerator.
+	fn get_single_token_swap_amount(supply_pool: Balance, supply_amount: Balance, fee_rate: (u32, u32)) -> Balance {
+		let (fee_numerator, fee_denominator) = fee_rate;
+		let fee_remainder = U256::from(fee_denominator.saturating_sub(fee_numerator));
+		let fee_denominator = U256::from(fee_denominator);
+		if supply_pool.is_zero() || supply_amount.is_zero() || fee_remainder.is_zero() {
+			return Zero::zero();
+		}
+
+		let supply_pool = U256::from(supply_pool);
+		let b = supply_pool.saturating_mul(fee_denominator.saturating_add(fee_remainder));
+		let discriminant = b.saturating_mul(b).saturating_add(
+			U256::from(4u8)
+				.saturating_mul(fee_remainder)
+				.saturating_mul(fee_denominator)
+				.saturating_mul(U256::from(supply_amount))
+				.saturating_mul(supply_pool),
+		);
+
+		discriminant
+			.integer_sqrt()
+			.saturating_sub(b)
+			.checked_div(U256::from(2u8).saturating_mul(fee_remainder))
+			.and_then(|n| TryInto::<Balance>::try_into(n).ok())
+			.unwrap_or_else(Zero::zero)
 	}
 
 	fn do_remove_liquidity(
@@ -311,7 +1673,9 @@ impl<T: Trait> Module<T> {
 			return Ok(());
 		}
 		let trading_pair = TradingPair::new(currency_id_a, currency_id_b);
+		Self::ensure_not_lent(trading_pair)?;
 
+		Self::update_cumulative_price(trading_pair);
 		LiquidityPool::try_mutate(trading_pair, |(pool_0, pool_1)| -> DispatchResult {
 			let lp_share_currency_id = trading_pair
 				.get_dex_share_currency_id()
@@ -344,7 +1708,7 @@ impl<T: Trait> Module<T> {
 		})
 	}
 
-	fn get_liquidity(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
+	pub fn get_liquidity(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
 		let trading_pair = TradingPair::new(currency_id_a, currency_id_b);
 		let (pool_0, pool_1) = Self::liquidity_pool(trading_pair);
 		if currency_id_a == trading_pair.0 {
@@ -354,13 +1718,56 @@ impl<T: Trait> Module<T> {
 		}
 	}
 
+	/// Get the underlying amounts of the currencies for the share of the
+	/// liquidity pool, in the order of the queried currencies.
+	pub fn get_share_amounts(
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		share: Balance,
+	) -> (Balance, Balance) {
+		let total_shares = TradingPair::new(currency_id_a, currency_id_b)
+			.get_dex_share_currency_id()
+			.map(T::Currency::total_issuance)
+			.unwrap_or_default();
+		if share.is_zero() || total_shares.is_zero() {
+			return (Zero::zero(), Zero::zero());
+		}
+
+		let proportion = Ratio::checked_from_rational(share, total_shares).unwrap_or_default();
+		let (pool_a, pool_b) = Self::get_liquidity(currency_id_a, currency_id_b);
+		(
+			proportion.saturating_mul_int(pool_a),
+			proportion.saturating_mul_int(pool_b),
+		)
+	}
+
+	/// Get the liquidity position of the account in the liquidity pool, returns
+	/// (free_share, staked_share), the staked share is deposited to
+	/// `DEXIncentives`.
+	pub fn get_liquidity_position(
+		who: &T::AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+	) -> Option<(Balance, Balance)> {
+		let lp_share_currency_id = TradingPair::new(currency_id_a, currency_id_b).get_dex_share_currency_id()?;
+		Some((
+			T::Currency::free_balance(lp_share_currency_id, who),
+			T::DEXIncentives::get_staked_dex_share(who, lp_share_currency_id),
+		))
+	}
+
 	/// Get how much target amount will be got for specific supply amount and
 	/// price impact
-	fn get_target_amount(supply_pool: Balance, target_pool: Balance, supply_amount: Balance) -> Balance {
+	fn get_target_amount(
+		supply_pool: Balance,
+		target_pool: Balance,
+		supply_amount: Balance,
+		fee_rate: (u32, u32),
+	) -> Balance {
 		if supply_amount.is_zero() || supply_pool.is_zero() || target_pool.is_zero() {
 			Zero::zero()
 		} else {
-			let (fee_numerator, fee_denominator) = T::GetExchangeFee::get();
+			let (fee_numerator, fee_denominator) = fee_rate;
 			let supply_amount_with_fee =
 				supply_amount.saturating_mul(fee_denominator.saturating_sub(fee_numerator).unique_saturated_into());
 			let numerator: U256 = U256::from(supply_amount_with_fee).saturating_mul(U256::from(target_pool));
@@ -376,11 +1783,16 @@ impl<T: Trait> Module<T> {
 	}
 
 	/// Get how much supply amount will be paid for specific target amount.
-	fn get_supply_amount(supply_pool: Balance, target_pool: Balance, target_amount: Balance) -> Balance {
+	fn get_supply_amount(
+		supply_pool: Balance,
+		target_pool: Balance,
+		target_amount: Balance,
+		fee_rate: (u32, u32),
+	) -> Balance {
 		if target_amount.is_zero() || supply_pool.is_zero() || target_pool.is_zero() {
 			Zero::zero()
 		} else {
-			let (fee_numerator, fee_denominator) = T::GetExchangeFee::get();
+			let (fee_numerator, fee_denominator) = fee_rate;
 			let numerator: U256 = U256::from(supply_pool)
 				.saturating_mul(U256::from(target_amount))
 				.saturating_mul(U256::from(fee_denominator));
@@ -396,7 +1808,208 @@ impl<T: Trait> Module<T> {
 		}
 	}
 
-	fn get_target_amounts(
+	/// Get the StableSwap invariant D of the pools, which satisfies
+	/// A * n^n * (x + y) + D = A * n^n * D + D^(n + 1) / (n^n * x * y), n = 2.
+	fn get_stable_swap_invariant(pool_0: U256, pool_1: U256, amplification: u128) -> Option<U256> {
+		let sum = pool_0.checked_add(pool_1)?;
+		if sum.is_zero() {
+			return Some(U256::zero());
+		}
+
+		let ann = U256::from(amplification).checked_mul(U256::from(4u8))?;
+		let mut invariant = sum;
+		for _ in 0..STABLE_SWAP_MAX_ITERATIONS {
+			// D_P = D^3 / (n^n * x * y)
+			let invariant_product = invariant
+				.checked_mul(invariant)?
+				.checked_div(pool_0.checked_mul(U256::from(2u8))?)?
+				.checked_mul(invariant)?
+				.checked_div(pool_1.checked_mul(U256::from(2u8))?)?;
+			let previous = invariant;
+			// D = (Ann * S + D_P * n) * D / ((Ann - 1) * D + (n + 1) * D_P)
+			let numerator = ann
+				.checked_mul(sum)?
+				.checked_add(invariant_product.checked_mul(U256::from(2u8))?)?
+				.checked_mul(invariant)?;
+			let denominator = ann
+				.checked_sub(U256::one())?
+				.checked_mul(invariant)?
+				.checked_add(invariant_product.checked_mul(U256::from(3u8))?)?;
+			invariant = numerator.checked_div(denominator)?;
+
+			if invariant.max(previous).saturating_sub(invariant.min(previous)) <= U256::one() {
+				return Some(invariant);
+			}
+		}
+		None
+	}
+
+	/// Get the other pool of the StableSwap for the new pool and the invariant
+	/// D, which is the root of y^2 + (x + D / Ann - D) * y = D^3 / (n^n * x *
+	/// Ann).
+	fn get_stable_swap_pool(new_pool: U256, invariant: U256, amplification: u128) -> Option<U256> {
+		let ann = U256::from(amplification).checked_mul(U256::from(4u8))?;
+		let c = invariant
+			.checked_mul(invariant)?
+			.checked_div(new_pool.checked_mul(U256::from(2u8))?)?
+			.checked_mul(invariant)?
+			.checked_div(ann.checked_mul(U256::from(2u8))?)?;
+		let b = new_pool.checked_add(invariant.checked_div(ann)?)?;
+
+		let mut pool = invariant;
+		for _ in 0..STABLE_SWAP_MAX_ITERATIONS {
+			let previous = pool;
+			// y = (y^2 + c) / (2 * y + b - D)
+			pool = pool.checked_mul(pool)?.checked_add(c)?.checked_div(
+				pool.checked_mul(U256::from(2u8))?
+					.checked_add(b)?
+					.checked_sub(invariant)?,
+			)?;
+
+			if pool.max(previous).saturating_sub(pool.min(previous)) <= U256::one() {
+				return Some(pool);
+			}
+		}
+		None
+	}
+
+	/// Normalize the amount by the rate multiplier of the StableSwap pool.
+	fn normalize_stable_swap_amount(amount: U256, rate: ExchangeRate) -> Option<U256> {
+		amount
+			.checked_mul(U256::from(rate.into_inner()))?
+			.checked_div(U256::from(ExchangeRate::accuracy()))
+	}
+
+	/// Convert the normalized amount of the StableSwap pool back by the rate
+	/// multiplier.
+	fn denormalize_stable_swap_amount(amount: U256, rate: ExchangeRate, rounding_up: bool) -> Option<U256> {
+		let rate = U256::from(rate.into_inner());
+		let numerator = amount.checked_mul(U256::from(ExchangeRate::accuracy()))?;
+		if rounding_up {
+			numerator.checked_add(rate.checked_sub(U256::one())?)?.checked_div(rate)
+		} else {
+			numerator.checked_div(rate)
+		}
+	}
+
+	/// Get the rate multipliers of (supply_currency_id, target_currency_id) in
+	/// the StableSwap pool.
+	fn get_stable_swap_rates(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+	) -> (ExchangeRate, ExchangeRate) {
+		let trading_pair = TradingPair::new(supply_currency_id, target_currency_id);
+		(
+			T::StableSwapRateProvider::get_rate_multiplier(trading_pair, supply_currency_id),
+			T::StableSwapRateProvider::get_rate_multiplier(trading_pair, target_currency_id),
+		)
+	}
+
+	/// Whether the liquidity pool of the trading pair is empty, or the pools
+	/// normalized by the rate multipliers are equal within
+	/// `POOL_TYPE_SWITCH_IMBALANCE_LIMIT`, where all the pool types give the
+	/// same price.
+	fn is_balanced_pool(trading_pair: TradingPair) -> bool {
+		let (pool_0, pool_1) = Self::liquidity_pool(trading_pair);
+		if pool_0.is_zero() && pool_1.is_zero() {
+			return true;
+		}
+
+		let (rate_0, rate_1) = Self::get_stable_swap_rates(trading_pair.0, trading_pair.1);
+		match (
+			Self::normalize_stable_swap_amount(U256::from(pool_0), rate_0),
+			Self::normalize_stable_swap_amount(U256::from(pool_1), rate_1),
+		) {
+			(Some(pool_0), Some(pool_1)) => {
+				let larger = pool_0.max(pool_1);
+				let difference = larger.saturating_sub(pool_0.min(pool_1));
+				difference.saturating_mul(U256::from(10_000u32))
+					<= larger.saturating_mul(U256::from(POOL_TYPE_SWITCH_IMBALANCE_LIMIT))
+			}
+			_ => false,
+		}
+	}
+
+	/// Get how much target amount will be got for specific supply amount in
+	/// the StableSwap pool, the pools are normalized by the rate multipliers
+	/// `rates` of (supply currency, target currency).
+	fn get_stable_swap_target_amount(
+		supply_pool: Balance,
+		target_pool: Balance,
+		supply_amount: Balance,
+		fee_rate: (u32, u32),
+		amplification: u128,
+		rates: (ExchangeRate, ExchangeRate),
+	) -> Balance {
+		if supply_amount.is_zero() || supply_pool.is_zero() || target_pool.is_zero() {
+			return Zero::zero();
+		}
+
+		let (fee_numerator, fee_denominator) = fee_rate;
+		let (supply_rate, target_rate) = rates;
+		let supply_amount_with_fee = U256::from(supply_amount)
+			.saturating_mul(U256::from(fee_denominator.saturating_sub(fee_numerator)))
+			.checked_div(U256::from(fee_denominator))
+			.unwrap_or_default();
+
+		let supply_pool = Self::normalize_stable_swap_amount(U256::from(supply_pool), supply_rate).unwrap_or_default();
+		let target_pool = Self::normalize_stable_swap_amount(U256::from(target_pool), target_rate).unwrap_or_default();
+		let supply_amount_with_fee =
+			Self::normalize_stable_swap_amount(supply_amount_with_fee, supply_rate).unwrap_or_default();
+
+		Self::get_stable_swap_invariant(supply_pool, target_pool, amplification)
+			.and_then(|invariant| {
+				Self::get_stable_swap_pool(
+					supply_pool.checked_add(supply_amount_with_fee)?,
+					invariant,
+					amplification,
+				)
+			})
+			// sub 1 from result so that correct the possible losses caused by remainder discarding
+			.and_then(|new_target_pool| target_pool.checked_sub(new_target_pool)?.checked_sub(U256::one()))
+			.and_then(|n| Self::denormalize_stable_swap_amount(n, target_rate, false))
+			.and_then(|n| TryInto::<Balance>::try_into(n).ok())
+			.unwrap_or_else(Zero::zero)
+	}
+
+	/// Get how much supply amount will be paid for specific target amount in
+	/// the StableSwap pool, the pools are normalized by the rate multipliers
+	/// `rates` of (supply currency, target currency).
+	fn get_stable_swap_supply_amount(
+		supply_pool: Balance,
+		target_pool: Balance,
+		target_amount: Balance,
+		fee_rate: (u32, u32),
+		amplification: u128,
+		rates: (ExchangeRate, ExchangeRate),
+	) -> Balance {
+		if target_amount.is_zero() || supply_pool.is_zero() || target_amount >= target_pool {
+			return Zero::zero();
+		}
+
+		let (fee_numerator, fee_denominator) = fee_rate;
+		let (supply_rate, target_rate) = rates;
+		let new_target_pool =
+			Self::normalize_stable_swap_amount(U256::from(target_pool.saturating_sub(target_amount)), target_rate)
+				.unwrap_or_default();
+		let supply_pool = Self::normalize_stable_swap_amount(U256::from(supply_pool), supply_rate).unwrap_or_default();
+		let target_pool = Self::normalize_stable_swap_amount(U256::from(target_pool), target_rate).unwrap_or_default();
+
+		Self::get_stable_swap_invariant(supply_pool, target_pool, amplification)
+			.and_then(|invariant| Self::get_stable_swap_pool(new_target_pool, invariant, amplification))
+			// add 1 to result so that correct the possible losses caused by remainder discarding
+			.and_then(|new_supply_pool| new_supply_pool.checked_sub(supply_pool)?.checked_add(U256::one()))
+			.and_then(|n| Self::denormalize_stable_swap_amount(n, supply_rate, true))
+			.and_then(|n| {
+				n.checked_mul(U256::from(fee_denominator))?
+					.checked_div(U256::from(fee_denominator.saturating_sub(fee_numerator)))?
+					.checked_add(U256::one())
+			})
+			.and_then(|n| TryInto::<Balance>::try_into(n).ok())
+			.unwrap_or_else(Zero::zero)
+	}
+
+	pub fn get_target_amounts(
 		path: &[CurrencyId],
 		supply_amount: Balance,
 		price_impact_limit: Option<Ratio>,
@@ -411,16 +2024,27 @@ impl<T: Trait> Module<T> {
 
 		let mut i: usize = 0;
 		while i + 1 < path_length {
-			ensure!(
-				T::EnabledTradingPairs::get().contains(&TradingPair::new(path[i], path[i + 1])),
-				Error::<T>::TradingPairNotAllowed
-			);
+			let trading_pair = TradingPair::new(path[i], path[i + 1]);
+			ensure!(Self::is_enabled(trading_pair), Error::<T>::TradingPairNotAllowed);
 			let (supply_pool, target_pool) = Self::get_liquidity(path[i], path[i + 1]);
 			ensure!(
 				!supply_pool.is_zero() && !target_pool.is_zero(),
 				Error::<T>::InsufficientLiquidity
 			);
-			let target_amount = Self::get_target_amount(supply_pool, target_pool, target_amounts[i]);
+			let fee_rate = Self::get_exchange_fee(trading_pair);
+			let target_amount = match Self::pool_types(trading_pair) {
+				PoolType::ConstantProduct => {
+					Self::get_target_amount(supply_pool, target_pool, target_amounts[i], fee_rate)
+				}
+				PoolType::StableSwap(amplification) => Self::get_stable_swap_target_amount(
+					supply_pool,
+					target_pool,
+					target_amounts[i],
+					fee_rate,
+					amplification,
+					Self::get_stable_swap_rates(path[i], path[i + 1]),
+				),
+			};
 			ensure!(!target_amount.is_zero(), Error::<T>::ZeroTargetAmount);
 
 			// check price impact if limit exists
@@ -436,7 +2060,7 @@ impl<T: Trait> Module<T> {
 		Ok(target_amounts)
 	}
 
-	fn get_supply_amounts(
+	pub fn get_supply_amounts(
 		path: &[CurrencyId],
 		target_amount: Balance,
 		price_impact_limit: Option<Ratio>,
@@ -451,16 +2075,27 @@ impl<T: Trait> Module<T> {
 
 		let mut i: usize = path_length - 1;
 		while i > 0 {
-			ensure!(
-				T::EnabledTradingPairs::get().contains(&TradingPair::new(path[i - 1], path[i])),
-				Error::<T>::TradingPairNotAllowed
-			);
+			let trading_pair = TradingPair::new(path[i - 1], path[i]);
+			ensure!(Self::is_enabled(trading_pair), Error::<T>::TradingPairNotAllowed);
 			let (supply_pool, target_pool) = Self::get_liquidity(path[i - 1], path[i]);
 			ensure!(
 				!supply_pool.is_zero() && !target_pool.is_zero(),
 				Error::<T>::InsufficientLiquidity
 			);
-			let supply_amount = Self::get_supply_amount(supply_pool, target_pool, supply_amounts[i]);
+			let fee_rate = Self::get_exchange_fee(trading_pair);
+			let supply_amount = match Self::pool_types(trading_pair) {
+				PoolType::ConstantProduct => {
+					Self::get_supply_amount(supply_pool, target_pool, supply_amounts[i], fee_rate)
+				}
+				PoolType::StableSwap(amplification) => Self::get_stable_swap_supply_amount(
+					supply_pool,
+					target_pool,
+					supply_amounts[i],
+					fee_rate,
+					amplification,
+					Self::get_stable_swap_rates(path[i - 1], path[i]),
+				),
+			};
 			ensure!(!supply_amount.is_zero(), Error::<T>::ZeroSupplyAmount);
 
 			// check price impact if limit exists
@@ -477,6 +2112,258 @@ impl<T: Trait> Module<T> {
 		Ok(supply_amounts)
 	}
 
+	/// Get the trading paths from `supply_currency_id` to
+	/// `target_currency_id` through enabled trading pairs, the length of path
+	/// is limited by `TradingPathLimit` and the number of paths is limited by
+	/// `TradingPathCandidatesLimit`. Shorter paths are in front, currencies
+	/// can not be repeated in the path.
+	fn get_trading_paths(supply_currency_id: CurrencyId, target_currency_id: CurrencyId) -> Vec<Vec<CurrencyId>> {
+		let mut paths: Vec<Vec<CurrencyId>> = vec![];
+		if supply_currency_id == target_currency_id {
+			return paths;
+		}
+
+		let trading_pairs = Self::enabled_trading_pairs();
+		let candidates_limit = T::TradingPathCandidatesLimit::get();
+
+		// breadth-first search, so the search stops with the shortest paths
+		// when the candidates reach the limit
+		let mut partial_paths: Vec<Vec<CurrencyId>> = vec![vec![supply_currency_id]];
+		while !partial_paths.is_empty() {
+			let mut next_partial_paths: Vec<Vec<CurrencyId>> = vec![];
+			for path in partial_paths {
+				let last_currency_id = path[path.len() - 1];
+				for trading_pair in &trading_pairs {
+					let next_currency_id = if trading_pair.0 == last_currency_id {
+						trading_pair.1
+					} else if trading_pair.1 == last_currency_id {
+						trading_pair.0
+					} else {
+						continue;
+					};
+					if path.contains(&next_currency_id) {
+						continue;
+					}
+
+					let mut next_path = path.clone();
+					next_path.push(next_currency_id);
+					if next_currency_id == target_currency_id {
+						paths.push(next_path);
+						if paths.len() >= candidates_limit {
+							return paths;
+						}
+					} else if next_path.len() < T::TradingPathLimit::get() {
+						next_partial_paths.push(next_path);
+					}
+				}
+			}
+			partial_paths = next_partial_paths;
+		}
+
+		paths
+	}
+
+	/// The weight upper bound of finding the best trading path, which reads
+	/// the enabled trading pairs once and the pool, the pool type and the
+	/// exchange fee of every hop of the candidate paths.
+	pub fn get_best_path_weight() -> Weight {
+		let hops = T::TradingPathCandidatesLimit::get().saturating_mul(T::TradingPathLimit::get().saturating_sub(1));
+		T::DbWeight::get().reads((hops as Weight).saturating_mul(3).saturating_add(1))
+	}
+
+	/// Get the trading path which gives the most target amount for specific
+	/// supply amount.
+	pub fn get_best_path_with_exact_supply(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		supply_amount: Balance,
+		price_impact_limit: Option<Ratio>,
+	) -> Option<Vec<CurrencyId>> {
+		let mut best: Option<(Vec<CurrencyId>, Balance)> = None;
+		for path in Self::get_trading_paths(supply_currency_id, target_currency_id) {
+			if let Ok(amounts) = Self::get_target_amounts(&path, supply_amount, price_impact_limit) {
+				let target_amount = amounts[amounts.len() - 1];
+				if best
+					.as_ref()
+					.map_or(true, |(_, best_target_amount)| target_amount > *best_target_amount)
+				{
+					best = Some((path, target_amount));
+				}
+			}
+		}
+
+		best.map(|(path, _)| path)
+	}
+
+	/// Get the trading path which costs the least supply amount for specific
+	/// target amount.
+	pub fn get_best_path_with_exact_target(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		target_amount: Balance,
+		price_impact_limit: Option<Ratio>,
+	) -> Option<Vec<CurrencyId>> {
+		let mut best: Option<(Vec<CurrencyId>, Balance)> = None;
+		for path in Self::get_trading_paths(supply_currency_id, target_currency_id) {
+			if let Ok(amounts) = Self::get_supply_amounts(&path, target_amount, price_impact_limit) {
+				let supply_amount = amounts[0];
+				if best
+					.as_ref()
+					.map_or(true, |(_, best_supply_amount)| supply_amount < *best_supply_amount)
+				{
+					best = Some((path, supply_amount));
+				}
+			}
+		}
+
+		best.map(|(path, _)| path)
+	}
+
+	/// Get the price impact of every hop in the trading path, the price impact
+	/// of a hop is the proportion of target pool that will be swapped out.
+	pub fn get_price_impacts(path: &[CurrencyId], amounts: &[Balance]) -> Vec<Ratio> {
+		path.windows(2)
+			.zip(amounts.iter().skip(1))
+			.map(|(pair, target_amount)| {
+				let (_, target_pool) = Self::get_liquidity(pair[0], pair[1]);
+				Ratio::checked_from_rational(*target_amount, target_pool).unwrap_or_else(Ratio::zero)
+			})
+			.collect()
+	}
+
+	/// Get the spot prices of (currency 0 in currency 1, currency 1 in
+	/// currency 0) of the trading pair by its pool type.
+	fn get_spot_prices(trading_pair: TradingPair) -> Option<(Price, Price)> {
+		let (pool_0, pool_1) = Self::liquidity_pool(trading_pair);
+		if pool_0.is_zero() || pool_1.is_zero() {
+			return None;
+		}
+
+		match Self::pool_types(trading_pair) {
+			PoolType::ConstantProduct => Some((
+				Price::checked_from_rational(pool_1, pool_0)?,
+				Price::checked_from_rational(pool_0, pool_1)?,
+			)),
+			PoolType::StableSwap(amplification) => Self::get_stable_swap_spot_prices(
+				pool_0,
+				pool_1,
+				amplification,
+				Self::get_stable_swap_rates(trading_pair.0, trading_pair.1),
+			),
+		}
+	}
+
+	/// Get the marginal prices of (pool 0 in pool 1, pool 1 in pool 0) of the
+	/// StableSwap pool, the pools are normalized by the rate multipliers
+	/// `rates`. With x and y normalized by the invariant D, the marginal price
+	/// of x in y is (4 * Ann * x^2 * y^2 + y) / (4 * Ann * x^2 * y^2 + x).
+	fn get_stable_swap_spot_prices(
+		pool_0: Balance,
+		pool_1: Balance,
+		amplification: u128,
+		rates: (ExchangeRate, ExchangeRate),
+	) -> Option<(Price, Price)> {
+		let pool_0 = Self::normalize_stable_swap_amount(U256::from(pool_0), rates.0)?;
+		let pool_1 = Self::normalize_stable_swap_amount(U256::from(pool_1), rates.1)?;
+		let invariant =
+			TryInto::<Balance>::try_into(Self::get_stable_swap_invariant(pool_0, pool_1, amplification)?).ok()?;
+		let x = Price::checked_from_rational(TryInto::<Balance>::try_into(pool_0).ok()?, invariant)?;
+		let y = Price::checked_from_rational(TryInto::<Balance>::try_into(pool_1).ok()?, invariant)?;
+
+		let weight = Price::saturating_from_integer(amplification.checked_mul(16)?)
+			.saturating_mul(x)
+			.saturating_mul(x)
+			.saturating_mul(y)
+			.saturating_mul(y);
+		let numerator = weight.saturating_add(y);
+		let denominator = weight.saturating_add(x);
+		Some((
+			numerator
+				.checked_div(&denominator)?
+				.checked_mul(&rates.0)?
+				.checked_div(&rates.1)?,
+			denominator
+				.checked_div(&numerator)?
+				.checked_mul(&rates.1)?
+				.checked_div(&rates.0)?,
+		))
+	}
+
+	/// Get the cumulative prices of the trading pair at current block, the
+	/// prices since the last update are accumulated with current spot prices.
+	fn get_current_cumulative_price(trading_pair: TradingPair) -> Option<CumulativePrices<T::BlockNumber>> {
+		let now = <system::Module<T>>::block_number();
+		Self::cumulative_price(trading_pair).map(|cumulative_price| {
+			let elapsed: u128 = now
+				.saturating_sub(cumulative_price.block_number)
+				.unique_saturated_into();
+			let (price_0, price_1) = match Self::get_spot_prices(trading_pair) {
+				Some(spot_prices) if !elapsed.is_zero() => spot_prices,
+				_ => {
+					return CumulativePrices {
+						block_number: now,
+						..cumulative_price
+					}
+				}
+			};
+
+			let elapsed = Price::saturating_from_integer(elapsed);
+			CumulativePrices {
+				price_0: cumulative_price.price_0.saturating_add(price_0.saturating_mul(elapsed)),
+				price_1: cumulative_price.price_1.saturating_add(price_1.saturating_mul(elapsed)),
+				block_number: now,
+			}
+		})
+	}
+
+	/// Accumulate the prices of the trading pair with the liquidity before it
+	/// changes, and make a new TWAP checkpoint if the newer one is older than
+	/// `TWAPWindow`.
+	fn update_cumulative_price(trading_pair: TradingPair) {
+		let now = <system::Module<T>>::block_number();
+		let cumulative_price = Self::get_current_cumulative_price(trading_pair).unwrap_or(CumulativePrices {
+			price_0: Zero::zero(),
+			price_1: Zero::zero(),
+			block_number: now,
+		});
+		CumulativePrice::<T>::insert(trading_pair, cumulative_price);
+
+		TWAPCheckpoints::<T>::mutate(trading_pair, |(older, newer)| match newer {
+			Some(checkpoint) if now.saturating_sub(checkpoint.block_number) < T::TWAPWindow::get() => {}
+			_ => {
+				*older = newer.take();
+				*newer = Some(cumulative_price);
+			}
+		});
+	}
+
+	/// Get the time-weighted average price of currency A in currency B, it's
+	/// averaged over at least `TWAPWindow` blocks. Returns `None` if there's
+	/// not enough price history.
+	pub fn get_twap(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> Option<Price> {
+		let trading_pair = TradingPair::new(currency_id_a, currency_id_b);
+		let current = Self::get_current_cumulative_price(trading_pair)?;
+		let (older, newer) = Self::twap_checkpoints(trading_pair);
+		let window = T::TWAPWindow::get();
+		let checkpoint = match newer {
+			Some(newer) if current.block_number.saturating_sub(newer.block_number) >= window => newer,
+			_ => older?,
+		};
+
+		let elapsed: u128 = current
+			.block_number
+			.saturating_sub(checkpoint.block_number)
+			.unique_saturated_into();
+		let (current_price, checkpoint_price) = if currency_id_a == trading_pair.0 {
+			(current.price_0, checkpoint.price_0)
+		} else {
+			(current.price_1, checkpoint.price_1)
+		};
+		current_price
+			.saturating_sub(checkpoint_price)
+			.checked_div(&Price::saturating_from_integer(elapsed))
+	}
+
 	fn _swap(
 		supply_currency_id: CurrencyId,
 		target_currency_id: CurrencyId,
@@ -484,6 +2371,7 @@ impl<T: Trait> Module<T> {
 		target_decrement: Balance,
 	) {
 		let trading_pair = TradingPair::new(supply_currency_id, target_currency_id);
+		Self::update_cumulative_price(trading_pair);
 		LiquidityPool::mutate(trading_pair, |(pool_0, pool_1)| {
 			if supply_currency_id == trading_pair.0 {
 				*pool_0 = pool_0.saturating_add(supply_increment);
@@ -495,19 +2383,48 @@ impl<T: Trait> Module<T> {
 		});
 	}
 
-	fn _swap_by_path(path: &[CurrencyId], amounts: &[Balance]) {
+	fn _swap_by_path(path: &[CurrencyId], amounts: &[Balance]) -> DispatchResult {
+		let protocol_fee_share = Self::protocol_fee_share().unwrap_or_else(Ratio::zero);
+		let module_account_id = Self::account_id();
 		let mut i: usize = 0;
 		while i + 1 < path.len() {
 			let (supply_currency_id, target_currency_id) = (path[i], path[i + 1]);
-			let (supply_increment, target_decrement) = (amounts[i], amounts[i + 1]);
+			let (supply_amount, target_decrement) = (amounts[i], amounts[i + 1]);
+			Self::ensure_not_lent(TradingPair::new(supply_currency_id, target_currency_id))?;
+
+			// the protocol fee share of the swap fee is taken out of the supply amount
+			// before it's added to the pool.
+			let (fee_numerator, fee_denominator) =
+				Self::get_exchange_fee(TradingPair::new(supply_currency_id, target_currency_id));
+			let fee_amount = Ratio::checked_from_rational(fee_numerator, fee_denominator)
+				.unwrap_or_else(Ratio::zero)
+				.saturating_mul_int(supply_amount);
+			let protocol_fee_amount = protocol_fee_share.saturating_mul_int(fee_amount);
+			if !protocol_fee_amount.is_zero() {
+				T::Currency::transfer(
+					supply_currency_id,
+					&module_account_id,
+					&T::ProtocolFeeReceiver::get(),
+					protocol_fee_amount,
+				)?;
+			}
+
 			Self::_swap(
 				supply_currency_id,
 				target_currency_id,
-				supply_increment,
+				supply_amount.saturating_sub(protocol_fee_amount),
 				target_decrement,
 			);
+			Self::deposit_event(RawEvent::SwapFee(
+				supply_currency_id,
+				target_currency_id,
+				fee_amount,
+				protocol_fee_amount,
+			));
 			i += 1;
 		}
+
+		Ok(())
 	}
 
 	fn do_swap_with_exact_supply(
@@ -527,7 +2444,7 @@ impl<T: Trait> Module<T> {
 			let actual_target_amount = amounts[amounts.len() - 1];
 
 			T::Currency::transfer(path[0], who, &module_account_id, supply_amount)?;
-			Self::_swap_by_path(&path, &amounts);
+			Self::_swap_by_path(&path, &amounts)?;
 			T::Currency::transfer(path[path.len() - 1], &module_account_id, who, actual_target_amount)?;
 
 			Self::deposit_event(RawEvent::Swap(
@@ -554,7 +2471,7 @@ impl<T: Trait> Module<T> {
 			let actual_supply_amount = amounts[0];
 
 			T::Currency::transfer(path[0], who, &module_account_id, actual_supply_amount)?;
-			Self::_swap_by_path(&path, &amounts);
+			Self::_swap_by_path(&path, &amounts)?;
 			T::Currency::transfer(path[path.len() - 1], &module_account_id, who, target_amount)?;
 
 			Self::deposit_event(RawEvent::Swap(
@@ -593,6 +2510,38 @@ impl<T: Trait> DEXManager<T::AccountId, CurrencyId, Balance> for Module<T> {
 			.map(|amounts| amounts[0])
 	}
 
+	fn get_best_path_with_exact_supply(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		supply_amount: Balance,
+		price_impact_limit: Option<Ratio>,
+	) -> Option<Vec<CurrencyId>> {
+		Self::get_best_path_with_exact_supply(
+			supply_currency_id,
+			target_currency_id,
+			supply_amount,
+			price_impact_limit,
+		)
+	}
+
+	fn get_best_path_with_exact_target(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		target_amount: Balance,
+		price_impact_limit: Option<Ratio>,
+	) -> Option<Vec<CurrencyId>> {
+		Self::get_best_path_with_exact_target(
+			supply_currency_id,
+			target_currency_id,
+			target_amount,
+			price_impact_limit,
+		)
+	}
+
+	fn get_best_path_weight() -> Weight {
+		Self::get_best_path_weight()
+	}
+
 	fn swap_with_exact_supply(
 		who: &T::AccountId,
 		path: &[CurrencyId],
@@ -613,3 +2562,77 @@ impl<T: Trait> DEXManager<T::AccountId, CurrencyId, Balance> for Module<T> {
 		Self::do_swap_with_exact_target(who, path, target_amount, max_supply_amount, gas_price_limit)
 	}
 }
+
+#[allow(deprecated)]
+impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
+	type Call = Call<T>;
+
+	fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+		match call {
+			Call::execute_limit_order(order_id) => {
+				match Self::limit_orders(order_id) {
+					Some(order) if !Self::is_limit_order_expired(&order) && Self::is_limit_order_executable(&order) => {
+					}
+					_ => return InvalidTransaction::Stale.into(),
+				}
+
+				ValidTransaction::with_tag_prefix("DexOffchainWorker")
+					.priority(T::UnsignedPriority::get())
+					.and_provides(order_id)
+					.longevity(64_u64)
+					.propagate(true)
+					.build()
+			}
+			Call::expire_limit_order(order_id) => {
+				match Self::limit_orders(order_id) {
+					Some(order) if Self::is_limit_order_expired(&order) => {}
+					_ => return InvalidTransaction::Stale.into(),
+				}
+
+				ValidTransaction::with_tag_prefix("DexOffchainWorker")
+					.priority(T::UnsignedPriority::get())
+					.and_provides(order_id)
+					.longevity(64_u64)
+					.propagate(true)
+					.build()
+			}
+			_ => InvalidTransaction::Call.into(),
+		}
+	}
+}
+
+/// The `PriceProvider` which provides the TWAP of the liquidity pools in DEX,
+/// the prices in USD are derived from the TWAP in stable currency.
+pub struct TWAPPriceProvider<T, GetStableCurrencyId, StableCurrencyFixedPrice>(
+	PhantomData<(T, GetStableCurrencyId, StableCurrencyFixedPrice)>,
+);
+
+impl<T, GetStableCurrencyId, StableCurrencyFixedPrice> PriceProvider<CurrencyId>
+	for TWAPPriceProvider<T, GetStableCurrencyId, StableCurrencyFixedPrice>
+where
+	T: Trait,
+	GetStableCurrencyId: Get<CurrencyId>,
+	StableCurrencyFixedPrice: Get<Price>,
+{
+	fn get_relative_price(base_currency_id: CurrencyId, quote_currency_id: CurrencyId) -> Option<Price> {
+		Module::<T>::get_twap(base_currency_id, quote_currency_id).or_else(|| {
+			let base_price = Self::get_price(base_currency_id)?;
+			let quote_price = Self::get_price(quote_currency_id)?;
+			base_price.checked_div(&quote_price)
+		})
+	}
+
+	fn get_price(currency_id: CurrencyId) -> Option<Price> {
+		let stable_currency_id = GetStableCurrencyId::get();
+		if currency_id == stable_currency_id {
+			Some(StableCurrencyFixedPrice::get())
+		} else {
+			Module::<T>::get_twap(currency_id, stable_currency_id)
+				.and_then(|price| price.checked_mul(&StableCurrencyFixedPrice::get()))
+		}
+	}
+
+	fn lock_price(_currency_id: CurrencyId) {}
+
+	fn unlock_price(_currency_id: CurrencyId) {}
+}
@@ -3,17 +3,25 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{impl_outer_event, impl_outer_origin, parameter_types};
+use frame_support::{
+	impl_outer_dispatch, impl_outer_event, impl_outer_origin, ord_parameter_types, parameter_types, traits::Filter,
+};
+use frame_system::{offchain::SendTransactionTypes, EnsureSignedBy};
 use orml_traits::MultiReservableCurrency;
 use primitives::{Amount, TokenSymbol};
 use sp_core::H256;
-use sp_runtime::{testing::Header, traits::IdentityLookup, Perbill};
+use sp_runtime::{
+	testing::{Header, TestXt},
+	traits::IdentityLookup,
+	Perbill,
+};
 
 pub type BlockNumber = u64;
 pub type AccountId = u128;
 
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
+pub const CAROL: AccountId = 3;
 pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
 pub const XBTC: CurrencyId = CurrencyId::Token(TokenSymbol::XBTC);
 pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
@@ -41,6 +49,13 @@ impl_outer_origin! {
 	pub enum Origin for Runtime {}
 }
 
+impl_outer_dispatch! {
+	pub enum Call for Runtime where origin: Origin {
+		dex::DexModule,
+		orml_tokens::Tokens,
+	}
+}
+
 parameter_types! {
 	pub const BlockHashCount: BlockNumber = 250;
 	pub const MaximumBlockWeight: u32 = 1024;
@@ -52,7 +67,7 @@ impl frame_system::Trait for Runtime {
 	type Origin = Origin;
 	type Index = u64;
 	type BlockNumber = BlockNumber;
-	type Call = ();
+	type Call = Call;
 	type Hash = H256;
 	type Hashing = ::sp_runtime::traits::BlakeTwo256;
 	type AccountId = AccountId;
@@ -97,29 +112,92 @@ impl DEXIncentives<AccountId, CurrencyId, Balance> for MockDEXIncentives {
 		let _ = Tokens::unreserve(lp_currency_id, who, amount);
 		Ok(())
 	}
+
+	fn get_staked_dex_share(who: &AccountId, lp_currency_id: CurrencyId) -> Balance {
+		Tokens::reserved_balance(lp_currency_id, who)
+	}
 }
 
 parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (1, 100);
 	pub const TradingPathLimit: usize = 3;
-	pub EnabledTradingPairs : Vec<TradingPair> = vec![AUSD_DOT_PAIR, AUSD_XBTC_PAIR, DOT_XBTC_PAIR];
+	pub const TradingPathCandidatesLimit: usize = 2;
+	pub const TWAPWindow: BlockNumber = 10;
+	pub const GetNativeCurrencyId: CurrencyId = ACA;
+	pub const LimitOrderDeposit: Balance = 100;
+	pub const MaxLimitOrdersPerAccount: u32 = 2;
+	pub const UnsignedPriority: u64 = 1 << 20;
 	pub const DEXModuleId: ModuleId = ModuleId(*b"aca/dexm");
+	pub const ProtocolFeeReceiver: AccountId = 4;
+	pub LegacyEnabledTradingPairs: Vec<TradingPair> = vec![AUSD_DOT_PAIR, AUSD_XBTC_PAIR, DOT_XBTC_PAIR];
+}
+
+ord_parameter_types! {
+	pub const ListingOrigin: AccountId = 3;
+}
+
+pub struct MockStableSwapRateProvider;
+impl StableSwapRateProvider for MockStableSwapRateProvider {
+	fn get_rate_multiplier(_trading_pair: TradingPair, currency_id: CurrencyId) -> ExchangeRate {
+		if currency_id == XBTC {
+			ExchangeRate::saturating_from_integer(2)
+		} else {
+			ExchangeRate::one()
+		}
+	}
+}
+
+pub struct MockFlashSwapCallFilter;
+impl Filter<Call> for MockFlashSwapCallFilter {
+	fn filter(call: &Call) -> bool {
+		!matches!(call, Call::DexModule(..))
+	}
 }
 
 impl Trait for Runtime {
 	type Event = TestEvent;
 	type Currency = Tokens;
-	type EnabledTradingPairs = EnabledTradingPairs;
 	type GetExchangeFee = GetExchangeFee;
 	type TradingPathLimit = TradingPathLimit;
+	type TradingPathCandidatesLimit = TradingPathCandidatesLimit;
 	type ModuleId = DEXModuleId;
 	type WeightInfo = ();
 	type DEXIncentives = MockDEXIncentives;
+	type UpdateOrigin = EnsureSignedBy<ListingOrigin, AccountId>;
+	type ProtocolFeeReceiver = ProtocolFeeReceiver;
+	type TWAPWindow = TWAPWindow;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type LimitOrderDeposit = LimitOrderDeposit;
+	type MaxLimitOrdersPerAccount = MaxLimitOrdersPerAccount;
+	type UnsignedPriority = UnsignedPriority;
+	type Call = Call;
+	type LegacyEnabledTradingPairs = LegacyEnabledTradingPairs;
+	type StableSwapRateProvider = MockStableSwapRateProvider;
+	type FlashSwapCallFilter = MockFlashSwapCallFilter;
 }
 pub type DexModule = Module<Runtime>;
 
+/// An extrinsic type used for tests.
+pub type Extrinsic = TestXt<Call, ()>;
+
+impl<LocalCall> SendTransactionTypes<LocalCall> for Runtime
+where
+	Call: From<LocalCall>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = Extrinsic;
+}
+
+parameter_types! {
+	pub const GetStableCurrencyId: CurrencyId = AUSD;
+	pub StableCurrencyFixedPrice: Price = Price::one();
+}
+
+pub type TWAPPrices = TWAPPriceProvider<Runtime, GetStableCurrencyId, StableCurrencyFixedPrice>;
+
 pub struct ExtBuilder {
 	endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>,
+	initial_enabled_trading_pairs: Vec<TradingPair>,
 }
 
 impl Default for ExtBuilder {
@@ -132,12 +210,20 @@ impl Default for ExtBuilder {
 				(BOB, XBTC, 1_000_000_000_000_000_000u128),
 				(ALICE, DOT, 1_000_000_000_000_000_000u128),
 				(BOB, DOT, 1_000_000_000_000_000_000u128),
+				(ALICE, ACA, 1_000_000_000_000_000_000u128),
+				(BOB, ACA, 1_000_000_000_000_000_000u128),
 			],
+			initial_enabled_trading_pairs: vec![AUSD_DOT_PAIR, AUSD_XBTC_PAIR, DOT_XBTC_PAIR],
 		}
 	}
 }
 
 impl ExtBuilder {
+	pub fn initial_enabled_trading_pairs(mut self, initial_enabled_trading_pairs: Vec<TradingPair>) -> Self {
+		self.initial_enabled_trading_pairs = initial_enabled_trading_pairs;
+		self
+	}
+
 	pub fn build(self) -> sp_io::TestExternalities {
 		let mut t = frame_system::GenesisConfig::default()
 			.build_storage::<Runtime>()
@@ -149,6 +235,12 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		GenesisConfig {
+			initial_enabled_trading_pairs: self.initial_enabled_trading_pairs,
+		}
+		.assimilate_storage::<Runtime>(&mut t)
+		.unwrap();
+
 		t.into()
 	}
 }
@@ -3,12 +3,446 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::OnRuntimeUpgrade};
 use mock::{
-	DexModule, ExtBuilder, Origin, Runtime, System, TestEvent, Tokens, ACA, ALICE, AUSD, AUSD_DOT_PAIR, AUSD_XBTC_PAIR,
-	BOB, DOT, XBTC,
+	AccountId, Call as MockCall, DexModule, ExtBuilder, Extrinsic, Origin, ProtocolFeeReceiver, Runtime, System,
+	TWAPPrices, TestEvent, Tokens, ACA, ALICE, AUSD, AUSD_DOT_PAIR, AUSD_XBTC_PAIR, BOB, CAROL, DOT, DOT_XBTC_PAIR,
+	XBTC,
 };
 use orml_traits::MultiReservableCurrency;
+use sp_core::offchain::{testing, OffchainExt, TransactionPoolExt};
+use sp_runtime::{offchain::storage::StorageValueRef, traits::BadOrigin};
+
+#[test]
+fn enable_trading_pair_work() {
+	ExtBuilder::default()
+		.initial_enabled_trading_pairs(vec![])
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_noop!(
+				DexModule::enable_trading_pair(Origin::signed(ALICE), AUSD, DOT),
+				BadOrigin
+			);
+			assert_noop!(
+				DexModule::enable_trading_pair(Origin::signed(CAROL), AUSD, AUSD),
+				Error::<Runtime>::InvalidCurrencyId
+			);
+
+			assert_eq!(
+				DexModule::trading_pair_statuses(AUSD_DOT_PAIR),
+				TradingPairStatus::<_, _>::NotEnabled
+			);
+			assert_ok!(DexModule::enable_trading_pair(Origin::signed(CAROL), DOT, AUSD));
+			assert_eq!(
+				DexModule::trading_pair_statuses(AUSD_DOT_PAIR),
+				TradingPairStatus::<_, _>::Enabled
+			);
+			assert_eq!(DexModule::enabled_trading_pairs(), vec![AUSD_DOT_PAIR]);
+			let enable_trading_pair_event = TestEvent::dex(RawEvent::EnableTradingPair(AUSD_DOT_PAIR));
+			assert!(System::events()
+				.iter()
+				.any(|record| record.event == enable_trading_pair_event));
+
+			assert_noop!(
+				DexModule::enable_trading_pair(Origin::signed(CAROL), AUSD, DOT),
+				Error::<Runtime>::MustBeNotEnabled
+			);
+		});
+}
+
+#[test]
+fn disable_trading_pair_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			DexModule::disable_trading_pair(Origin::signed(ALICE), AUSD, DOT),
+			BadOrigin
+		);
+		assert_noop!(
+			DexModule::disable_trading_pair(Origin::signed(CAROL), ACA, AUSD),
+			Error::<Runtime>::MustBeEnabled
+		);
+
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			500_000,
+			100_000,
+			false
+		));
+		assert_eq!(
+			DexModule::enabled_trading_pairs(),
+			vec![AUSD_DOT_PAIR, AUSD_XBTC_PAIR, DOT_XBTC_PAIR]
+		);
+		assert_ok!(DexModule::disable_trading_pair(Origin::signed(CAROL), DOT, AUSD));
+		assert_eq!(
+			DexModule::trading_pair_statuses(AUSD_DOT_PAIR),
+			TradingPairStatus::<_, _>::NotEnabled
+		);
+		assert_eq!(DexModule::enabled_trading_pairs(), vec![AUSD_XBTC_PAIR, DOT_XBTC_PAIR]);
+		let disable_trading_pair_event = TestEvent::dex(RawEvent::DisableTradingPair(AUSD_DOT_PAIR));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == disable_trading_pair_event));
+
+		// liquidity of the disabled trading pair can not be added or swapped, but can be removed
+		assert_noop!(
+			DexModule::add_liquidity(Origin::signed(ALICE), AUSD, DOT, 500_000, 100_000, false),
+			Error::<Runtime>::TradingPairNotAllowed
+		);
+		assert_noop!(
+			DexModule::swap_with_exact_supply(Origin::signed(BOB), vec![DOT, AUSD], 10_000, 0),
+			Error::<Runtime>::TradingPairNotAllowed
+		);
+		assert_ok!(DexModule::remove_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			500_000,
+			false
+		));
+	});
+}
+
+#[test]
+fn list_trading_pair_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			DexModule::list_trading_pair(Origin::signed(ALICE), ACA, AUSD, 1, 1, 100, 100, 10),
+			BadOrigin
+		);
+		assert_noop!(
+			DexModule::list_trading_pair(Origin::signed(CAROL), AUSD, AUSD, 1, 1, 100, 100, 10),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+		assert_noop!(
+			DexModule::list_trading_pair(Origin::signed(CAROL), AUSD, DOT, 1, 1, 100, 100, 10),
+			Error::<Runtime>::MustBeNotEnabled
+		);
+
+		// the trading pair with existing shares can not be listed
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			500_000,
+			100_000,
+			false
+		));
+		assert_ok!(DexModule::disable_trading_pair(Origin::signed(CAROL), AUSD, DOT));
+		assert_noop!(
+			DexModule::list_trading_pair(Origin::signed(CAROL), AUSD, DOT, 1, 1, 100, 100, 10),
+			Error::<Runtime>::NotAllowedList
+		);
+
+		let trading_pair = TradingPair::new(ACA, AUSD);
+		assert_ok!(DexModule::list_trading_pair(
+			Origin::signed(CAROL),
+			ACA,
+			AUSD,
+			1,
+			2,
+			100,
+			200,
+			10
+		));
+		let (min_contribution, target_provision) = if trading_pair.0 == ACA {
+			((1, 2), (100, 200))
+		} else {
+			((2, 1), (200, 100))
+		};
+		assert_eq!(
+			DexModule::trading_pair_statuses(trading_pair),
+			TradingPairStatus::<_, _>::Provisioning(ProvisioningParameters {
+				min_contribution,
+				target_provision,
+				accumulated_provision: (0, 0),
+				not_before: 10,
+			})
+		);
+		let list_trading_pair_event = TestEvent::dex(RawEvent::ListTradingPair(trading_pair));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == list_trading_pair_event));
+	});
+}
+
+#[test]
+fn add_provision_work() {
+	ExtBuilder::default()
+		.initial_enabled_trading_pairs(vec![])
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_noop!(
+				DexModule::add_provision(Origin::signed(ALICE), AUSD, DOT, 5_000, 1_000),
+				Error::<Runtime>::MustBeProvisioning
+			);
+
+			assert_ok!(DexModule::list_trading_pair(
+				Origin::signed(CAROL),
+				AUSD,
+				DOT,
+				5_000,
+				1_000,
+				1_000_000,
+				200_000,
+				10
+			));
+			assert_noop!(
+				DexModule::add_provision(Origin::signed(ALICE), AUSD, DOT, 4_999, 999),
+				Error::<Runtime>::InvalidContributionIncrement
+			);
+
+			assert_ok!(DexModule::add_provision(Origin::signed(ALICE), AUSD, DOT, 5_000, 0));
+			assert_ok!(DexModule::add_provision(Origin::signed(BOB), DOT, AUSD, 1_000, 100));
+			assert_eq!(DexModule::provisioning_pool(AUSD_DOT_PAIR, ALICE), (5_000, 0));
+			assert_eq!(DexModule::provisioning_pool(AUSD_DOT_PAIR, BOB), (100, 1_000));
+			assert_eq!(
+				DexModule::trading_pair_statuses(AUSD_DOT_PAIR),
+				TradingPairStatus::<_, _>::Provisioning(ProvisioningParameters {
+					min_contribution: (5_000, 1_000),
+					target_provision: (1_000_000, 200_000),
+					accumulated_provision: (5_100, 1_000),
+					not_before: 10,
+				})
+			);
+			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 5_100);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 1_000);
+			assert_eq!(Tokens::free_balance(AUSD, &ALICE), 999_999_999_999_995_000);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 999_999_999_999_999_000);
+			let add_provision_event = TestEvent::dex(RawEvent::AddProvision(BOB, AUSD, 100, DOT, 1_000));
+			assert!(System::events()
+				.iter()
+				.any(|record| record.event == add_provision_event));
+
+			// provision is not allowed to add liquidity
+			assert_noop!(
+				DexModule::add_liquidity(Origin::signed(ALICE), AUSD, DOT, 500_000, 100_000, false),
+				Error::<Runtime>::TradingPairNotAllowed
+			);
+		});
+}
+
+#[test]
+fn end_provisioning_work() {
+	ExtBuilder::default()
+		.initial_enabled_trading_pairs(vec![])
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_noop!(
+				DexModule::end_provisioning(Origin::signed(BOB), AUSD, DOT),
+				Error::<Runtime>::MustBeProvisioning
+			);
+
+			assert_ok!(DexModule::list_trading_pair(
+				Origin::signed(CAROL),
+				AUSD,
+				DOT,
+				5_000,
+				1_000,
+				1_000_000,
+				200_000,
+				10
+			));
+			assert_ok!(DexModule::add_provision(Origin::signed(ALICE), AUSD, DOT, 1_000_000, 0));
+			assert_noop!(
+				DexModule::end_provisioning(Origin::signed(BOB), AUSD, DOT),
+				Error::<Runtime>::UnqualifiedProvision
+			);
+			assert_ok!(DexModule::add_provision(Origin::signed(BOB), AUSD, DOT, 0, 200_000));
+
+			// can not end before `not_before`
+			assert_noop!(
+				DexModule::end_provisioning(Origin::signed(BOB), AUSD, DOT),
+				Error::<Runtime>::UnqualifiedProvision
+			);
+
+			System::set_block_number(10);
+			let lp_currency_id = AUSD_DOT_PAIR.get_dex_share_currency_id().unwrap();
+			assert_ok!(DexModule::end_provisioning(Origin::signed(BOB), AUSD, DOT));
+			assert_eq!(
+				DexModule::trading_pair_statuses(AUSD_DOT_PAIR),
+				TradingPairStatus::<_, _>::Enabled
+			);
+			assert_eq!(DexModule::enabled_trading_pairs(), vec![AUSD_DOT_PAIR]);
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (1_000_000, 200_000));
+			assert_eq!(
+				Tokens::free_balance(lp_currency_id, &DexModule::account_id()),
+				2_000_000
+			);
+			assert_eq!(Tokens::total_issuance(lp_currency_id), 2_000_000);
+			let provisioning_to_enabled_event = TestEvent::dex(RawEvent::ProvisioningToEnabled(
+				AUSD_DOT_PAIR,
+				1_000_000,
+				200_000,
+				2_000_000,
+			));
+			assert!(System::events()
+				.iter()
+				.any(|record| record.event == provisioning_to_enabled_event));
+
+			// contributors claim their shares
+			assert_noop!(
+				DexModule::claim_dex_share(Origin::signed(BOB), CAROL, AUSD, DOT),
+				Error::<Runtime>::NoProvision
+			);
+			assert_ok!(DexModule::claim_dex_share(Origin::signed(BOB), ALICE, AUSD, DOT));
+			assert_ok!(DexModule::claim_dex_share(Origin::signed(BOB), BOB, AUSD, DOT));
+			let claim_event = TestEvent::dex(RawEvent::ClaimDexShare(BOB, AUSD_DOT_PAIR, 1_000_000));
+			assert!(System::events().iter().any(|record| record.event == claim_event));
+			assert_eq!(DexModule::provisioning_pool(AUSD_DOT_PAIR, ALICE), (0, 0));
+			assert_eq!(DexModule::provisioning_pool(AUSD_DOT_PAIR, BOB), (0, 0));
+			assert_eq!(Tokens::free_balance(lp_currency_id, &ALICE), 1_000_000);
+			assert_eq!(Tokens::free_balance(lp_currency_id, &BOB), 1_000_000);
+			assert_eq!(Tokens::free_balance(lp_currency_id, &DexModule::account_id()), 0);
+			assert_noop!(
+				DexModule::claim_dex_share(Origin::signed(BOB), BOB, AUSD, DOT),
+				Error::<Runtime>::NoProvision
+			);
+			assert_noop!(
+				DexModule::refund_provision(Origin::signed(BOB), BOB, AUSD, DOT),
+				Error::<Runtime>::ProvisioningNotCancelled
+			);
+
+			// the shares of provision can be removed as liquidity
+			assert_ok!(DexModule::remove_liquidity(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				1_000_000,
+				false
+			));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (500_000, 100_000));
+		});
+}
+
+#[test]
+fn cancel_provisioning_work() {
+	ExtBuilder::default()
+		.initial_enabled_trading_pairs(vec![])
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_noop!(
+				DexModule::cancel_provisioning(Origin::signed(CAROL), AUSD, DOT),
+				Error::<Runtime>::MustBeProvisioning
+			);
+
+			assert_ok!(DexModule::list_trading_pair(
+				Origin::signed(CAROL),
+				AUSD,
+				DOT,
+				5_000,
+				1_000,
+				1_000_000,
+				200_000,
+				10
+			));
+			assert_ok!(DexModule::add_provision(Origin::signed(ALICE), AUSD, DOT, 1_000_000, 0));
+			assert_noop!(
+				DexModule::refund_provision(Origin::signed(BOB), ALICE, AUSD, DOT),
+				Error::<Runtime>::ProvisioningNotCancelled
+			);
+			assert_noop!(
+				DexModule::claim_dex_share(Origin::signed(BOB), ALICE, AUSD, DOT),
+				Error::<Runtime>::ProvisioningNotEnded
+			);
+			assert_noop!(
+				DexModule::cancel_provisioning(Origin::signed(ALICE), AUSD, DOT),
+				BadOrigin
+			);
+
+			assert_ok!(DexModule::cancel_provisioning(Origin::signed(CAROL), AUSD, DOT));
+			assert_eq!(
+				DexModule::trading_pair_statuses(AUSD_DOT_PAIR),
+				TradingPairStatus::<_, _>::NotEnabled
+			);
+			let cancel_event = TestEvent::dex(RawEvent::ProvisioningCancelled(AUSD_DOT_PAIR));
+			assert!(System::events().iter().any(|record| record.event == cancel_event));
+			assert_noop!(
+				DexModule::claim_dex_share(Origin::signed(BOB), ALICE, AUSD, DOT),
+				Error::<Runtime>::ProvisioningNotEnded
+			);
+
+			// can not list again before the provision is refunded
+			assert_noop!(
+				DexModule::list_trading_pair(Origin::signed(CAROL), AUSD, DOT, 5_000, 1_000, 1_000_000, 200_000, 10),
+				Error::<Runtime>::NotAllowedList
+			);
+
+			let alice_ausd = Tokens::free_balance(AUSD, &ALICE);
+			assert_ok!(DexModule::refund_provision(Origin::signed(BOB), ALICE, AUSD, DOT));
+			assert_eq!(Tokens::free_balance(AUSD, &ALICE), alice_ausd + 1_000_000);
+			assert_eq!(DexModule::provisioning_pool(AUSD_DOT_PAIR, ALICE), (0, 0));
+			let refund_event = TestEvent::dex(RawEvent::RefundProvision(ALICE, AUSD, 1_000_000, DOT, 0));
+			assert!(System::events().iter().any(|record| record.event == refund_event));
+			assert_noop!(
+				DexModule::refund_provision(Origin::signed(BOB), ALICE, AUSD, DOT),
+				Error::<Runtime>::NoProvision
+			);
+
+			assert_ok!(DexModule::list_trading_pair(
+				Origin::signed(CAROL),
+				AUSD,
+				DOT,
+				5_000,
+				1_000,
+				1_000_000,
+				200_000,
+				10
+			));
+		});
+}
+
+#[test]
+fn on_runtime_upgrade_work() {
+	ExtBuilder::default()
+		.initial_enabled_trading_pairs(vec![])
+		.build()
+		.execute_with(|| {
+			let provisioning_parameters = ProvisioningParameters {
+				min_contribution: (1, 1),
+				target_provision: (100, 100),
+				accumulated_provision: (0, 0),
+				not_before: 10,
+			};
+			TradingPairStatuses::<Runtime>::insert(
+				DOT_XBTC_PAIR,
+				TradingPairStatus::Provisioning(provisioning_parameters),
+			);
+			TradingPairsMigrated::put(false);
+
+			// legacy trading pairs are enabled even if their pools are empty
+			DexModule::on_runtime_upgrade();
+			assert_eq!(
+				DexModule::trading_pair_statuses(AUSD_DOT_PAIR),
+				TradingPairStatus::<_, _>::Enabled
+			);
+			assert_eq!(
+				DexModule::trading_pair_statuses(AUSD_XBTC_PAIR),
+				TradingPairStatus::<_, _>::Enabled
+			);
+			assert_eq!(
+				DexModule::trading_pair_statuses(DOT_XBTC_PAIR),
+				TradingPairStatus::<_, _>::Provisioning(provisioning_parameters)
+			);
+			assert!(TradingPairsMigrated::get());
+			assert_eq!(DexModule::enabled_trading_pairs(), vec![AUSD_DOT_PAIR, AUSD_XBTC_PAIR]);
+
+			// only migrate once
+			TradingPairStatuses::<Runtime>::insert(AUSD_DOT_PAIR, TradingPairStatus::NotEnabled);
+			assert_eq!(DexModule::on_runtime_upgrade(), 0);
+			assert_eq!(
+				DexModule::trading_pair_statuses(AUSD_DOT_PAIR),
+				TradingPairStatus::<_, _>::NotEnabled
+			);
+		});
+}
 
 #[test]
 fn get_liquidity_work() {
@@ -23,26 +457,26 @@ fn get_liquidity_work() {
 #[test]
 fn get_target_amount_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_eq!(DexModule::get_target_amount(10000, 0, 1000), 0);
-		assert_eq!(DexModule::get_target_amount(0, 20000, 1000), 0);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 0), 0);
-		assert_eq!(DexModule::get_target_amount(10000, 1, 1000000), 0);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 10000), 9949);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 1000), 1801);
+		assert_eq!(DexModule::get_target_amount(10000, 0, 1000, (1, 100)), 0);
+		assert_eq!(DexModule::get_target_amount(0, 20000, 1000, (1, 100)), 0);
+		assert_eq!(DexModule::get_target_amount(10000, 20000, 0, (1, 100)), 0);
+		assert_eq!(DexModule::get_target_amount(10000, 1, 1000000, (1, 100)), 0);
+		assert_eq!(DexModule::get_target_amount(10000, 20000, 10000, (1, 100)), 9949);
+		assert_eq!(DexModule::get_target_amount(10000, 20000, 1000, (1, 100)), 1801);
 	});
 }
 
 #[test]
 fn get_supply_amount_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_eq!(DexModule::get_supply_amount(10000, 0, 1000), 0);
-		assert_eq!(DexModule::get_supply_amount(0, 20000, 1000), 0);
-		assert_eq!(DexModule::get_supply_amount(10000, 20000, 0), 0);
-		assert_eq!(DexModule::get_supply_amount(10000, 1, 1), 0);
-		assert_eq!(DexModule::get_supply_amount(10000, 20000, 9949), 9999);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 9999), 9949);
-		assert_eq!(DexModule::get_supply_amount(10000, 20000, 1801), 1000);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 1000), 1801);
+		assert_eq!(DexModule::get_supply_amount(10000, 0, 1000, (1, 100)), 0);
+		assert_eq!(DexModule::get_supply_amount(0, 20000, 1000, (1, 100)), 0);
+		assert_eq!(DexModule::get_supply_amount(10000, 20000, 0, (1, 100)), 0);
+		assert_eq!(DexModule::get_supply_amount(10000, 1, 1, (1, 100)), 0);
+		assert_eq!(DexModule::get_supply_amount(10000, 20000, 9949, (1, 100)), 9999);
+		assert_eq!(DexModule::get_target_amount(10000, 20000, 9999, (1, 100)), 9949);
+		assert_eq!(DexModule::get_supply_amount(10000, 20000, 1801, (1, 100)), 1000);
+		assert_eq!(DexModule::get_target_amount(10000, 20000, 1000, (1, 100)), 1801);
 	});
 }
 
@@ -101,7 +535,8 @@ fn calculate_amount_for_big_number_work() {
 			DexModule::get_supply_amount(
 				171_000_000_000_000_000_000_000,
 				56_000_000_000_000_000_000_000,
-				1_000_000_000_000_000_000_000
+				1_000_000_000_000_000_000_000,
+				(1, 100)
 			),
 			3_140_495_867_768_595_041_323
 		);
@@ -109,7 +544,8 @@ fn calculate_amount_for_big_number_work() {
 			DexModule::get_target_amount(
 				171_000_000_000_000_000_000_000,
 				56_000_000_000_000_000_000_000,
-				3_140_495_867_768_595_041_323
+				3_140_495_867_768_595_041_323,
+				(1, 100)
 			),
 			1_000_000_000_000_000_000_000
 		);
@@ -156,6 +592,98 @@ fn get_supply_amounts_work() {
 	});
 }
 
+#[test]
+fn get_price_impacts_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		LiquidityPool::insert(AUSD_DOT_PAIR, (50000, 10000));
+		LiquidityPool::insert(AUSD_XBTC_PAIR, (100000, 10));
+
+		assert_eq!(
+			DexModule::get_price_impacts(&vec![DOT, AUSD], &vec![10000, 24874]),
+			vec![Ratio::saturating_from_rational(24874, 50000)]
+		);
+		assert_eq!(
+			DexModule::get_price_impacts(&vec![DOT, AUSD, XBTC], &vec![10000, 24874, 1]),
+			vec![
+				Ratio::saturating_from_rational(24874, 50000),
+				Ratio::saturating_from_rational(1, 10)
+			]
+		);
+		assert_eq!(
+			DexModule::get_price_impacts(&vec![DOT, XBTC], &vec![100, 1]),
+			vec![Ratio::zero()]
+		);
+	});
+}
+
+#[test]
+fn get_trading_paths_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			DexModule::get_trading_paths(DOT, XBTC),
+			vec![vec![DOT, XBTC], vec![DOT, AUSD, XBTC]]
+		);
+		assert_eq!(
+			DexModule::get_trading_paths(AUSD, DOT),
+			vec![vec![AUSD, DOT], vec![AUSD, XBTC, DOT]]
+		);
+		assert_eq!(DexModule::get_trading_paths(DOT, DOT), Vec::<Vec<CurrencyId>>::new());
+		assert_eq!(DexModule::get_trading_paths(DOT, ACA), Vec::<Vec<CurrencyId>>::new());
+
+		// the candidates are limited by `TradingPathCandidatesLimit`, the shortest are kept
+		DexModule::set_enabled(TradingPair::new(DOT, ACA));
+		DexModule::set_enabled(TradingPair::new(ACA, XBTC));
+		let paths = DexModule::get_trading_paths(DOT, XBTC);
+		assert_eq!(paths.len(), 2);
+		assert_eq!(paths[0], vec![DOT, XBTC]);
+		assert_eq!(paths[1].len(), 3);
+	});
+}
+
+#[test]
+fn get_best_path_with_exact_supply_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		LiquidityPool::insert(AUSD_DOT_PAIR, (500_000, 100_000));
+		LiquidityPool::insert(AUSD_XBTC_PAIR, (1_000_000, 100_000));
+		LiquidityPool::insert(DOT_XBTC_PAIR, (100_000, 1_000));
+		assert_eq!(
+			DexModule::get_best_path_with_exact_supply(DOT, XBTC, 10_000, None),
+			Some(vec![DOT, AUSD, XBTC])
+		);
+		assert_eq!(
+			DexModule::get_best_path_with_exact_supply(DOT, XBTC, 10_000, Ratio::checked_from_rational(5, 100)),
+			None
+		);
+		assert_eq!(DexModule::get_best_path_with_exact_supply(DOT, ACA, 10_000, None), None);
+
+		LiquidityPool::insert(DOT_XBTC_PAIR, (100_000, 50_000));
+		assert_eq!(
+			DexModule::get_best_path_with_exact_supply(DOT, XBTC, 10_000, None),
+			Some(vec![DOT, XBTC])
+		);
+	});
+}
+
+#[test]
+fn get_best_path_with_exact_target_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		LiquidityPool::insert(AUSD_DOT_PAIR, (500_000, 100_000));
+		LiquidityPool::insert(AUSD_XBTC_PAIR, (1_000_000, 100_000));
+		LiquidityPool::insert(DOT_XBTC_PAIR, (100_000, 1_000));
+		assert_eq!(
+			DexModule::get_best_path_with_exact_target(DOT, XBTC, 500, None),
+			Some(vec![DOT, AUSD, XBTC])
+		);
+		assert_eq!(DexModule::get_best_path_with_exact_target(DOT, ACA, 500, None), None);
+
+		LiquidityPool::insert(DOT_XBTC_PAIR, (100_000, 50_000));
+		assert_eq!(
+			DexModule::get_best_path_with_exact_target(DOT, XBTC, 500, None),
+			Some(vec![DOT, XBTC])
+		);
+	});
+}
+
 #[test]
 fn _swap_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -698,3 +1226,1219 @@ fn do_swap_with_exact_target_work() {
 		assert_eq!(Tokens::free_balance(XBTC, &BOB), 1_000_000_005_000_000_000);
 	});
 }
+
+#[test]
+fn swap_with_exact_supply_by_best_path_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			500_000,
+			100_000,
+			false
+		));
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			XBTC,
+			1_000_000,
+			100_000,
+			false
+		));
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			DOT,
+			XBTC,
+			100_000,
+			1_000,
+			false
+		));
+
+		assert_noop!(
+			DexModule::swap_with_exact_supply_by_best_path(Origin::signed(BOB), DOT, ACA, 10_000, 0),
+			Error::<Runtime>::NoAvailableTradingPath
+		);
+		assert_noop!(
+			DexModule::swap_with_exact_supply_by_best_path(Origin::signed(BOB), DOT, XBTC, 10_000, 5_000),
+			Error::<Runtime>::InsufficientTargetAmount
+		);
+
+		assert_ok!(DexModule::swap_with_exact_supply_by_best_path(
+			Origin::signed(BOB),
+			DOT,
+			XBTC,
+			10_000,
+			4_000
+		));
+		let swap_event = TestEvent::dex(RawEvent::Swap(BOB, vec![DOT, AUSD, XBTC], 10_000, 4_268));
+		assert!(System::events().iter().any(|record| record.event == swap_event));
+		assert_eq!(DexModule::get_liquidity(AUSD, DOT), (454_960, 110_000));
+		assert_eq!(DexModule::get_liquidity(AUSD, XBTC), (1_045_040, 95_732));
+		assert_eq!(DexModule::get_liquidity(DOT, XBTC), (100_000, 1_000));
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 999_999_999_999_990_000);
+		assert_eq!(Tokens::free_balance(XBTC, &BOB), 1_000_000_000_000_004_268);
+	});
+}
+
+#[test]
+fn swap_with_exact_target_by_best_path_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			500_000,
+			100_000,
+			false
+		));
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			XBTC,
+			1_000_000,
+			100_000,
+			false
+		));
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			DOT,
+			XBTC,
+			100_000,
+			1_000,
+			false
+		));
+
+		assert_noop!(
+			DexModule::swap_with_exact_target_by_best_path(Origin::signed(BOB), DOT, ACA, 500, 2_000),
+			Error::<Runtime>::NoAvailableTradingPath
+		);
+		assert_noop!(
+			DexModule::swap_with_exact_target_by_best_path(Origin::signed(BOB), DOT, XBTC, 500, 1_000),
+			Error::<Runtime>::ExcessiveSupplyAmount
+		);
+
+		assert_ok!(DexModule::swap_with_exact_target_by_best_path(
+			Origin::signed(BOB),
+			DOT,
+			XBTC,
+			500,
+			2_000
+		));
+		let swap_event = TestEvent::dex(RawEvent::Swap(BOB, vec![DOT, AUSD, XBTC], 1_036, 500));
+		assert!(System::events().iter().any(|record| record.event == swap_event));
+		assert_eq!(DexModule::get_liquidity(AUSD, DOT), (494_924, 101_036));
+		assert_eq!(DexModule::get_liquidity(AUSD, XBTC), (1_005_076, 99_500));
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 999_999_999_999_998_964);
+		assert_eq!(Tokens::free_balance(XBTC, &BOB), 1_000_000_000_000_000_500);
+	});
+}
+
+#[test]
+fn set_exchange_fee_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			DexModule::set_exchange_fee(Origin::signed(ALICE), AUSD, DOT, Some((5, 100))),
+			BadOrigin
+		);
+		assert_noop!(
+			DexModule::set_exchange_fee(Origin::signed(CAROL), AUSD, AUSD, Some((5, 100))),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+		assert_noop!(
+			DexModule::set_exchange_fee(Origin::signed(CAROL), AUSD, DOT, Some((100, 100))),
+			Error::<Runtime>::InvalidExchangeFee
+		);
+
+		assert_eq!(DexModule::get_exchange_fee(AUSD_DOT_PAIR), (1, 100));
+		assert_ok!(DexModule::set_exchange_fee(
+			Origin::signed(CAROL),
+			DOT,
+			AUSD,
+			Some((5, 100))
+		));
+		assert_eq!(DexModule::exchange_fees(AUSD_DOT_PAIR), Some((5, 100)));
+		assert_eq!(DexModule::get_exchange_fee(AUSD_DOT_PAIR), (5, 100));
+		assert_eq!(DexModule::get_exchange_fee(AUSD_XBTC_PAIR), (1, 100));
+		let exchange_fee_updated_event = TestEvent::dex(RawEvent::ExchangeFeeUpdated(AUSD_DOT_PAIR, Some((5, 100))));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == exchange_fee_updated_event));
+
+		// swap amounts are calculated by the fee rate of the trading pair
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			500_000,
+			100_000,
+			false
+		));
+		assert_eq!(
+			DexModule::get_target_amounts(&vec![DOT, AUSD], 10_000, None),
+			Ok(vec![10_000, 43_378])
+		);
+
+		assert_ok!(DexModule::set_exchange_fee(Origin::signed(CAROL), AUSD, DOT, None));
+		assert_eq!(DexModule::exchange_fees(AUSD_DOT_PAIR), None);
+		assert_eq!(DexModule::get_exchange_fee(AUSD_DOT_PAIR), (1, 100));
+		assert_eq!(
+			DexModule::get_target_amounts(&vec![DOT, AUSD], 10_000, None),
+			Ok(vec![10_000, 45_040])
+		);
+	});
+}
+
+#[test]
+fn set_protocol_fee_share_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			DexModule::set_protocol_fee_share(Origin::signed(ALICE), Some(Ratio::saturating_from_rational(1, 2))),
+			BadOrigin
+		);
+		assert_noop!(
+			DexModule::set_protocol_fee_share(Origin::signed(CAROL), Some(Ratio::saturating_from_rational(3, 2))),
+			Error::<Runtime>::InvalidProtocolFeeShare
+		);
+
+		assert_eq!(DexModule::protocol_fee_share(), None);
+		assert_ok!(DexModule::set_protocol_fee_share(
+			Origin::signed(CAROL),
+			Some(Ratio::saturating_from_rational(1, 2))
+		));
+		assert_eq!(
+			DexModule::protocol_fee_share(),
+			Some(Ratio::saturating_from_rational(1, 2))
+		);
+		let protocol_fee_share_updated_event = TestEvent::dex(RawEvent::ProtocolFeeShareUpdated(Some(
+			Ratio::saturating_from_rational(1, 2),
+		)));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == protocol_fee_share_updated_event));
+
+		assert_ok!(DexModule::set_protocol_fee_share(Origin::signed(CAROL), None));
+		assert_eq!(DexModule::protocol_fee_share(), None);
+	});
+}
+
+#[test]
+fn swap_with_protocol_fee_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			500_000,
+			100_000,
+			false
+		));
+
+		// all the swap fee stays in the pool without protocol fee share
+		assert_ok!(DexModule::swap_with_exact_supply(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			10_000,
+			0
+		));
+		let swap_fee_event = TestEvent::dex(RawEvent::SwapFee(DOT, AUSD, 100, 0));
+		assert!(System::events().iter().any(|record| record.event == swap_fee_event));
+		assert_eq!(DexModule::get_liquidity(AUSD, DOT), (454_960, 110_000));
+		assert_eq!(Tokens::free_balance(DOT, &ProtocolFeeReceiver::get()), 0);
+
+		assert_ok!(DexModule::set_protocol_fee_share(
+			Origin::signed(CAROL),
+			Some(Ratio::saturating_from_rational(1, 2))
+		));
+		assert_ok!(DexModule::swap_with_exact_supply(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			10_000,
+			0
+		));
+		let swap_fee_event = TestEvent::dex(RawEvent::SwapFee(DOT, AUSD, 100, 50));
+		assert!(System::events().iter().any(|record| record.event == swap_fee_event));
+		assert_eq!(DexModule::get_liquidity(AUSD, DOT), (417_395, 119_950));
+		assert_eq!(Tokens::free_balance(DOT, &ProtocolFeeReceiver::get()), 50);
+		assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 119_950);
+	});
+}
+
+#[test]
+fn twap_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(DexModule::get_twap(DOT, AUSD), None);
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			500_000,
+			100_000,
+			false
+		));
+		assert_eq!(
+			DexModule::cumulative_price(AUSD_DOT_PAIR),
+			Some(CumulativePrices {
+				price_0: Price::zero(),
+				price_1: Price::zero(),
+				block_number: 1,
+			})
+		);
+		// not enough price history
+		assert_eq!(DexModule::get_twap(DOT, AUSD), None);
+
+		System::set_block_number(11);
+		assert_eq!(DexModule::get_twap(DOT, AUSD), Some(Price::saturating_from_integer(5)));
+		assert_eq!(
+			DexModule::get_twap(AUSD, DOT),
+			Some(Price::saturating_from_rational(1, 5))
+		);
+
+		// the prices are accumulated with the liquidity before swap
+		assert_ok!(DexModule::swap_with_exact_supply(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			10_000,
+			0
+		));
+		assert_eq!(DexModule::get_liquidity(AUSD, DOT), (454_960, 110_000));
+		assert_eq!(
+			DexModule::cumulative_price(AUSD_DOT_PAIR),
+			Some(CumulativePrices {
+				price_0: Price::saturating_from_integer(2),
+				price_1: Price::saturating_from_integer(50),
+				block_number: 11,
+			})
+		);
+		assert_eq!(DexModule::get_twap(DOT, AUSD), Some(Price::saturating_from_integer(5)));
+
+		// the newer checkpoint is not old enough, the older one is used
+		System::set_block_number(16);
+		assert_eq!(
+			DexModule::get_twap(DOT, AUSD),
+			Some(Price::saturating_from_rational(4_712, 1_000))
+		);
+		assert_eq!(
+			TWAPPrices::get_price(DOT),
+			Some(Price::saturating_from_rational(4_712, 1_000))
+		);
+		assert_eq!(TWAPPrices::get_price(AUSD), Some(Price::one()));
+		assert_eq!(
+			TWAPPrices::get_relative_price(DOT, AUSD),
+			Some(Price::saturating_from_rational(4_712, 1_000))
+		);
+		assert_eq!(TWAPPrices::get_price(XBTC), None);
+		assert_eq!(TWAPPrices::get_relative_price(DOT, XBTC), None);
+	});
+}
+
+#[test]
+fn get_stable_swap_spot_prices_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			DexModule::get_stable_swap_spot_prices(1_000, 1_000, 100, (ExchangeRate::one(), ExchangeRate::one())),
+			Some((Price::one(), Price::one()))
+		);
+		assert_eq!(
+			DexModule::get_stable_swap_spot_prices(
+				2_000,
+				1_000,
+				100,
+				(ExchangeRate::one(), ExchangeRate::saturating_from_integer(2))
+			),
+			Some((Price::saturating_from_rational(1, 2), Price::saturating_from_integer(2)))
+		);
+
+		// the price of the imbalanced pool is much closer to the peg than the reserve ratio
+		let (price_0, price_1) =
+			DexModule::get_stable_swap_spot_prices(1_000, 3_000, 100, (ExchangeRate::one(), ExchangeRate::one()))
+				.unwrap();
+		assert!(price_0 > Price::one() && price_0 < Price::saturating_from_rational(101, 100));
+		assert!(price_1 < Price::one() && price_1 > Price::saturating_from_rational(99, 100));
+	});
+}
+
+#[test]
+fn twap_of_stable_swap_pool_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			100_000,
+			100_000,
+			false
+		));
+		assert_ok!(DexModule::set_pool_type(
+			Origin::signed(CAROL),
+			AUSD,
+			DOT,
+			PoolType::StableSwap(100)
+		));
+		assert_ok!(DexModule::swap_with_exact_supply(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			50_000,
+			0
+		));
+
+		// the TWAP follows the marginal price of the StableSwap pool rather than the reserve ratio
+		System::set_block_number(11);
+		let (pool_0, pool_1) = DexModule::liquidity_pool(AUSD_DOT_PAIR);
+		let twap = DexModule::get_twap(DOT, AUSD).unwrap();
+		assert!(twap < Price::one());
+		assert!(twap > Price::checked_from_rational(pool_0, pool_1).unwrap());
+	});
+}
+
+#[test]
+fn place_limit_order_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			DexModule::place_limit_order(Origin::signed(BOB), vec![DOT], 10_000, 45_000, 10),
+			Error::<Runtime>::InvalidTradingPathLength
+		);
+		assert_noop!(
+			DexModule::place_limit_order(Origin::signed(BOB), vec![DOT, ACA], 10_000, 45_000, 10),
+			Error::<Runtime>::TradingPairNotAllowed
+		);
+		assert_noop!(
+			DexModule::place_limit_order(Origin::signed(BOB), vec![DOT, AUSD], 0, 45_000, 10),
+			Error::<Runtime>::ZeroSupplyAmount
+		);
+		assert_noop!(
+			DexModule::place_limit_order(Origin::signed(BOB), vec![DOT, AUSD], 10_000, 45_000, 1),
+			Error::<Runtime>::InvalidExpiry
+		);
+
+		assert_eq!(DexModule::next_order_id(), 0);
+		assert_eq!(Tokens::reserved_balance(DOT, &BOB), 0);
+		assert_ok!(DexModule::place_limit_order(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			10_000,
+			45_000,
+			10
+		));
+		assert_eq!(DexModule::next_order_id(), 1);
+		assert_eq!(
+			DexModule::limit_orders(0),
+			Some(LimitOrder {
+				owner: BOB,
+				path: vec![DOT, AUSD],
+				supply_amount: 10_000,
+				min_target_amount: 45_000,
+				expiry: 10,
+				deposit: 100,
+			})
+		);
+		assert_eq!(Tokens::reserved_balance(DOT, &BOB), 10_000);
+		assert_eq!(Tokens::reserved_balance(ACA, &BOB), 100);
+		assert_eq!(DexModule::limit_order_counts(&BOB), 1);
+
+		let limit_order_placed_event =
+			TestEvent::dex(RawEvent::LimitOrderPlaced(0, BOB, vec![DOT, AUSD], 10_000, 45_000, 10));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == limit_order_placed_event));
+
+		// the number of pending orders of an account is limited
+		assert_ok!(DexModule::place_limit_order(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			10_000,
+			45_000,
+			10
+		));
+		assert_noop!(
+			DexModule::place_limit_order(Origin::signed(BOB), vec![DOT, AUSD], 10_000, 45_000, 10),
+			Error::<Runtime>::TooManyLimitOrders
+		);
+		assert_eq!(DexModule::limit_order_counts(&BOB), 2);
+
+		// the order can not be placed without the deposit
+		assert_ok!(Tokens::transfer(Origin::signed(ALICE), CAROL, AUSD, 10_000));
+		assert_noop!(
+			DexModule::place_limit_order(Origin::signed(CAROL), vec![AUSD, DOT], 10_000, 1, 10),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+	});
+}
+
+#[test]
+fn cancel_limit_order_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			DexModule::cancel_limit_order(Origin::signed(BOB), 0),
+			Error::<Runtime>::LimitOrderNotFound
+		);
+		assert_ok!(DexModule::place_limit_order(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			10_000,
+			45_000,
+			10
+		));
+		assert_noop!(
+			DexModule::cancel_limit_order(Origin::signed(ALICE), 0),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(DexModule::cancel_limit_order(Origin::signed(BOB), 0));
+		assert_eq!(DexModule::limit_orders(0), None);
+		assert_eq!(Tokens::reserved_balance(DOT, &BOB), 0);
+		assert_eq!(Tokens::reserved_balance(ACA, &BOB), 0);
+		assert_eq!(DexModule::limit_order_counts(&BOB), 0);
+
+		let limit_order_cancelled_event = TestEvent::dex(RawEvent::LimitOrderCancelled(0, BOB));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == limit_order_cancelled_event));
+	});
+}
+
+#[test]
+fn execute_limit_order_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			500_000,
+			100_000,
+			false
+		));
+		assert_ok!(DexModule::place_limit_order(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			10_000,
+			46_000,
+			10
+		));
+		assert_ok!(DexModule::place_limit_order(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			10_000,
+			45_000,
+			10
+		));
+		assert_eq!(Tokens::reserved_balance(DOT, &BOB), 20_000);
+		assert_eq!(Tokens::reserved_balance(ACA, &BOB), 200);
+
+		assert_noop!(DexModule::execute_limit_order(Origin::signed(BOB), 1), BadOrigin);
+		assert_noop!(
+			DexModule::execute_limit_order(Origin::none(), 2),
+			Error::<Runtime>::LimitOrderNotFound
+		);
+
+		// the target amount does not meet the limit
+		assert!(!DexModule::is_limit_order_executable(
+			&DexModule::limit_orders(0).unwrap()
+		));
+		assert_noop!(
+			DexModule::execute_limit_order(Origin::none(), 0),
+			Error::<Runtime>::InsufficientTargetAmount
+		);
+
+		assert!(DexModule::is_limit_order_executable(
+			&DexModule::limit_orders(1).unwrap()
+		));
+		let bob_ausd_before = Tokens::free_balance(AUSD, &BOB);
+		assert_ok!(DexModule::execute_limit_order(Origin::none(), 1));
+		assert_eq!(DexModule::limit_orders(1), None);
+		assert_eq!(Tokens::reserved_balance(DOT, &BOB), 10_000);
+		assert_eq!(Tokens::reserved_balance(ACA, &BOB), 100);
+		assert_eq!(DexModule::limit_order_counts(&BOB), 1);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), bob_ausd_before + 45_040);
+		assert_eq!(DexModule::get_liquidity(AUSD, DOT), (454_960, 110_000));
+
+		let limit_order_executed_event = TestEvent::dex(RawEvent::LimitOrderExecuted(1, BOB, 45_040));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == limit_order_executed_event));
+
+		System::set_block_number(11);
+		assert_noop!(
+			DexModule::execute_limit_order(Origin::none(), 0),
+			Error::<Runtime>::LimitOrderExpired
+		);
+	});
+}
+
+#[test]
+fn expire_limit_order_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DexModule::place_limit_order(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			10_000,
+			45_000,
+			10
+		));
+
+		assert_noop!(DexModule::expire_limit_order(Origin::signed(BOB), 0), BadOrigin);
+		System::set_block_number(10);
+		assert_noop!(
+			DexModule::expire_limit_order(Origin::none(), 0),
+			Error::<Runtime>::LimitOrderNotExpired
+		);
+
+		System::set_block_number(11);
+		assert_ok!(DexModule::expire_limit_order(Origin::none(), 0));
+		assert_eq!(DexModule::limit_orders(0), None);
+		assert_eq!(Tokens::reserved_balance(DOT, &BOB), 0);
+		assert_eq!(Tokens::reserved_balance(ACA, &BOB), 0);
+		assert_eq!(DexModule::limit_order_counts(&BOB), 0);
+
+		let limit_order_expired_event = TestEvent::dex(RawEvent::LimitOrderExpired(0, BOB));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == limit_order_expired_event));
+
+		assert_noop!(
+			DexModule::expire_limit_order(Origin::none(), 0),
+			Error::<Runtime>::LimitOrderNotFound
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_resumes_from_persisted_cursor() {
+	let (offchain, _) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let mut ext = ExtBuilder::default().build();
+	ext.register_extension(OffchainExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			500_000,
+			100_000,
+			false
+		));
+		assert_ok!(DexModule::place_limit_order(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			10_000,
+			0,
+			10
+		));
+		assert_ok!(DexModule::place_limit_order(
+			Origin::signed(ALICE),
+			vec![DOT, AUSD],
+			10_000,
+			0,
+			10
+		));
+		StorageValueRef::persistent(&OFFCHAIN_WORKER_MAX_ITERATIONS).set(&1u32);
+
+		// only one order is checked in a run, the rest is checked in the next run
+		assert_ok!(DexModule::_offchain_worker());
+		assert_eq!(pool_state.read().transactions.len(), 1);
+		assert!(StorageValueRef::persistent(&OFFCHAIN_WORKER_DATA)
+			.get::<Vec<u8>>()
+			.is_some());
+
+		StorageValueRef::persistent(&OFFCHAIN_WORKER_LOCK).clear();
+		assert_ok!(DexModule::_offchain_worker());
+		assert_eq!(pool_state.read().transactions.len(), 2);
+
+		let mut order_ids = pool_state
+			.read()
+			.transactions
+			.iter()
+			.map(|tx| match Extrinsic::decode(&mut &**tx).unwrap().call {
+				MockCall::DexModule(Call::execute_limit_order(order_id)) => order_id,
+				_ => panic!("unexpected call"),
+			})
+			.collect::<Vec<_>>();
+		order_ids.sort();
+		assert_eq!(order_ids, vec![0, 1]);
+	});
+}
+
+#[test]
+fn get_single_token_swap_amount_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(DexModule::get_single_token_swap_amount(0, 10_000, (1, 100)), 0);
+		assert_eq!(DexModule::get_single_token_swap_amount(100_000, 0, (1, 100)), 0);
+		assert_eq!(DexModule::get_single_token_swap_amount(100_000, 10_000, (1, 1)), 0);
+		assert_eq!(DexModule::get_single_token_swap_amount(100_000, 10_000, (0, 1)), 4_880);
+		assert_eq!(
+			DexModule::get_single_token_swap_amount(100_000, 10_000, (1, 100)),
+			4_905
+		);
+	});
+}
+
+#[test]
+fn add_liquidity_single_token_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			DexModule::add_liquidity_single_token(Origin::signed(BOB), DOT, ACA, 10_000, 0, false),
+			Error::<Runtime>::TradingPairNotAllowed
+		);
+		assert_noop!(
+			DexModule::add_liquidity_single_token(Origin::signed(BOB), DOT, AUSD, 10_000, 0, false),
+			Error::<Runtime>::InsufficientLiquidity
+		);
+
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			500_000,
+			100_000,
+			false
+		));
+		assert_noop!(
+			DexModule::add_liquidity_single_token(Origin::signed(BOB), DOT, AUSD, 10_000, 24_280, false),
+			Error::<Runtime>::InsufficientShareIncrement
+		);
+
+		let bob_dot_before = Tokens::free_balance(DOT, &BOB);
+		let bob_ausd_before = Tokens::free_balance(AUSD, &BOB);
+		assert_ok!(DexModule::add_liquidity_single_token(
+			Origin::signed(BOB),
+			DOT,
+			AUSD,
+			10_000,
+			24_279,
+			true
+		));
+		assert_eq!(DexModule::get_liquidity(AUSD, DOT), (500_000, 109_999));
+		assert_eq!(Tokens::free_balance(DOT, &BOB), bob_dot_before - 9_999);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), bob_ausd_before);
+		assert_eq!(
+			Tokens::free_balance(AUSD_DOT_PAIR.get_dex_share_currency_id().unwrap(), &BOB),
+			0
+		);
+		assert_eq!(
+			Tokens::reserved_balance(AUSD_DOT_PAIR.get_dex_share_currency_id().unwrap(), &BOB),
+			24_279
+		);
+
+		let swap_event = TestEvent::dex(RawEvent::Swap(BOB, vec![DOT, AUSD], 4_905, 23_155));
+		assert!(System::events().iter().any(|record| record.event == swap_event));
+		let add_liquidity_event = TestEvent::dex(RawEvent::AddLiquidity(BOB, AUSD, 23_155, DOT, 5_094, 24_279));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == add_liquidity_event));
+	});
+}
+
+#[test]
+fn flash_swap_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let transfer_call = |dest: AccountId, currency_id: CurrencyId, amount: Balance| -> Box<MockCall> {
+			Box::new(MockCall::Tokens(orml_tokens::Call::transfer(dest, currency_id, amount)))
+		};
+		let swap_call = Box::new(MockCall::DexModule(Call::swap_with_exact_supply(
+			vec![AUSD, DOT],
+			10_000,
+			0,
+		)));
+
+		assert_noop!(
+			DexModule::flash_swap(
+				Origin::signed(BOB),
+				AUSD,
+				ACA,
+				10_000,
+				transfer_call(CAROL, AUSD, 10_000)
+			),
+			Error::<Runtime>::TradingPairNotAllowed
+		);
+		assert_noop!(
+			DexModule::flash_swap(Origin::signed(BOB), AUSD, DOT, 0, transfer_call(CAROL, AUSD, 10_000)),
+			Error::<Runtime>::ZeroFlashSwapAmount
+		);
+		assert_noop!(
+			DexModule::flash_swap(Origin::signed(BOB), AUSD, DOT, 10_000, swap_call),
+			Error::<Runtime>::FlashSwapCallNotAllowed
+		);
+		assert_noop!(
+			DexModule::flash_swap(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				10_000,
+				transfer_call(CAROL, AUSD, 10_000)
+			),
+			Error::<Runtime>::InsufficientLiquidity
+		);
+
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			500_000,
+			100_000,
+			false
+		));
+
+		// the failure of the dispatched call reverts the flash swap
+		assert_noop!(
+			DexModule::flash_swap(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				10_000,
+				transfer_call(CAROL, AUSD, Balance::max_value())
+			),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+
+		// the borrower can not repay the borrowed amount plus fee
+		assert_ok!(Tokens::transfer(Origin::signed(BOB), CAROL, AUSD, 10_000));
+		assert_noop!(
+			DexModule::flash_swap(
+				Origin::signed(CAROL),
+				AUSD,
+				DOT,
+				10_000,
+				transfer_call(BOB, AUSD, 10_000)
+			),
+			orml_tokens::Error::<Runtime>::BalanceTooLow
+		);
+
+		let bob_ausd_before = Tokens::free_balance(AUSD, &BOB);
+		assert_ok!(DexModule::flash_swap(
+			Origin::signed(BOB),
+			AUSD,
+			DOT,
+			10_000,
+			transfer_call(CAROL, AUSD, 10_000)
+		));
+		assert_eq!(DexModule::get_liquidity(AUSD, DOT), (500_100, 100_000));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), bob_ausd_before - 10_100);
+		assert_eq!(Tokens::free_balance(AUSD, &CAROL), 20_000);
+		assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 500_100);
+		assert_eq!(DexModule::flash_swap_locked_pair(), None);
+
+		let flash_swap_event = TestEvent::dex(RawEvent::FlashSwap(BOB, AUSD, 10_000, 100));
+		assert!(System::events().iter().any(|record| record.event == flash_swap_event));
+
+		// the fee is rounded up
+		assert_ok!(DexModule::flash_swap(
+			Origin::signed(BOB),
+			AUSD,
+			DOT,
+			99,
+			transfer_call(CAROL, AUSD, 99)
+		));
+		assert_eq!(DexModule::get_liquidity(AUSD, DOT), (500_101, 100_000));
+		let flash_swap_event = TestEvent::dex(RawEvent::FlashSwap(BOB, AUSD, 99, 1));
+		assert!(System::events().iter().any(|record| record.event == flash_swap_event));
+
+		// the protocol fee share of the flash swap fee is taken out
+		assert_ok!(DexModule::set_protocol_fee_share(
+			Origin::signed(CAROL),
+			Some(Ratio::saturating_from_rational(1, 2))
+		));
+		assert_ok!(DexModule::flash_swap(
+			Origin::signed(BOB),
+			AUSD,
+			DOT,
+			10_000,
+			transfer_call(CAROL, AUSD, 10_000)
+		));
+		assert_eq!(Tokens::free_balance(AUSD, &ProtocolFeeReceiver::get()), 50);
+		assert_eq!(DexModule::get_liquidity(AUSD, DOT), (500_151, 100_000));
+		assert_eq!(
+			Tokens::free_balance(AUSD, &DexModule::account_id()),
+			DexModule::get_liquidity(AUSD, DOT).0
+		);
+	});
+}
+
+#[test]
+fn flash_swap_locks_trading_pair() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			500_000,
+			100_000,
+			false
+		));
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			XBTC,
+			500_000,
+			100_000,
+			false
+		));
+
+		FlashSwapLockedPair::put(AUSD_DOT_PAIR);
+		assert_noop!(
+			DexModule::swap_with_exact_supply(Origin::signed(BOB), vec![AUSD, DOT], 10_000, 0),
+			Error::<Runtime>::FlashSwapInProgress
+		);
+		assert_noop!(
+			DexModule::add_liquidity(Origin::signed(BOB), AUSD, DOT, 5_000, 1_000, false),
+			Error::<Runtime>::FlashSwapInProgress
+		);
+		assert_noop!(
+			DexModule::remove_liquidity(Origin::signed(ALICE), AUSD, DOT, 1_000, false),
+			Error::<Runtime>::FlashSwapInProgress
+		);
+		assert_noop!(
+			DexModule::flash_swap(
+				Origin::signed(BOB),
+				AUSD,
+				XBTC,
+				10_000,
+				Box::new(MockCall::Tokens(orml_tokens::Call::transfer(CAROL, AUSD, 10_000)))
+			),
+			Error::<Runtime>::FlashSwapInProgress
+		);
+
+		// the other trading pairs are not locked
+		assert_ok!(DexModule::swap_with_exact_supply(
+			Origin::signed(BOB),
+			vec![AUSD, XBTC],
+			10_000,
+			0
+		));
+	});
+}
+
+#[test]
+fn set_pool_type_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			DexModule::set_pool_type(Origin::signed(ALICE), AUSD, DOT, PoolType::StableSwap(100)),
+			BadOrigin
+		);
+		assert_noop!(
+			DexModule::set_pool_type(Origin::signed(CAROL), AUSD, AUSD, PoolType::StableSwap(100)),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+		assert_noop!(
+			DexModule::set_pool_type(Origin::signed(CAROL), AUSD, DOT, PoolType::StableSwap(0)),
+			Error::<Runtime>::InvalidAmplification
+		);
+		assert_noop!(
+			DexModule::set_pool_type(
+				Origin::signed(CAROL),
+				AUSD,
+				DOT,
+				PoolType::StableSwap(MAX_AMPLIFICATION + 1)
+			),
+			Error::<Runtime>::InvalidAmplification
+		);
+
+		assert_eq!(DexModule::pool_types(AUSD_DOT_PAIR), PoolType::ConstantProduct);
+		assert_ok!(DexModule::set_pool_type(
+			Origin::signed(CAROL),
+			DOT,
+			AUSD,
+			PoolType::StableSwap(100)
+		));
+		assert_eq!(DexModule::pool_types(AUSD_DOT_PAIR), PoolType::StableSwap(100));
+
+		let pool_type_updated_event =
+			TestEvent::dex(RawEvent::PoolTypeUpdated(AUSD_DOT_PAIR, PoolType::StableSwap(100)));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == pool_type_updated_event));
+
+		assert_ok!(DexModule::set_pool_type(
+			Origin::signed(CAROL),
+			AUSD,
+			DOT,
+			PoolType::ConstantProduct
+		));
+		assert_eq!(DexModule::pool_types(AUSD_DOT_PAIR), PoolType::ConstantProduct);
+
+		// the pool type can not be switched when the normalized pools are not balanced
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			100_000,
+			50_000,
+			false
+		));
+		assert_noop!(
+			DexModule::set_pool_type(Origin::signed(CAROL), AUSD, DOT, PoolType::StableSwap(100)),
+			Error::<Runtime>::PoolNotBalanced
+		);
+
+		// the rate multiplier of XBTC is 2
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			XBTC,
+			100_000,
+			50_000,
+			false
+		));
+		assert_ok!(DexModule::set_pool_type(
+			Origin::signed(CAROL),
+			AUSD,
+			XBTC,
+			PoolType::StableSwap(100)
+		));
+		assert_eq!(
+			DexModule::get_swap_target_amount(&[AUSD, XBTC], 10_000, None),
+			Some(4_947)
+		);
+	});
+}
+
+#[test]
+fn get_stable_swap_amount_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			DexModule::get_stable_swap_invariant(U256::from(100_000), U256::from(100_000), 100),
+			Some(U256::from(200_000))
+		);
+		assert_eq!(
+			DexModule::get_stable_swap_invariant(U256::zero(), U256::zero(), 100),
+			Some(U256::zero())
+		);
+
+		assert_eq!(
+			DexModule::get_stable_swap_target_amount(
+				0,
+				100_000,
+				10_000,
+				(1, 100),
+				100,
+				(ExchangeRate::one(), ExchangeRate::one())
+			),
+			0
+		);
+		assert_eq!(
+			DexModule::get_stable_swap_target_amount(
+				100_000,
+				100_000,
+				0,
+				(1, 100),
+				100,
+				(ExchangeRate::one(), ExchangeRate::one())
+			),
+			0
+		);
+		assert_eq!(
+			DexModule::get_stable_swap_target_amount(
+				100_000,
+				100_000,
+				10_000,
+				(1, 100),
+				100,
+				(ExchangeRate::one(), ExchangeRate::one())
+			),
+			9_895
+		);
+		assert_eq!(
+			DexModule::get_stable_swap_target_amount(
+				100_000,
+				100_000,
+				10_000,
+				(0, 1),
+				100,
+				(ExchangeRate::one(), ExchangeRate::one())
+			),
+			9_994
+		);
+		// the lower amplification, the higher slippage
+		assert_eq!(
+			DexModule::get_stable_swap_target_amount(
+				100_000,
+				100_000,
+				10_000,
+				(0, 1),
+				1,
+				(ExchangeRate::one(), ExchangeRate::one())
+			),
+			9_676
+		);
+		// the slippage is still lower than constant product
+		assert_eq!(DexModule::get_target_amount(100_000, 100_000, 10_000, (0, 1)), 9_090);
+
+		assert_eq!(
+			DexModule::get_stable_swap_supply_amount(
+				100_000,
+				100_000,
+				0,
+				(1, 100),
+				100,
+				(ExchangeRate::one(), ExchangeRate::one())
+			),
+			0
+		);
+		assert_eq!(
+			DexModule::get_stable_swap_supply_amount(
+				100_000,
+				100_000,
+				100_000,
+				(1, 100),
+				100,
+				(ExchangeRate::one(), ExchangeRate::one())
+			),
+			0
+		);
+		assert_eq!(
+			DexModule::get_stable_swap_supply_amount(
+				100_000,
+				100_000,
+				9_895,
+				(1, 100),
+				100,
+				(ExchangeRate::one(), ExchangeRate::one())
+			),
+			10_001
+		);
+
+		// the pools are normalized by the rate multipliers
+		let rates = (ExchangeRate::one(), ExchangeRate::saturating_from_integer(2));
+		assert_eq!(
+			DexModule::get_stable_swap_target_amount(100_000, 50_000, 10_000, (0, 1), 100, rates),
+			4_997
+		);
+		assert_eq!(
+			DexModule::get_stable_swap_supply_amount(100_000, 50_000, 4_997, (0, 1), 100, rates),
+			10_001
+		);
+		let rates = (ExchangeRate::saturating_from_integer(2), ExchangeRate::one());
+		assert_eq!(
+			DexModule::get_stable_swap_target_amount(50_000, 100_000, 5_000, (0, 1), 100, rates),
+			9_994
+		);
+		assert_eq!(
+			DexModule::get_stable_swap_supply_amount(50_000, 100_000, 9_994, (0, 1), 100, rates),
+			5_001
+		);
+	});
+}
+
+#[test]
+fn swap_with_stable_swap_pool_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DexModule::set_pool_type(
+			Origin::signed(CAROL),
+			AUSD,
+			DOT,
+			PoolType::StableSwap(100)
+		));
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			100_000,
+			100_000,
+			false
+		));
+		assert_noop!(
+			DexModule::add_liquidity_single_token(Origin::signed(BOB), DOT, AUSD, 10_000, 0, false),
+			Error::<Runtime>::NotSupportedPoolType
+		);
+
+		assert_eq!(
+			DexModule::get_swap_target_amount(&[AUSD, DOT], 10_000, None),
+			Some(9_895)
+		);
+		assert_ok!(DexModule::swap_with_exact_supply(
+			Origin::signed(BOB),
+			vec![AUSD, DOT],
+			10_000,
+			9_895
+		));
+		assert_eq!(DexModule::get_liquidity(AUSD, DOT), (110_000, 90_105));
+		let swap_event = TestEvent::dex(RawEvent::Swap(BOB, vec![AUSD, DOT], 10_000, 9_895));
+		assert!(System::events().iter().any(|record| record.event == swap_event));
+
+		assert_eq!(
+			DexModule::get_swap_supply_amount(&[DOT, AUSD], 5_000, None),
+			Some(5_048)
+		);
+		assert_ok!(DexModule::swap_with_exact_target(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			5_000,
+			6_000
+		));
+		assert_eq!(DexModule::get_liquidity(AUSD, DOT), (105_000, 95_153));
+		let swap_event = TestEvent::dex(RawEvent::Swap(BOB, vec![DOT, AUSD], 5_048, 5_000));
+		assert!(System::events().iter().any(|record| record.event == swap_event));
+	});
+}
+
+#[test]
+fn transfer_share_and_get_liquidity_position_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let lp_currency_id = AUSD_DOT_PAIR.get_dex_share_currency_id().unwrap();
+		assert_eq!(DexModule::get_share_amounts(AUSD, DOT, 100_000), (0, 0));
+		assert_eq!(DexModule::get_liquidity_position(&BOB, AUSD, DOT), Some((0, 0)));
+		assert_eq!(DexModule::get_liquidity_position(&BOB, AUSD, AUSD), None);
+
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			500_000,
+			100_000,
+			false
+		));
+		assert_eq!(DexModule::get_share_amounts(AUSD, DOT, 0), (0, 0));
+		assert_eq!(DexModule::get_share_amounts(AUSD, DOT, 200_000), (200_000, 40_000));
+		assert_eq!(DexModule::get_share_amounts(DOT, AUSD, 200_000), (40_000, 200_000));
+
+		// shares are transferable as other currencies
+		assert_ok!(Tokens::transfer(Origin::signed(ALICE), BOB, lp_currency_id, 200_000));
+		assert_eq!(DexModule::get_liquidity_position(&ALICE, AUSD, DOT), Some((300_000, 0)));
+		assert_eq!(DexModule::get_liquidity_position(&BOB, AUSD, DOT), Some((200_000, 0)));
+
+		assert_ok!(<Runtime as Trait>::DEXIncentives::do_deposit_dex_share(
+			&BOB,
+			lp_currency_id,
+			50_000
+		));
+		assert_eq!(
+			DexModule::get_liquidity_position(&BOB, AUSD, DOT),
+			Some((150_000, 50_000))
+		);
+
+		let bob_ausd_before = Tokens::free_balance(AUSD, &BOB);
+		let bob_dot_before = Tokens::free_balance(DOT, &BOB);
+		assert_ok!(DexModule::remove_liquidity(
+			Origin::signed(BOB),
+			AUSD,
+			DOT,
+			150_000,
+			false
+		));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), bob_ausd_before + 150_000);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), bob_dot_before + 30_000);
+		assert_eq!(DexModule::get_liquidity_position(&BOB, AUSD, DOT), Some((0, 50_000)));
+		assert_eq!(DexModule::get_liquidity(AUSD, DOT), (350_000, 70_000));
+	});
+}
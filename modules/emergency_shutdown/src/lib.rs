@@ -15,7 +15,7 @@
 
 use frame_support::{
 	decl_error, decl_event, decl_module, decl_storage, ensure,
-	traits::{EnsureOrigin, Get},
+	traits::EnsureOrigin,
 	weights::{DispatchClass, Weight},
 };
 use frame_system::{self as system, ensure_signed};
@@ -23,7 +23,7 @@ use orml_utilities::with_transaction_result;
 use primitives::{Balance, CurrencyId};
 use sp_runtime::{traits::Zero, FixedPointNumber};
 use sp_std::prelude::*;
-use support::{AuctionManager, CDPTreasury, EmergencyShutdown, PriceProvider, Ratio};
+use support::{AuctionManager, CDPTreasury, CollateralRegistry, EmergencyShutdown, PriceProvider, Ratio};
 
 mod default_weight;
 mod mock;
@@ -38,8 +38,8 @@ pub trait WeightInfo {
 pub trait Trait: system::Trait + loans::Trait {
 	type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
-	/// The list of valid collateral currency types
-	type CollateralCurrencyIds: Get<Vec<CurrencyId>>;
+	/// The registry of valid collateral currency types
+	type CollateralRegistry: CollateralRegistry<CurrencyId>;
 
 	/// Price source to freeze currencies' price
 	type PriceSource: PriceProvider<CurrencyId>;
@@ -105,9 +105,6 @@ decl_module! {
 		type Error = Error<T>;
 		fn deposit_event() = default;
 
-		/// The list of valid collateral currency types
-		const CollateralCurrencyIds: Vec<CurrencyId> = T::CollateralCurrencyIds::get();
-
 		/// Start emergency shutdown
 		///
 		/// The dispatch origin of this call must be `ShutdownOrigin`.
@@ -123,14 +120,14 @@ decl_module! {
 		/// -------------------
 		/// Base Weight: 148.3 µs
 		/// # </weight>
-		#[weight = (T::WeightInfo::emergency_shutdown(T::CollateralCurrencyIds::get().len() as u32), DispatchClass::Operational)]
+		#[weight = (T::WeightInfo::emergency_shutdown(T::CollateralRegistry::collateral_currency_ids().len() as u32), DispatchClass::Operational)]
 		pub fn emergency_shutdown(origin) {
 			with_transaction_result(|| {
 				T::ShutdownOrigin::ensure_origin(origin)?;
 				ensure!(!Self::is_shutdown(), Error::<T>::AlreadyShutdown);
 
 				// get all collateral types
-				let collateral_currency_ids = T::CollateralCurrencyIds::get();
+				let collateral_currency_ids = T::CollateralRegistry::collateral_currency_ids();
 
 				// lock price for every collateral
 				for currency_id in collateral_currency_ids {
@@ -175,7 +172,7 @@ decl_module! {
 				// Ensure all debits of CDPs have been settled, and all collateral auction has been done or canceled.
 				// Settle all collaterals type CDPs which have debit, cancel all collateral auctions in forward stage and
 				// wait for all collateral auctions in reverse stage to be ended.
-				let collateral_currency_ids = T::CollateralCurrencyIds::get();
+				let collateral_currency_ids = T::CollateralRegistry::collateral_currency_ids();
 				for currency_id in collateral_currency_ids {
 					// there's no collateral auction
 					ensure!(
@@ -211,14 +208,14 @@ decl_module! {
 		/// -------------------
 		/// Base Weight: 455.1 µs
 		/// # </weight>
-		#[weight = T::WeightInfo::refund_collaterals(T::CollateralCurrencyIds::get().len() as u32)]
+		#[weight = T::WeightInfo::refund_collaterals(T::CollateralRegistry::collateral_currency_ids().len() as u32)]
 		pub fn refund_collaterals(origin, #[compact] amount: Balance) {
 			with_transaction_result(|| {
 				let who = ensure_signed(origin)?;
 				ensure!(Self::can_refund(), Error::<T>::CanNotRefund);
 
 				let refund_ratio: Ratio = <T as Trait>::CDPTreasury::get_debit_proportion(amount);
-				let collateral_currency_ids = T::CollateralCurrencyIds::get();
+				let collateral_currency_ids = T::CollateralRegistry::collateral_currency_ids();
 
 				// burn caller's stable currency by CDP treasury
 				<T as Trait>::CDPTreasury::burn_debit(&who, amount)?;
@@ -175,6 +175,15 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		Ok(())
 	}
 
+	fn new_dutch_collateral_auction(
+		_refund_recipient: &AccountId,
+		_currency_id: Self::CurrencyId,
+		_amount: Self::Balance,
+		_target: Self::Balance,
+	) -> DispatchResult {
+		Ok(())
+	}
+
 	fn new_debit_auction(_amount: Self::Balance, _fix: Self::Balance) -> DispatchResult {
 		Ok(())
 	}
@@ -218,22 +227,39 @@ impl cdp_treasury::Trait for Runtime {
 	type Event = TestEvent;
 	type Currency = Currencies;
 	type GetStableCurrencyId = GetStableCurrencyId;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type AuctionManagerHandler = MockAuctionManager;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = ();
+	type CollateralRegistry = MockCollateralRegistry;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type SurplusBuybackInterval = ();
+	type MaxSurplusBuybackSlippage = ();
+	type StakingRewardPool = ();
+	type PriceSource = ();
 	type WeightInfo = ();
 }
 pub type CDPTreasuryModule = cdp_treasury::Module<Runtime>;
 
-ord_parameter_types! {
-	pub const CollateralCurrencyIds: Vec<CurrencyId> = vec![BTC, DOT];
+pub struct MockCollateralRegistry;
+impl CollateralRegistry<CurrencyId> for MockCollateralRegistry {
+	fn collateral_currency_ids() -> Vec<CurrencyId> {
+		vec![BTC, DOT]
+	}
+
+	fn is_collateral(currency_id: CurrencyId) -> bool {
+		Self::collateral_currency_ids().contains(&currency_id)
+	}
+
+	fn is_active_collateral(currency_id: CurrencyId) -> bool {
+		Self::is_collateral(currency_id)
+	}
 }
 
 impl Trait for Runtime {
 	type Event = TestEvent;
-	type CollateralCurrencyIds = CollateralCurrencyIds;
+	type CollateralRegistry = MockCollateralRegistry;
 	type PriceSource = MockPriceSource;
 	type CDPTreasury = CDPTreasuryModule;
 	type AuctionManagerHandler = MockAuctionManager;
@@ -4,18 +4,23 @@
 //!
 //! The entry of the Honzon protocol for users, user can manipulate their CDP
 //! position to loan/payback, and can also authorize others to manage the their
-//! CDP under specific collateral type.
+//! CDP under specific collateral type, or delegate scoped permissions to
+//! managers to maintain their CDP.
 //!
 //! After system shutdown, some operations will be restricted.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{decl_error, decl_event, decl_module, decl_storage, ensure, traits::Get, weights::Weight};
+use codec::{Decode, Encode};
+use frame_support::{decl_error, decl_event, decl_module, decl_storage, ensure, weights::Weight};
 use frame_system::{self as system, ensure_signed};
 use orml_utilities::with_transaction_result;
-use primitives::{Amount, CurrencyId};
-use sp_runtime::{traits::Zero, DispatchResult};
-use support::EmergencyShutdown;
+use primitives::{Amount, Balance, CurrencyId};
+use sp_runtime::{
+	traits::{SaturatedConversion, Zero},
+	DispatchResult, RuntimeDebug,
+};
+use support::{DEXManager, EmergencyShutdown, Ratio};
 
 mod default_weight;
 mod mock;
@@ -27,6 +32,33 @@ pub trait WeightInfo {
 	fn unauthorize_all(c: u32) -> Weight;
 	fn adjust_loan() -> Weight;
 	fn transfer_loan_from() -> Weight;
+	fn close_loan_has_debit_by_dex() -> Weight;
+	fn expand_position_collateral() -> Weight;
+	fn set_cross_margin_mode(c: u32) -> Weight;
+	fn delegate() -> Weight;
+	fn undelegate() -> Weight;
+	fn adjust_loan_by_delegation() -> Weight;
+}
+
+/// The scoped permissions delegated to the manager of a CDP.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default)]
+pub struct DelegatedPermissions {
+	/// Allow to deposit and withdraw the collateral of the CDP.
+	pub adjust_collateral: bool,
+	/// Allow to repay the debit of the CDP.
+	pub repay: bool,
+	/// The remaining debit value allowed to be borrowed from the CDP, it is
+	/// reduced by every borrowing of the manager.
+	pub borrow_cap: Balance,
+}
+
+/// The delegation of a CDP to a manager.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub struct Delegation<BlockNumber> {
+	/// The permissions delegated to the manager.
+	pub permissions: DelegatedPermissions,
+	/// The delegation is no longer valid after this block.
+	pub expiry: BlockNumber,
 }
 
 pub trait Trait: system::Trait + cdp_engine::Trait {
@@ -41,12 +73,17 @@ decl_storage! {
 		/// The authorization relationship map from
 		/// Authorizer -> (CollateralType, Authorizee) -> Authorized
 		pub Authorization get(fn authorization): double_map hasher(twox_64_concat) T::AccountId, hasher(blake2_128_concat) (CurrencyId, T::AccountId) => bool;
+
+		/// The delegation relationship map from
+		/// Owner -> (CollateralType, Manager) -> Delegation
+		pub Delegations get(fn delegations): double_map hasher(twox_64_concat) T::AccountId, hasher(blake2_128_concat) (CurrencyId, T::AccountId) => Option<Delegation<T::BlockNumber>>;
 	}
 }
 
 decl_event!(
 	pub enum Event<T> where
 		<T as system::Trait>::AccountId,
+		<T as system::Trait>::BlockNumber,
 		CurrencyId = CurrencyId,
 	{
 		/// Authorize someone to operate the loan of specific collateral. \[authorizer, authorizee, collateral_type\]
@@ -55,6 +92,10 @@ decl_event!(
 		UnAuthorization(AccountId, AccountId, CurrencyId),
 		/// Cancel all authorization. \[authorizer\]
 		UnAuthorizationAll(AccountId),
+		/// Delegate the permissions of the loan of specific collateral to a manager. \[owner, manager, collateral_type, permissions, expiry\]
+		Delegation(AccountId, AccountId, CurrencyId, DelegatedPermissions, BlockNumber),
+		/// Cancel the delegation of the loan of specific collateral to a manager. \[owner, manager, collateral_type\]
+		UnDelegation(AccountId, AccountId, CurrencyId),
 	}
 );
 
@@ -65,6 +106,16 @@ decl_error! {
 		NoAuthorization,
 		// The system has been shutdown
 		AlreadyShutdown,
+		// The delegation has expired
+		DelegationExpired,
+		// The expiry of the delegation must be in the future
+		InvalidExpiry,
+		// The adjustment is out of the scope of the delegated permissions
+		ExceedDelegatedPermissions,
+		// The debit value to borrow exceeds the remaining borrow cap of the delegation
+		ExceedBorrowCap,
+		// There's no delegation to the manager
+		NoDelegation,
 	}
 }
 
@@ -107,6 +158,70 @@ decl_module! {
 			})?;
 		}
 
+		/// Close caller's CDP which has debit under `currency_id` by swapping just enough collateral
+		/// with DEX to repay all the debit, the remain collateral will be refunded to caller
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `max_collateral_amount`: the max amount of collateral can be sold to repay the debit.
+		///
+		/// # <weight>
+		/// - Complexity: `O(1)`
+		/// - Db reads: 25
+		/// - Db writes: 13
+		/// -------------------
+		/// Base Weight: 291.5 µs
+		/// # </weight>
+		#[weight = <T as Trait>::WeightInfo::close_loan_has_debit_by_dex()]
+		pub fn close_loan_has_debit_by_dex(
+			origin,
+			currency_id: CurrencyId,
+			#[compact] max_collateral_amount: Balance,
+		) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+				<cdp_engine::Module<T>>::close_cdp_has_debit_by_dex(who, currency_id, max_collateral_amount)?;
+				Ok(())
+			})?;
+		}
+
+		/// Expand caller's CDP under `currency_id` by issuing debit and swapping the stablecoin to collateral
+		/// with DEX in one call, until the collateral ratio decreases to about `target_collateral_ratio`
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `target_collateral_ratio`: the collateral ratio after the expansion, the CDP must still
+		///			be above the required collateral ratio.
+		/// - `min_increase_collateral`: the min amount of collateral the swap must get for the issued
+		///			stablecoin.
+		///
+		/// # <weight>
+		/// - Complexity: `O(1)`
+		/// - Db reads: 27
+		/// - Db writes: 12
+		/// -------------------
+		/// Base Weight: 302.8 µs
+		/// # </weight>
+		#[weight = <T as Trait>::WeightInfo::expand_position_collateral()
+			.saturating_add(<T as cdp_engine::Trait>::DEX::get_best_path_weight())]
+		pub fn expand_position_collateral(
+			origin,
+			currency_id: CurrencyId,
+			target_collateral_ratio: Ratio,
+			#[compact] min_increase_collateral: Balance,
+		) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+				<cdp_engine::Module<T>>::expand_position_collateral(
+					&who,
+					currency_id,
+					target_collateral_ratio,
+					min_increase_collateral,
+				)?;
+				Ok(())
+			})?;
+		}
+
 		/// Transfer the whole CDP of `from` under `currency_id` to caller's CDP under the same `currency_id`,
 		/// caller must have the authorization of `from` for the specific collateral type
 		///
@@ -135,6 +250,29 @@ decl_module! {
 			})?;
 		}
 
+		/// Enable or disable the cross-margin mode of caller. In cross-margin mode,
+		/// the positions of the cross-margin collateral types share their collaterals
+		/// to back the total debit, and are liquidated together.
+		///
+		/// - `enabled`: enable the cross-margin mode or not, to disable it, every
+		///			position must be safe on its own.
+		///
+		/// # <weight>
+		/// - Complexity: `O(C)` where C is the length of collateral_ids
+		/// - Db reads: 2 + 2 * C
+		/// - Db writes: 1
+		/// -------------------
+		/// Base Weight: 31.6 + 52.7 * C µs
+		/// # </weight>
+		#[weight = <T as Trait>::WeightInfo::set_cross_margin_mode(<cdp_engine::Module<T>>::collateral_currency_ids().len() as u32)]
+		pub fn set_cross_margin_mode(origin, enabled: bool) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				<cdp_engine::Module<T>>::set_cross_margin(&who, enabled)?;
+				Ok(())
+			})?;
+		}
+
 		/// Authorize `to` to manipulate the loan under `currency_id`
 		///
 		/// - `currency_id`: collateral currency id.
@@ -196,7 +334,7 @@ decl_module! {
 		/// -------------------
 		/// Base Weight: 0 + 3.8 * M + 128.4 * C µs
 		/// # </weight>
-		#[weight = <T as Trait>::WeightInfo::unauthorize_all(<T as cdp_engine::Trait>::CollateralCurrencyIds::get().len() as u32)]
+		#[weight = <T as Trait>::WeightInfo::unauthorize_all(<cdp_engine::Module<T>>::collateral_currency_ids().len() as u32)]
 		pub fn unauthorize_all(origin) {
 			with_transaction_result(|| {
 				let from = ensure_signed(origin)?;
@@ -205,6 +343,105 @@ decl_module! {
 				Ok(())
 			})?;
 		}
+
+		/// Delegate `permissions` of the loan under `currency_id` to `manager` until the `expiry` block,
+		/// replace the existing delegation to `manager` if there is one
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `manager`: manager account
+		/// - `permissions`: the scope of the permissions delegated to `manager`.
+		/// - `expiry`: the delegation is no longer valid after this block.
+		///
+		/// # <weight>
+		/// - Complexity: `O(1)`
+		/// - Db reads: 0
+		/// - Db writes: 1
+		/// -------------------
+		/// Base Weight: 29.36 µs
+		/// # </weight>
+		#[weight = <T as Trait>::WeightInfo::delegate()]
+		pub fn delegate(
+			origin,
+			currency_id: CurrencyId,
+			manager: T::AccountId,
+			permissions: DelegatedPermissions,
+			expiry: T::BlockNumber,
+		) {
+			with_transaction_result(|| {
+				let owner = ensure_signed(origin)?;
+				ensure!(expiry > <system::Module<T>>::block_number(), Error::<T>::InvalidExpiry);
+				<Delegations<T>>::insert(&owner, (currency_id, &manager), Delegation { permissions, expiry });
+				Self::deposit_event(RawEvent::Delegation(owner, manager, currency_id, permissions, expiry));
+				Ok(())
+			})?;
+		}
+
+		/// Cancel the delegation to `manager` under `currency_id`
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `manager`: manager account
+		///
+		/// # <weight>
+		/// - Complexity: `O(1)`
+		/// - Db reads: 1
+		/// - Db writes: 1
+		/// -------------------
+		/// Base Weight: 28.47 µs
+		/// # </weight>
+		#[weight = <T as Trait>::WeightInfo::undelegate()]
+		pub fn undelegate(
+			origin,
+			currency_id: CurrencyId,
+			manager: T::AccountId,
+		) {
+			with_transaction_result(|| {
+				let owner = ensure_signed(origin)?;
+				ensure!(
+					<Delegations<T>>::take(&owner, (currency_id, &manager)).is_some(),
+					Error::<T>::NoDelegation
+				);
+				Self::deposit_event(RawEvent::UnDelegation(owner, manager, currency_id));
+				Ok(())
+			})?;
+		}
+
+		/// Adjust the loan of `owner` under `currency_id` within the scope of the permissions
+		/// delegated to caller. The collateral and stablecoin are transferred from / to `owner`.
+		///
+		/// - `owner`: the owner of the loan.
+		/// - `currency_id`: collateral currency id.
+		/// - `collateral_adjustment`: signed amount, requires the permission to adjust collateral
+		///			if it's not zero.
+		/// - `debit_adjustment`: signed amount, negative requires the permission to repay, positive
+		///			requires the remaining borrow cap to cover the debit value to issue.
+		///
+		/// # <weight>
+		/// - Complexity: `O(1)`
+		/// - Db reads: 18
+		/// - Db writes: 10
+		/// -------------------
+		/// Base Weight: 258.9 µs
+		/// # </weight>
+		#[weight = <T as Trait>::WeightInfo::adjust_loan_by_delegation()]
+		pub fn adjust_loan_by_delegation(
+			origin,
+			owner: T::AccountId,
+			currency_id: CurrencyId,
+			collateral_adjustment: Amount,
+			debit_adjustment: Amount,
+		) {
+			with_transaction_result(|| {
+				let manager = ensure_signed(origin)?;
+
+				// not allowed to adjust the debit after system shutdown
+				if !debit_adjustment.is_zero() {
+					ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+				}
+				Self::consume_delegation(&owner, &manager, currency_id, collateral_adjustment, debit_adjustment)?;
+				<cdp_engine::Module<T>>::adjust_position(&owner, currency_id, collateral_adjustment, debit_adjustment)?;
+				Ok(())
+			})?;
+		}
 	}
 }
 
@@ -217,4 +454,39 @@ impl<T: Trait> Module<T> {
 		);
 		Ok(())
 	}
+
+	/// Check the adjustment to the loan of `owner` under `currency_id` is
+	/// within the delegation to `manager`, and reduce the borrow cap by the
+	/// debit value to issue.
+	fn consume_delegation(
+		owner: &T::AccountId,
+		manager: &T::AccountId,
+		currency_id: CurrencyId,
+		collateral_adjustment: Amount,
+		debit_adjustment: Amount,
+	) -> DispatchResult {
+		<Delegations<T>>::try_mutate(owner, (currency_id, manager), |maybe_delegation| -> DispatchResult {
+			let delegation = maybe_delegation.as_mut().ok_or(Error::<T>::NoAuthorization)?;
+			ensure!(
+				<system::Module<T>>::block_number() <= delegation.expiry,
+				Error::<T>::DelegationExpired
+			);
+
+			let permissions = &mut delegation.permissions;
+			if !collateral_adjustment.is_zero() {
+				ensure!(permissions.adjust_collateral, Error::<T>::ExceedDelegatedPermissions);
+			}
+			if debit_adjustment.is_negative() {
+				ensure!(permissions.repay, Error::<T>::ExceedDelegatedPermissions);
+			} else if debit_adjustment.is_positive() {
+				let debit_value =
+					<cdp_engine::Module<T>>::get_debit_value(currency_id, debit_adjustment.saturated_into());
+				permissions.borrow_cap = permissions
+					.borrow_cap
+					.checked_sub(debit_value)
+					.ok_or(Error::<T>::ExceedBorrowCap)?;
+			}
+			Ok(())
+		})
+	}
 }
@@ -5,11 +5,12 @@
 use super::*;
 use frame_support::{impl_outer_dispatch, impl_outer_event, impl_outer_origin, ord_parameter_types, parameter_types};
 use frame_system::{offchain::SendTransactionTypes, EnsureSignedBy};
+use orml_traits::DataProvider;
 use primitives::{Balance, TokenSymbol};
 use sp_core::H256;
 use sp_runtime::{
 	testing::{Header, TestXt},
-	traits::IdentityLookup,
+	traits::{Convert, IdentityLookup},
 	FixedPointNumber, ModuleId, Perbill,
 };
 use sp_std::cell::RefCell;
@@ -162,6 +163,13 @@ impl PriceProvider<CurrencyId> for MockPriceSource {
 	fn unlock_price(_currency_id: CurrencyId) {}
 }
 
+pub struct MockStableCurrencyPriceSource;
+impl DataProvider<CurrencyId, Price> for MockStableCurrencyPriceSource {
+	fn get(_currency_id: &CurrencyId) -> Option<Price> {
+		Some(Price::one())
+	}
+}
+
 pub struct MockAuctionManager;
 impl AuctionManager<AccountId> for MockAuctionManager {
 	type Balance = Balance;
@@ -177,6 +185,15 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		Ok(())
 	}
 
+	fn new_dutch_collateral_auction(
+		_refund_recipient: &AccountId,
+		_currency_id: Self::CurrencyId,
+		_amount: Self::Balance,
+		_target: Self::Balance,
+	) -> DispatchResult {
+		Ok(())
+	}
+
 	fn new_debit_auction(_amount: Self::Balance, _fix: Self::Balance) -> DispatchResult {
 		Ok(())
 	}
@@ -221,6 +238,13 @@ impl EmergencyShutdown for MockEmergencyShutdown {
 	}
 }
 
+pub struct MockRiskParamsUpdateOriginConvertor;
+impl Convert<Origin, cdp_engine::RiskParamsUpdateOrigin<AccountId>> for MockRiskParamsUpdateOriginConvertor {
+	fn convert(_origin: Origin) -> cdp_engine::RiskParamsUpdateOrigin<AccountId> {
+		cdp_engine::RiskParamsUpdateOrigin::Other
+	}
+}
+
 ord_parameter_types! {
 	pub const One: AccountId = 1;
 }
@@ -235,40 +259,56 @@ impl cdp_treasury::Trait for Runtime {
 	type Event = TestEvent;
 	type Currency = Currencies;
 	type GetStableCurrencyId = GetStableCurrencyId;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type AuctionManagerHandler = MockAuctionManager;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = ();
+	type CollateralRegistry = CDPEngineModule;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type SurplusBuybackInterval = ();
+	type MaxSurplusBuybackSlippage = ();
+	type StakingRewardPool = ();
+	type PriceSource = ();
 	type WeightInfo = ();
 }
 pub type CDPTreasuryModule = cdp_treasury::Module<Runtime>;
 
 parameter_types! {
-	pub CollateralCurrencyIds: Vec<CurrencyId> = vec![BTC, DOT];
 	pub DefaultLiquidationRatio: Ratio = Ratio::saturating_from_rational(3, 2);
 	pub DefaultDebitExchangeRate: ExchangeRate = ExchangeRate::one();
 	pub DefaultLiquidationPenalty: Rate = Rate::saturating_from_rational(10, 100);
 	pub const MinimumDebitValue: Balance = 2;
+	pub const KeeperDeposit: Balance = 10;
+	pub const KeeperSlashAmount: Balance = 2;
+	pub const MaxRiskParamsHistory: u32 = 3;
 	pub MaxSlippageSwapWithDEX: Ratio = Ratio::saturating_from_rational(50, 100);
 	pub const UnsignedPriority: u64 = 1 << 20;
+	pub LegacyCollateralCurrencyIds: Vec<CurrencyId> = vec![BTC, DOT];
 }
 
 impl cdp_engine::Trait for Runtime {
 	type Event = TestEvent;
 	type PriceSource = MockPriceSource;
-	type CollateralCurrencyIds = CollateralCurrencyIds;
+	type StableCurrencyPriceSource = MockStableCurrencyPriceSource;
 	type DefaultLiquidationRatio = DefaultLiquidationRatio;
 	type DefaultDebitExchangeRate = DefaultDebitExchangeRate;
 	type DefaultLiquidationPenalty = DefaultLiquidationPenalty;
 	type MinimumDebitValue = MinimumDebitValue;
 	type GetStableCurrencyId = GetStableCurrencyId;
 	type CDPTreasury = CDPTreasuryModule;
+	type AuctionManagerHandler = MockAuctionManager;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
 	type DEX = ();
 	type UnsignedPriority = UnsignedPriority;
 	type EmergencyShutdown = MockEmergencyShutdown;
+	type Currency = Currencies;
+	type KeeperDeposit = KeeperDeposit;
+	type KeeperSlashAmount = KeeperSlashAmount;
+	type MaxRiskParamsHistory = MaxRiskParamsHistory;
+	type RiskParamsUpdateOriginConvertor = MockRiskParamsUpdateOriginConvertor;
+	type LegacyCollateralCurrencyIds = LegacyCollateralCurrencyIds;
 	type WeightInfo = ();
 }
 pub type CDPEngineModule = cdp_engine::Module<Runtime>;
@@ -319,6 +359,13 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		cdp_engine::GenesisConfig {
+			global_stability_fee: Default::default(),
+			collaterals_params: vec![(BTC, None, None, None, None, 0), (DOT, None, None, None, None, 0)],
+		}
+		.assimilate_storage::<Runtime>(&mut t)
+		.unwrap();
+
 		t.into()
 	}
 }
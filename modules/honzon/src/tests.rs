@@ -5,7 +5,7 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::*;
-use orml_traits::Change;
+use orml_traits::{Change, MultiCurrency};
 use sp_runtime::FixedPointNumber;
 use support::{Rate, Ratio};
 
@@ -88,6 +88,42 @@ fn transfer_loan_from_should_work() {
 	});
 }
 
+#[test]
+fn transfer_loan_from_cross_margin_vault_should_check_remaining_vault() {
+	ExtBuilder::default().build().execute_with(|| {
+		for currency_id in vec![BTC, DOT] {
+			assert_ok!(CDPEngineModule::set_collateral_params(
+				Origin::signed(1),
+				currency_id,
+				Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+				Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+				Change::NewValue(10000),
+			));
+		}
+		assert_ok!(CDPEngineModule::set_cross_margin_liquidation_order(
+			Origin::signed(1),
+			vec![BTC, DOT]
+		));
+		assert_ok!(HonzonModule::set_cross_margin_mode(Origin::signed(ALICE), true));
+		assert_ok!(HonzonModule::adjust_loan(Origin::signed(ALICE), BTC, 100, 50));
+		assert_ok!(HonzonModule::adjust_loan(Origin::signed(ALICE), DOT, 0, 5));
+		assert_ok!(HonzonModule::authorize(Origin::signed(ALICE), BTC, BOB));
+
+		// the debit of DOT would be left without collateral
+		assert_noop!(
+			HonzonModule::transfer_loan_from(Origin::signed(BOB), BTC, ALICE),
+			cdp_engine::Error::<Runtime>::BelowRequiredCollateralRatio,
+		);
+
+		assert_ok!(HonzonModule::adjust_loan(Origin::signed(ALICE), DOT, 0, -5));
+		assert_ok!(HonzonModule::transfer_loan_from(Origin::signed(BOB), BTC, ALICE));
+		assert_eq!(LoansModule::positions(BTC, BOB).collateral, 100);
+		assert_eq!(LoansModule::positions(BTC, BOB).debit, 50);
+	});
+}
+
 #[test]
 fn transfer_unauthorization_loans_should_not_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -128,5 +164,259 @@ fn on_emergency_shutdown_should_work() {
 			HonzonModule::transfer_loan_from(Origin::signed(ALICE), BTC, BOB),
 			Error::<Runtime>::AlreadyShutdown,
 		);
+		assert_noop!(
+			HonzonModule::close_loan_has_debit_by_dex(Origin::signed(ALICE), BTC, 100),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+		assert_noop!(
+			HonzonModule::expand_position_collateral(
+				Origin::signed(ALICE),
+				BTC,
+				Ratio::saturating_from_rational(3, 1),
+				0
+			),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+		assert_noop!(
+			HonzonModule::adjust_loan_by_delegation(Origin::signed(BOB), ALICE, BTC, 0, -10),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+	});
+}
+
+#[test]
+fn close_loan_has_debit_by_dex_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(HonzonModule::adjust_loan(Origin::signed(ALICE), BTC, 100, 50));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 50);
+
+		assert_ok!(HonzonModule::close_loan_has_debit_by_dex(
+			Origin::signed(ALICE),
+			BTC,
+			100
+		));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+	});
+}
+
+#[test]
+fn set_cross_margin_mode_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		for currency_id in vec![BTC, DOT] {
+			assert_ok!(CDPEngineModule::set_collateral_params(
+				Origin::signed(1),
+				currency_id,
+				Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+				Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+				Change::NewValue(10000),
+			));
+		}
+		assert_ok!(CDPEngineModule::set_cross_margin_liquidation_order(
+			Origin::signed(1),
+			vec![BTC, DOT]
+		));
+		assert_ok!(HonzonModule::adjust_loan(Origin::signed(ALICE), BTC, 100, 50));
+		assert_noop!(
+			HonzonModule::adjust_loan(Origin::signed(ALICE), DOT, 0, 5),
+			cdp_engine::Error::<Runtime>::BelowRequiredCollateralRatio,
+		);
+
+		assert_ok!(HonzonModule::set_cross_margin_mode(Origin::signed(ALICE), true));
+		let cross_margin_mode_event = TestEvent::cdp_engine(cdp_engine::RawEvent::CrossMarginModeUpdated(ALICE, true));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == cross_margin_mode_event));
+		assert_eq!(CDPEngineModule::is_cross_margin(ALICE), true);
+
+		// the debit of DOT is backed by the collateral of BTC
+		assert_ok!(HonzonModule::adjust_loan(Origin::signed(ALICE), DOT, 0, 5));
+		assert_eq!(LoansModule::positions(DOT, ALICE).debit, 5);
+
+		// the DOT position is invalid on its own
+		assert_noop!(
+			HonzonModule::set_cross_margin_mode(Origin::signed(ALICE), false),
+			cdp_engine::Error::<Runtime>::BelowRequiredCollateralRatio,
+		);
+
+		assert_ok!(HonzonModule::adjust_loan(Origin::signed(ALICE), DOT, 0, -5));
+		assert_ok!(HonzonModule::set_cross_margin_mode(Origin::signed(ALICE), false));
+		assert_eq!(CDPEngineModule::is_cross_margin(ALICE), false);
+	});
+}
+
+#[test]
+fn delegate_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let permissions = DelegatedPermissions {
+			adjust_collateral: true,
+			repay: false,
+			borrow_cap: 100,
+		};
+		assert_noop!(
+			HonzonModule::delegate(Origin::signed(ALICE), BTC, BOB, permissions, 1),
+			Error::<Runtime>::InvalidExpiry,
+		);
+		assert_ok!(HonzonModule::delegate(Origin::signed(ALICE), BTC, BOB, permissions, 10));
+
+		let delegation_event = TestEvent::honzon(RawEvent::Delegation(ALICE, BOB, BTC, permissions, 10));
+		assert!(System::events().iter().any(|record| record.event == delegation_event));
+		assert_eq!(
+			HonzonModule::delegations(ALICE, (BTC, BOB)),
+			Some(Delegation {
+				permissions,
+				expiry: 10
+			})
+		);
+
+		// delegation does not grant the authorization to transfer the loan
+		assert_noop!(
+			HonzonModule::check_authorization(&ALICE, &BOB, BTC),
+			Error::<Runtime>::NoAuthorization
+		);
+	});
+}
+
+#[test]
+fn undelegate_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			HonzonModule::undelegate(Origin::signed(ALICE), BTC, BOB),
+			Error::<Runtime>::NoDelegation,
+		);
+		assert_ok!(HonzonModule::delegate(
+			Origin::signed(ALICE),
+			BTC,
+			BOB,
+			Default::default(),
+			10
+		));
+		assert_ok!(HonzonModule::undelegate(Origin::signed(ALICE), BTC, BOB));
+
+		let undelegation_event = TestEvent::honzon(RawEvent::UnDelegation(ALICE, BOB, BTC));
+		assert!(System::events().iter().any(|record| record.event == undelegation_event));
+		assert_eq!(HonzonModule::delegations(ALICE, (BTC, BOB)), None);
+		assert_noop!(
+			HonzonModule::undelegate(Origin::signed(ALICE), BTC, BOB),
+			Error::<Runtime>::NoDelegation,
+		);
+	});
+}
+
+#[test]
+fn adjust_loan_by_delegation_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(HonzonModule::adjust_loan(Origin::signed(ALICE), BTC, 100, 50));
+		assert_noop!(
+			HonzonModule::adjust_loan_by_delegation(Origin::signed(BOB), ALICE, BTC, 10, 0),
+			Error::<Runtime>::NoAuthorization,
+		);
+
+		assert_ok!(HonzonModule::delegate(
+			Origin::signed(ALICE),
+			BTC,
+			BOB,
+			DelegatedPermissions {
+				adjust_collateral: true,
+				repay: false,
+				borrow_cap: 0,
+			},
+			10
+		));
+		assert_ok!(HonzonModule::adjust_loan_by_delegation(
+			Origin::signed(BOB),
+			ALICE,
+			BTC,
+			10,
+			0
+		));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 110);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 890);
+		assert_eq!(Currencies::free_balance(BTC, &BOB), 1000);
+		assert_noop!(
+			HonzonModule::adjust_loan_by_delegation(Origin::signed(BOB), ALICE, BTC, 0, -10),
+			Error::<Runtime>::ExceedDelegatedPermissions,
+		);
+		assert_noop!(
+			HonzonModule::adjust_loan_by_delegation(Origin::signed(BOB), ALICE, BTC, 0, 10),
+			Error::<Runtime>::ExceedBorrowCap,
+		);
+
+		assert_ok!(HonzonModule::delegate(
+			Origin::signed(ALICE),
+			BTC,
+			BOB,
+			DelegatedPermissions {
+				adjust_collateral: false,
+				repay: true,
+				borrow_cap: 20,
+			},
+			10
+		));
+		assert_ok!(HonzonModule::adjust_loan_by_delegation(
+			Origin::signed(BOB),
+			ALICE,
+			BTC,
+			0,
+			-10
+		));
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 40);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 40);
+		assert_ok!(HonzonModule::adjust_loan_by_delegation(
+			Origin::signed(BOB),
+			ALICE,
+			BTC,
+			0,
+			15
+		));
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 55);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 55);
+		assert_eq!(Currencies::free_balance(AUSD, &BOB), 0);
+		assert_eq!(
+			HonzonModule::delegations(ALICE, (BTC, BOB))
+				.unwrap()
+				.permissions
+				.borrow_cap,
+			5
+		);
+		assert_noop!(
+			HonzonModule::adjust_loan_by_delegation(Origin::signed(BOB), ALICE, BTC, 0, 10),
+			Error::<Runtime>::ExceedBorrowCap,
+		);
+		assert_noop!(
+			HonzonModule::adjust_loan_by_delegation(Origin::signed(BOB), ALICE, BTC, 10, 0),
+			Error::<Runtime>::ExceedDelegatedPermissions,
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			HonzonModule::adjust_loan_by_delegation(Origin::signed(BOB), ALICE, BTC, 0, -5),
+			Error::<Runtime>::DelegationExpired,
+		);
 	});
 }
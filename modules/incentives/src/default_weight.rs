@@ -23,7 +23,8 @@ impl crate::WeightInfo for () {
 	}
 	fn update_loans_incentive_rewards(c: u32) -> Weight {
 		(5_081_000 as Weight)
-			.saturating_add((5_495_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add((8_762_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
 			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
 	}
 	fn update_dex_incentive_rewards(c: u32) -> Weight {
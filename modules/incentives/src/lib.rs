@@ -16,7 +16,7 @@ use sp_runtime::{
 	DispatchResult, FixedPointNumber, ModuleId, RuntimeDebug,
 };
 use sp_std::prelude::*;
-use support::{CDPTreasury, DEXIncentives, DEXManager, EmergencyShutdown, Rate};
+use support::{CDPTreasury, CollateralRegistry, DEXIncentives, DEXManager, EmergencyShutdown, Rate};
 
 mod default_weight;
 mod mock;
@@ -108,6 +108,9 @@ pub trait Trait:
 	/// Emergency shutdown.
 	type EmergencyShutdown: EmergencyShutdown;
 
+	/// The registry of valid collateral currency types
+	type CollateralRegistry: CollateralRegistry<CurrencyId>;
+
 	/// The module id, keep DEXShare LP.
 	type ModuleId: Get<ModuleId>;
 
@@ -188,6 +191,11 @@ decl_module! {
 			with_transaction_result(|| {
 				T::UpdateOrigin::ensure_origin(origin)?;
 				for (currency_id, amount) in updates {
+					ensure!(
+						T::CollateralRegistry::is_collateral(currency_id),
+						Error::<T>::InvalidCurrencyId,
+					);
+
 					LoansIncentiveRewards::insert(currency_id, amount);
 				}
 				Ok(())
@@ -289,6 +297,10 @@ impl<T: Trait> DEXIncentives<T::AccountId, CurrencyId, Balance> for Module<T> {
 		Self::deposit_event(RawEvent::WithdrawDEXShare(who.clone(), lp_currency_id, amount));
 		Ok(())
 	}
+
+	fn get_staked_dex_share(who: &T::AccountId, lp_currency_id: CurrencyId) -> Balance {
+		<orml_rewards::Module<T>>::share_and_withdrawn_reward(PoolId::DexIncentive(lp_currency_id), who).0
+	}
 }
 
 impl<T: Trait> Module<T> {
@@ -132,6 +132,10 @@ impl CDPTreasury<AccountId> for MockCDPTreasury {
 		unimplemented!()
 	}
 
+	fn withdraw_surplus(_: &AccountId, _: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+
 	fn deposit_collateral(_: &AccountId, _: CurrencyId, _: Balance) -> DispatchResult {
 		unimplemented!()
 	}
@@ -161,6 +165,28 @@ impl DEXManager<AccountId, CurrencyId, Balance> for MockDEX {
 		unimplemented!()
 	}
 
+	fn get_best_path_with_exact_supply(
+		_: CurrencyId,
+		_: CurrencyId,
+		_: Balance,
+		_: Option<Ratio>,
+	) -> Option<Vec<CurrencyId>> {
+		unimplemented!()
+	}
+
+	fn get_best_path_with_exact_target(
+		_: CurrencyId,
+		_: CurrencyId,
+		_: Balance,
+		_: Option<Ratio>,
+	) -> Option<Vec<CurrencyId>> {
+		unimplemented!()
+	}
+
+	fn get_best_path_weight() -> Weight {
+		unimplemented!()
+	}
+
 	fn swap_with_exact_supply(
 		_: &AccountId,
 		_: &[CurrencyId],
@@ -197,6 +223,21 @@ impl EmergencyShutdown for MockEmergencyShutdown {
 	}
 }
 
+pub struct MockCollateralRegistry;
+impl CollateralRegistry<CurrencyId> for MockCollateralRegistry {
+	fn collateral_currency_ids() -> Vec<CurrencyId> {
+		vec![BTC, DOT]
+	}
+
+	fn is_collateral(currency_id: CurrencyId) -> bool {
+		Self::collateral_currency_ids().contains(&currency_id)
+	}
+
+	fn is_active_collateral(currency_id: CurrencyId) -> bool {
+		Self::is_collateral(currency_id)
+	}
+}
+
 impl orml_rewards::Trait for Runtime {
 	type Share = Balance;
 	type Balance = Balance;
@@ -233,6 +274,7 @@ impl Trait for Runtime {
 	type Currency = TokensModule;
 	type DEX = MockDEX;
 	type EmergencyShutdown = MockEmergencyShutdown;
+	type CollateralRegistry = MockCollateralRegistry;
 	type ModuleId = IncentivesModuleId;
 	type WeightInfo = ();
 }
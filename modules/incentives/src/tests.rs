@@ -86,6 +86,29 @@ fn deposit_dex_share_works() {
 	});
 }
 
+#[test]
+fn get_staked_dex_share_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TokensModule::deposit(BTC_AUSD_LP, &ALICE, 10000));
+		assert_eq!(IncentivesModule::get_staked_dex_share(&ALICE, BTC_AUSD_LP), 0);
+
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(ALICE),
+			BTC_AUSD_LP,
+			10000
+		));
+		assert_eq!(IncentivesModule::get_staked_dex_share(&ALICE, BTC_AUSD_LP), 10000);
+		assert_eq!(IncentivesModule::get_staked_dex_share(&ALICE, DOT_AUSD_LP), 0);
+
+		assert_ok!(IncentivesModule::withdraw_dex_share(
+			Origin::signed(ALICE),
+			BTC_AUSD_LP,
+			2000
+		));
+		assert_eq!(IncentivesModule::get_staked_dex_share(&ALICE, BTC_AUSD_LP), 8000);
+	});
+}
+
 #[test]
 fn withdraw_dex_share_works() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -181,6 +204,10 @@ fn update_loans_incentive_rewards_works() {
 			IncentivesModule::update_loans_incentive_rewards(Origin::signed(ALICE), vec![]),
 			BadOrigin
 		);
+		assert_noop!(
+			IncentivesModule::update_loans_incentive_rewards(Origin::signed(4), vec![(BTC_AUSD_LP, 200)]),
+			Error::<Runtime>::InvalidCurrencyId
+		);
 		assert_eq!(IncentivesModule::loans_incentive_rewards(BTC), 0);
 		assert_eq!(IncentivesModule::loans_incentive_rewards(DOT), 0);
 
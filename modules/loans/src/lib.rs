@@ -188,7 +188,7 @@ impl<T: Trait> Module<T> {
 
 			// ensure pass risk check
 			let Position { collateral, debit } = Self::positions(currency_id, who);
-			T::RiskManager::check_position_valid(currency_id, collateral, debit)?;
+			T::RiskManager::check_position_valid(who, currency_id, collateral, debit)?;
 
 			Self::deposit_event(RawEvent::PositionUpdated(
 				who.clone(),
@@ -217,7 +217,11 @@ impl<T: Trait> Module<T> {
 			.expect("existing debit balance cannot overflow; qed");
 
 		// check new position
-		T::RiskManager::check_position_valid(currency_id, new_to_collateral_balance, new_to_debit_balance)?;
+		T::RiskManager::check_position_valid(to, currency_id, new_to_collateral_balance, new_to_debit_balance)?;
+
+		// check the remaining positions of `from`, the position transferred out may
+		// back the debits of other positions in its cross-margin vault
+		T::RiskManager::check_position_valid(from, currency_id, Zero::zero(), Zero::zero())?;
 
 		// balance -> amount
 		let collateral_adjustment = Self::amount_try_from_balance(collateral)?;
@@ -235,8 +239,9 @@ impl<T: Trait> Module<T> {
 		Ok(())
 	}
 
-	/// mutate records of collaterals and debits
-	fn update_loan(
+	/// mutate records of collaterals and debits, the caller is responsible
+	/// for the transfer of collaterals and the risk check of the position
+	pub fn update_loan(
 		who: &T::AccountId,
 		currency_id: CurrencyId,
 		collateral_adjustment: Amount,
@@ -317,7 +322,7 @@ impl<T: Trait> Module<T> {
 
 impl<T: Trait> Module<T> {
 	/// Convert `Balance` to `Amount`.
-	fn amount_try_from_balance(b: Balance) -> result::Result<Amount, Error<T>> {
+	pub fn amount_try_from_balance(b: Balance) -> result::Result<Amount, Error<T>> {
 		TryInto::<Amount>::try_into(b).map_err(|_| Error::<T>::AmountConvertFailed)
 	}
 
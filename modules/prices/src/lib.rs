@@ -56,6 +56,10 @@ pub trait Trait: system::Trait {
 	/// currency.
 	type LiquidStakingExchangeRateProvider: ExchangeRateProvider;
 
+	/// The price provider used when the price from `Source` is unavailable,
+	/// such as the TWAP of DEX.
+	type FallbackPriceProvider: PriceProvider<CurrencyId>;
+
 	/// Weight information for the extrinsics in this module.
 	type WeightInfo: WeightInfo;
 }
@@ -140,8 +144,11 @@ impl<T: Trait> PriceProvider<CurrencyId> for Module<T> {
 			Self::get_price(T::GetStakingCurrencyId::get())
 				.and_then(|n| n.checked_mul(&T::LiquidStakingExchangeRateProvider::get_exchange_rate()))
 		} else {
-			// if locked price exists, return it, otherwise return latest price from oracle.
-			Self::locked_price(currency_id).or_else(|| T::Source::get(&currency_id))
+			// if locked price exists, return it, otherwise return latest price from oracle,
+			// and fallback to the price from `FallbackPriceProvider`.
+			Self::locked_price(currency_id)
+				.or_else(|| T::Source::get(&currency_id))
+				.or_else(|| T::FallbackPriceProvider::get_price(currency_id))
 		}
 	}
 
@@ -19,6 +19,7 @@ pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
 pub const BTC: CurrencyId = CurrencyId::Token(TokenSymbol::XBTC);
 pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
 pub const LDOT: CurrencyId = CurrencyId::Token(TokenSymbol::LDOT);
+pub const RENBTC: CurrencyId = CurrencyId::Token(TokenSymbol::RENBTC);
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Runtime;
@@ -100,6 +101,25 @@ impl ExchangeRateProvider for MockLiquidStakingExchangeProvider {
 	}
 }
 
+pub struct MockFallbackPriceProvider;
+impl PriceProvider<CurrencyId> for MockFallbackPriceProvider {
+	fn get_relative_price(_base: CurrencyId, _quote: CurrencyId) -> Option<Price> {
+		None
+	}
+
+	fn get_price(currency_id: CurrencyId) -> Option<Price> {
+		match currency_id {
+			RENBTC => Some(Price::saturating_from_integer(4900)),
+			BTC => Some(Price::saturating_from_integer(4800)),
+			_ => None,
+		}
+	}
+
+	fn lock_price(_currency_id: CurrencyId) {}
+
+	fn unlock_price(_currency_id: CurrencyId) {}
+}
+
 ord_parameter_types! {
 	pub const One: AccountId = 1;
 }
@@ -120,6 +140,7 @@ impl Trait for Runtime {
 	type GetLiquidCurrencyId = GetLiquidCurrencyId;
 	type LockOrigin = EnsureSignedBy<One, AccountId>;
 	type LiquidStakingExchangeRateProvider = MockLiquidStakingExchangeProvider;
+	type FallbackPriceProvider = MockFallbackPriceProvider;
 	type WeightInfo = ();
 }
 pub type PricesModule = Module<Runtime>;
@@ -4,7 +4,7 @@
 
 use super::*;
 use frame_support::{assert_noop, assert_ok};
-use mock::{ExtBuilder, Origin, PricesModule, System, TestEvent, ACA, AUSD, BTC, DOT, LDOT};
+use mock::{ExtBuilder, Origin, PricesModule, System, TestEvent, ACA, AUSD, BTC, DOT, LDOT, RENBTC};
 use sp_runtime::{traits::BadOrigin, FixedPointNumber};
 
 #[test]
@@ -16,6 +16,18 @@ fn get_price_from_oracle() {
 	});
 }
 
+#[test]
+fn get_price_from_fallback_price_provider() {
+	ExtBuilder::default().build().execute_with(|| {
+		// the price from oracle takes precedence
+		assert_eq!(PricesModule::get_price(BTC), Some(Price::saturating_from_integer(5000)));
+		assert_eq!(
+			PricesModule::get_price(RENBTC),
+			Some(Price::saturating_from_integer(4900))
+		);
+	});
+}
+
 #[test]
 fn get_price_of_stable_currency_id() {
 	ExtBuilder::default().build().execute_with(|| {
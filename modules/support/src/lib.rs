@@ -1,6 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Decode, Encode, FullCodec, HasCompact};
+use frame_support::weights::Weight;
 use sp_core::H160;
 use sp_runtime::{DispatchError, DispatchResult, FixedU128};
 use sp_std::{
@@ -24,6 +25,7 @@ pub trait RiskManager<AccountId, CurrencyId, Balance, DebitBalance> {
 	fn get_bad_debt_value(currency_id: CurrencyId, debit_balance: DebitBalance) -> Balance;
 
 	fn check_position_valid(
+		who: &AccountId,
 		currency_id: CurrencyId,
 		collateral_balance: Balance,
 		debit_balance: DebitBalance,
@@ -40,6 +42,7 @@ impl<AccountId, CurrencyId, Balance: Default, DebitBalance> RiskManager<AccountI
 	}
 
 	fn check_position_valid(
+		_who: &AccountId,
 		_currency_id: CurrencyId,
 		_collateral_balance: Balance,
 		_debit_balance: DebitBalance,
@@ -63,6 +66,12 @@ pub trait AuctionManager<AccountId> {
 		amount: Self::Balance,
 		target: Self::Balance,
 	) -> DispatchResult;
+	fn new_dutch_collateral_auction(
+		refund_recipient: &AccountId,
+		currency_id: Self::CurrencyId,
+		amount: Self::Balance,
+		target: Self::Balance,
+	) -> DispatchResult;
 	fn new_debit_auction(amount: Self::Balance, fix: Self::Balance) -> DispatchResult;
 	fn new_surplus_auction(amount: Self::Balance) -> DispatchResult;
 	fn cancel_auction(id: Self::AuctionId) -> DispatchResult;
@@ -88,6 +97,22 @@ pub trait DEXManager<AccountId, CurrencyId, Balance> {
 		price_impact_limit: Option<Ratio>,
 	) -> Option<Balance>;
 
+	fn get_best_path_with_exact_supply(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		supply_amount: Balance,
+		price_impact_limit: Option<Ratio>,
+	) -> Option<Vec<CurrencyId>>;
+
+	fn get_best_path_with_exact_target(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		target_amount: Balance,
+		price_impact_limit: Option<Ratio>,
+	) -> Option<Vec<CurrencyId>>;
+
+	fn get_best_path_weight() -> Weight;
+
 	fn swap_with_exact_supply(
 		who: &AccountId,
 		path: &[CurrencyId],
@@ -129,6 +154,28 @@ where
 		Some(Default::default())
 	}
 
+	fn get_best_path_with_exact_supply(
+		_supply_currency_id: CurrencyId,
+		_target_currency_id: CurrencyId,
+		_supply_amount: Balance,
+		_price_impact_limit: Option<Ratio>,
+	) -> Option<Vec<CurrencyId>> {
+		None
+	}
+
+	fn get_best_path_with_exact_target(
+		_supply_currency_id: CurrencyId,
+		_target_currency_id: CurrencyId,
+		_target_amount: Balance,
+		_price_impact_limit: Option<Ratio>,
+	) -> Option<Vec<CurrencyId>> {
+		None
+	}
+
+	fn get_best_path_weight() -> Weight {
+		0
+	}
+
 	fn swap_with_exact_supply(
 		_who: &AccountId,
 		_path: &[CurrencyId],
@@ -184,6 +231,10 @@ pub trait CDPTreasury<AccountId> {
 	/// deposit surplus(stable currency) to cdp treasury by `from`
 	fn deposit_surplus(from: &AccountId, surplus: Self::Balance) -> DispatchResult;
 
+	/// withdraw surplus(stable currency) of cdp treasury to `to`, the surplus
+	/// in auction or offsetting the debit pool cannot be withdrawn
+	fn withdraw_surplus(to: &AccountId, surplus: Self::Balance) -> DispatchResult;
+
 	/// deposit collateral assets to cdp treasury by `who`
 	fn deposit_collateral(from: &AccountId, currency_id: Self::CurrencyId, amount: Self::Balance) -> DispatchResult;
 
@@ -222,6 +273,20 @@ pub trait PriceProvider<CurrencyId> {
 	fn unlock_price(currency_id: CurrencyId);
 }
 
+impl<CurrencyId> PriceProvider<CurrencyId> for () {
+	fn get_relative_price(_: CurrencyId, _: CurrencyId) -> Option<Price> {
+		None
+	}
+
+	fn get_price(_: CurrencyId) -> Option<Price> {
+		None
+	}
+
+	fn lock_price(_: CurrencyId) {}
+
+	fn unlock_price(_: CurrencyId) {}
+}
+
 pub trait ExchangeRateProvider {
 	fn get_exchange_rate() -> ExchangeRate;
 }
@@ -230,12 +295,38 @@ pub trait EmergencyShutdown {
 	fn is_shutdown() -> bool;
 }
 
+/// The registry of collateral types accepted by the CDP system.
+pub trait CollateralRegistry<CurrencyId> {
+	/// All registered collateral types, including the paused ones.
+	fn collateral_currency_ids() -> Vec<CurrencyId>;
+	/// Check if `currency_id` is a registered collateral type.
+	fn is_collateral(currency_id: CurrencyId) -> bool;
+	/// Check if `currency_id` is a registered collateral type which is not
+	/// paused.
+	fn is_active_collateral(currency_id: CurrencyId) -> bool;
+}
+
+impl<CurrencyId> CollateralRegistry<CurrencyId> for () {
+	fn collateral_currency_ids() -> Vec<CurrencyId> {
+		vec![]
+	}
+
+	fn is_collateral(_: CurrencyId) -> bool {
+		false
+	}
+
+	fn is_active_collateral(_: CurrencyId) -> bool {
+		false
+	}
+}
+
 pub trait DEXIncentives<AccountId, CurrencyId, Balance> {
 	fn do_deposit_dex_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
 	fn do_withdraw_dex_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
+	fn get_staked_dex_share(who: &AccountId, lp_currency_id: CurrencyId) -> Balance;
 }
 
-impl<AccountId, CurrencyId, Balance> DEXIncentives<AccountId, CurrencyId, Balance> for () {
+impl<AccountId, CurrencyId, Balance: Default> DEXIncentives<AccountId, CurrencyId, Balance> for () {
 	fn do_deposit_dex_share(_: &AccountId, _: CurrencyId, _: Balance) -> DispatchResult {
 		Ok(())
 	}
@@ -243,6 +334,10 @@ impl<AccountId, CurrencyId, Balance> DEXIncentives<AccountId, CurrencyId, Balanc
 	fn do_withdraw_dex_share(_: &AccountId, _: CurrencyId, _: Balance) -> DispatchResult {
 		Ok(())
 	}
+
+	fn get_staked_dex_share(_: &AccountId, _: CurrencyId) -> Balance {
+		Default::default()
+	}
 }
 
 /// Mapping from `AccountId` into `H160`.
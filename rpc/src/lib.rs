@@ -2,7 +2,7 @@
 
 #![warn(missing_docs)]
 
-use primitives::{AccountId, Balance, Block, BlockNumber, CurrencyId, DataProviderId, Hash, Nonce};
+use primitives::{AccountId, AuctionId, Balance, Block, BlockNumber, CurrencyId, DataProviderId, Hash, Nonce};
 use sc_client_api::light::{Fetcher, RemoteBlockchain};
 use sc_consensus_babe::{Config, Epoch};
 use sc_consensus_epochs::SharedEpochChanges;
@@ -87,6 +87,16 @@ where
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, runtime_common::TimeStampedPrice>,
 	C::Api: module_staking_pool_rpc::StakingPoolRuntimeApi<Block, AccountId, Balance>,
+	C::Api: module_dex_rpc::DexRuntimeApi<Block, AccountId, CurrencyId, Balance>,
+	C::Api: module_cdp_engine_rpc::CdpEngineRuntimeApi<Block, AccountId, CurrencyId, Balance, BlockNumber>,
+	C::Api: module_auction_manager_rpc::AuctionManagerRuntimeApi<
+		Block,
+		AccountId,
+		AuctionId,
+		CurrencyId,
+		Balance,
+		BlockNumber,
+	>,
 	C::Api: EVMRuntimeRPCApi<Block>,
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
@@ -95,6 +105,9 @@ where
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
 	B::State: sc_client_api::StateBackend<sp_runtime::traits::HashFor<Block>>,
 {
+	use module_auction_manager_rpc::{AuctionManager, AuctionManagerApi};
+	use module_cdp_engine_rpc::{CdpEngine, CdpEngineApi};
+	use module_dex_rpc::{Dex, DexApi};
 	use module_staking_pool_rpc::{StakingPool, StakingPoolApi};
 	use orml_oracle_rpc::{Oracle, OracleApi};
 	use pallet_contracts_rpc::{Contracts, ContractsApi};
@@ -154,6 +167,9 @@ where
 	)));
 	io.extend_with(OracleApi::to_delegate(Oracle::new(client.clone())));
 	io.extend_with(StakingPoolApi::to_delegate(StakingPool::new(client.clone())));
+	io.extend_with(DexApi::to_delegate(Dex::new(client.clone())));
+	io.extend_with(CdpEngineApi::to_delegate(CdpEngine::new(client.clone())));
+	io.extend_with(AuctionManagerApi::to_delegate(AuctionManager::new(client.clone())));
 	io.extend_with(EVMApiServer::to_delegate(EVMApi::new(client)));
 
 	io
@@ -20,7 +20,7 @@ use sp_core::{
 	OpaqueMetadata, H160, U256,
 };
 use sp_runtime::traits::{
-	BadOrigin, BlakeTwo256, Block as BlockT, CheckedAdd, CheckedMul, CheckedSub, NumberFor, OpaqueKeys,
+	BadOrigin, BlakeTwo256, Block as BlockT, CheckedAdd, CheckedMul, CheckedSub, Convert, NumberFor, OpaqueKeys,
 	SaturatedConversion, Saturating, StaticLookup,
 };
 use sp_runtime::{
@@ -39,6 +39,7 @@ use static_assertions::const_assert;
 
 use frame_system::{EnsureOneOf, EnsureRoot, RawOrigin};
 use module_accounts::{Multiplier, TargetedFeeAdjustment};
+use module_auction_manager::PriceDecayCurve;
 use module_evm::{CallInfo, CreateInfo, EnsureAddressTruncated, Runner};
 use module_evm_accounts::EvmAddressMapping;
 use orml_currencies::{BasicCurrencyAdapter, Currency};
@@ -764,6 +765,7 @@ impl module_prices::Trait for Runtime {
 	type GetLiquidCurrencyId = GetLiquidCurrencyId;
 	type LockOrigin = EnsureRootOrTwoThirdsGeneralCouncil;
 	type LiquidStakingExchangeRateProvider = LiquidStakingExchangeRateProvider;
+	type FallbackPriceProvider = module_dex::TWAPPriceProvider<Runtime, GetStableCurrencyId, StableCurrencyFixedPrice>;
 	type WeightInfo = weights::prices::WeightInfo<Runtime>;
 }
 
@@ -855,6 +857,12 @@ parameter_types! {
 	pub MinimumIncrementSize: Rate = Rate::saturating_from_rational(2, 100);
 	pub const AuctionTimeToClose: BlockNumber = 15 * MINUTES;
 	pub const AuctionDurationSoftCap: BlockNumber = 2 * HOURS;
+	pub DutchAuctionStartPremium: Rate = Rate::saturating_from_rational(20, 100);
+	pub DutchAuctionPriceDecay: PriceDecayCurve<BlockNumber> =
+		PriceDecayCurve::StairstepExponential(MINUTES, Rate::saturating_from_rational(99, 100));
+	pub const DutchAuctionResetDuration: BlockNumber = 2 * HOURS;
+	pub const MinimumCollateralSplitAmount: Balance = MILLICENTS;
+	pub const MaxBidsPerBatch: u32 = 10;
 	pub const AuctionManagerUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
 }
 
@@ -867,11 +875,17 @@ impl module_auction_manager::Trait for Runtime {
 	type AuctionDurationSoftCap = AuctionDurationSoftCap;
 	type GetStableCurrencyId = GetStableCurrencyId;
 	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type DutchAuctionStartPremium = DutchAuctionStartPremium;
+	type DutchAuctionPriceDecay = DutchAuctionPriceDecay;
+	type DutchAuctionResetDuration = DutchAuctionResetDuration;
+	type MinimumCollateralSplitAmount = MinimumCollateralSplitAmount;
+	type MaxBidsPerBatch = MaxBidsPerBatch;
 	type CDPTreasury = CdpTreasury;
 	type DEX = Dex;
 	type PriceSource = Prices;
 	type UnsignedPriority = AuctionManagerUnsignedPriority;
 	type EmergencyShutdown = EmergencyShutdown;
+	type BidderAuthorityId = module_auction_manager::crypto::BidderAuthorityId;
 	type WeightInfo = weights::auction_manager::WeightInfo<Runtime>;
 }
 
@@ -944,33 +958,58 @@ where
 }
 
 parameter_types! {
-	pub CollateralCurrencyIds: Vec<CurrencyId> = vec![CurrencyId::Token(TokenSymbol::DOT), CurrencyId::Token(TokenSymbol::XBTC), CurrencyId::Token(TokenSymbol::LDOT), CurrencyId::Token(TokenSymbol::RENBTC)];
 	pub DefaultLiquidationRatio: Ratio = Ratio::saturating_from_rational(110, 100);
 	pub DefaultDebitExchangeRate: ExchangeRate = ExchangeRate::saturating_from_rational(1, 10);
 	pub DefaultLiquidationPenalty: Rate = Rate::saturating_from_rational(5, 100);
 	pub const MinimumDebitValue: Balance = DOLLARS;
 	pub MaxSlippageSwapWithDEX: Ratio = Ratio::saturating_from_rational(5, 100);
 	pub const CdpEngineUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
+	pub const KeeperDeposit: Balance = 10 * DOLLARS;
+	pub const KeeperSlashAmount: Balance = DOLLARS;
+	pub const MaxRiskParamsHistory: u32 = 50;
+	pub LegacyCollateralCurrencyIds: Vec<CurrencyId> = vec![CurrencyId::Token(TokenSymbol::DOT), CurrencyId::Token(TokenSymbol::XBTC), CurrencyId::Token(TokenSymbol::LDOT), CurrencyId::Token(TokenSymbol::RENBTC)];
 }
 
 impl module_cdp_engine::Trait for Runtime {
 	type Event = Event;
 	type PriceSource = Prices;
-	type CollateralCurrencyIds = CollateralCurrencyIds;
+	type StableCurrencyPriceSource = AggregatedDataProvider;
 	type DefaultLiquidationRatio = DefaultLiquidationRatio;
 	type DefaultDebitExchangeRate = DefaultDebitExchangeRate;
 	type DefaultLiquidationPenalty = DefaultLiquidationPenalty;
 	type MinimumDebitValue = MinimumDebitValue;
 	type GetStableCurrencyId = GetStableCurrencyId;
 	type CDPTreasury = CdpTreasury;
+	type AuctionManagerHandler = AuctionManager;
 	type UpdateOrigin = EnsureRootOrHalfHonzonCouncil;
 	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
 	type DEX = Dex;
 	type UnsignedPriority = CdpEngineUnsignedPriority;
 	type EmergencyShutdown = EmergencyShutdown;
+	type Currency = Currencies;
+	type KeeperDeposit = KeeperDeposit;
+	type KeeperSlashAmount = KeeperSlashAmount;
+	type MaxRiskParamsHistory = MaxRiskParamsHistory;
+	type RiskParamsUpdateOriginConvertor = RiskParamsUpdateOriginConvertor;
+	type LegacyCollateralCurrencyIds = LegacyCollateralCurrencyIds;
 	type WeightInfo = weights::cdp_engine::WeightInfo<Runtime>;
 }
 
+pub struct RiskParamsUpdateOriginConvertor;
+impl Convert<Origin, module_cdp_engine::RiskParamsUpdateOrigin<AccountId>> for RiskParamsUpdateOriginConvertor {
+	fn convert(origin: Origin) -> module_cdp_engine::RiskParamsUpdateOrigin<AccountId> {
+		// record the approvals of the honzon council proposal
+		let origin: Result<pallet_collective::RawOrigin<AccountId, HonzonCouncilInstance>, Origin> = origin.into();
+		match origin {
+			Ok(pallet_collective::RawOrigin::Members(yes_votes, total_votes)) => {
+				module_cdp_engine::RiskParamsUpdateOrigin::Collective(yes_votes, total_votes)
+			}
+			Ok(pallet_collective::RawOrigin::Member(who)) => module_cdp_engine::RiskParamsUpdateOrigin::Signed(who),
+			_ => module_cdp_engine::RiskParamsUpdateOrigin::Other,
+		}
+	}
+}
+
 impl module_honzon::Trait for Runtime {
 	type Event = Event;
 	type WeightInfo = weights::honzon::WeightInfo<Runtime>;
@@ -978,7 +1017,7 @@ impl module_honzon::Trait for Runtime {
 
 impl module_emergency_shutdown::Trait for Runtime {
 	type Event = Event;
-	type CollateralCurrencyIds = CollateralCurrencyIds;
+	type CollateralRegistry = CdpEngine;
 	type PriceSource = Prices;
 	type CDPTreasury = CdpTreasury;
 	type AuctionManagerHandler = AuctionManager;
@@ -989,7 +1028,13 @@ impl module_emergency_shutdown::Trait for Runtime {
 parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (1, 1000);	// 0.1%
 	pub const TradingPathLimit: usize = 3;
-	pub EnabledTradingPairs: Vec<TradingPair> = vec![
+	pub const TradingPathCandidatesLimit: usize = 5;
+	pub const DEXTWAPWindow: BlockNumber = HOURS;
+	pub const LimitOrderDeposit: Balance = DOLLARS;
+	pub const MaxLimitOrdersPerAccount: u32 = 20;
+	pub const DexUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 4;
+	pub CDPTreasuryAccount: AccountId = CDPTreasuryModuleId::get().into_account();
+	pub LegacyEnabledTradingPairs: Vec<TradingPair> = vec![
 		TradingPair::new(CurrencyId::Token(TokenSymbol::AUSD), CurrencyId::Token(TokenSymbol::DOT)),
 		TradingPair::new(CurrencyId::Token(TokenSymbol::AUSD), CurrencyId::Token(TokenSymbol::XBTC)),
 		TradingPair::new(CurrencyId::Token(TokenSymbol::AUSD), CurrencyId::Token(TokenSymbol::LDOT)),
@@ -1001,27 +1046,69 @@ parameter_types! {
 impl module_dex::Trait for Runtime {
 	type Event = Event;
 	type Currency = Currencies;
-	type EnabledTradingPairs = EnabledTradingPairs;
 	type GetExchangeFee = GetExchangeFee;
 	type TradingPathLimit = TradingPathLimit;
+	type TradingPathCandidatesLimit = TradingPathCandidatesLimit;
 	type ModuleId = DEXModuleId;
 	type DEXIncentives = Incentives;
 	type WeightInfo = weights::dex::WeightInfo<Runtime>;
+	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
+	type ProtocolFeeReceiver = CDPTreasuryAccount;
+	type TWAPWindow = DEXTWAPWindow;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type LimitOrderDeposit = LimitOrderDeposit;
+	type MaxLimitOrdersPerAccount = MaxLimitOrdersPerAccount;
+	type UnsignedPriority = DexUnsignedPriority;
+	type Call = Call;
+	type LegacyEnabledTradingPairs = LegacyEnabledTradingPairs;
+	type StableSwapRateProvider = DexStableSwapRateProvider;
+	type FlashSwapCallFilter = DexFlashSwapCallFilter;
+}
+
+pub struct DexFlashSwapCallFilter;
+impl Filter<Call> for DexFlashSwapCallFilter {
+	fn filter(call: &Call) -> bool {
+		// the borrower can not operate the DEX, nor wrap the calls to bypass the filter
+		!matches!(
+			call,
+			Call::Dex(..) | Call::Utility(..) | Call::Multisig(..) | Call::Proxy(..) | Call::Sudo(..)
+		)
+	}
+}
+
+pub struct DexStableSwapRateProvider;
+impl module_dex::StableSwapRateProvider for DexStableSwapRateProvider {
+	fn get_rate_multiplier(_trading_pair: TradingPair, currency_id: CurrencyId) -> ExchangeRate {
+		// LDOT is normalized to DOT by the liquid exchange rate
+		if currency_id == GetLDOTCurrencyId::get() {
+			StakingPool::liquid_exchange_rate()
+		} else {
+			ExchangeRate::one()
+		}
+	}
 }
 
 parameter_types! {
 	pub const MaxAuctionsCount: u32 = 100;
+	pub const SurplusBuybackInterval: BlockNumber = HOURS;
+	pub MaxSurplusBuybackSlippage: Ratio = Ratio::saturating_from_rational(5, 100);
 }
 
 impl module_cdp_treasury::Trait for Runtime {
 	type Event = Event;
 	type Currency = Currencies;
 	type GetStableCurrencyId = GetStableCurrencyId;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type AuctionManagerHandler = AuctionManager;
 	type UpdateOrigin = EnsureRootOrHalfHonzonCouncil;
 	type DEX = Dex;
+	type CollateralRegistry = CdpEngine;
 	type MaxAuctionsCount = MaxAuctionsCount;
 	type ModuleId = CDPTreasuryModuleId;
+	type SurplusBuybackInterval = SurplusBuybackInterval;
+	type MaxSurplusBuybackSlippage = MaxSurplusBuybackSlippage;
+	type StakingRewardPool = ZeroAccountId;
+	type PriceSource = Prices;
 	type WeightInfo = weights::cdp_treasury::WeightInfo<Runtime>;
 }
 
@@ -1084,6 +1171,7 @@ impl module_incentives::Trait for Runtime {
 	type Currency = Currencies;
 	type DEX = Dex;
 	type EmergencyShutdown = EmergencyShutdown;
+	type CollateralRegistry = CdpEngine;
 	type ModuleId = IncentivesModuleId;
 	type WeightInfo = weights::incentives::WeightInfo<Runtime>;
 }
@@ -1338,7 +1426,7 @@ construct_runtime!(
 		Prices: module_prices::{Module, Storage, Call, Event},
 
 		// DEX
-		Dex: module_dex::{Module, Storage, Call, Event<T>},
+		Dex: module_dex::{Module, Storage, Call, Event<T>, Config, ValidateUnsigned},
 
 		// Honzon
 		AuctionManager: module_auction_manager::{Module, Storage, Call, Event<T>, ValidateUnsigned},
@@ -1584,6 +1672,199 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_dex_rpc_runtime_api::DexApi<
+		Block,
+		AccountId,
+		CurrencyId,
+		Balance,
+	> for Runtime {
+		fn get_swap_target_amounts(
+			path: Vec<CurrencyId>,
+			supply_amount: Balance,
+		) -> Option<module_dex_rpc_runtime_api::SwapQuote<Balance>> {
+			Dex::get_target_amounts(&path, supply_amount, None)
+				.ok()
+				.map(|amounts| module_dex_rpc_runtime_api::SwapQuote {
+					price_impacts: Dex::get_price_impacts(&path, &amounts),
+					amounts,
+				})
+		}
+
+		fn get_swap_supply_amounts(
+			path: Vec<CurrencyId>,
+			target_amount: Balance,
+		) -> Option<module_dex_rpc_runtime_api::SwapQuote<Balance>> {
+			Dex::get_supply_amounts(&path, target_amount, None)
+				.ok()
+				.map(|amounts| module_dex_rpc_runtime_api::SwapQuote {
+					price_impacts: Dex::get_price_impacts(&path, &amounts),
+					amounts,
+				})
+		}
+
+		fn get_liquidity_pool(
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+		) -> module_dex_rpc_runtime_api::LiquidityPoolInfo<Balance> {
+			let (amount_a, amount_b) = Dex::get_liquidity(currency_id_a, currency_id_b);
+			module_dex_rpc_runtime_api::LiquidityPoolInfo { amount_a, amount_b }
+		}
+
+		fn get_liquidity_position(
+			who: AccountId,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+		) -> Option<module_dex_rpc_runtime_api::LiquidityPosition<Balance>> {
+			Dex::get_liquidity_position(&who, currency_id_a, currency_id_b).map(|(share, staked_share)| {
+				let (amount_a, amount_b) = Dex::get_share_amounts(currency_id_a, currency_id_b, share);
+				let (staked_amount_a, staked_amount_b) =
+					Dex::get_share_amounts(currency_id_a, currency_id_b, staked_share);
+				module_dex_rpc_runtime_api::LiquidityPosition {
+					share,
+					staked_share,
+					amount_a,
+					amount_b,
+					staked_amount_a,
+					staked_amount_b,
+				}
+			})
+		}
+	}
+
+	impl module_cdp_engine_rpc_runtime_api::CdpEngineApi<
+		Block,
+		AccountId,
+		CurrencyId,
+		Balance,
+		BlockNumber,
+	> for Runtime {
+		fn get_cdp_position(
+			who: AccountId,
+			currency_id: CurrencyId,
+		) -> module_cdp_engine_rpc_runtime_api::CdpPosition<Balance> {
+			let module_loans::Position { collateral, debit } = Loans::positions(currency_id, &who);
+			let price = CdpEngine::get_collateral_price(currency_id);
+			module_cdp_engine_rpc_runtime_api::CdpPosition {
+				collateral,
+				debit,
+				debit_value: CdpEngine::get_debit_value(currency_id, debit),
+				collateral_ratio: price
+					.map(|price| CdpEngine::calculate_collateral_ratio(currency_id, collateral, debit, price)),
+				liquidation_ratio: CdpEngine::get_liquidation_ratio(currency_id),
+				required_collateral_ratio: CdpEngine::required_collateral_ratio(currency_id),
+				liquidation_price: CdpEngine::get_liquidation_price(currency_id, collateral, debit),
+				is_unsafe: CdpEngine::is_position_unsafe(&who, currency_id, collateral, debit),
+				available_debit_value: price
+					.map(|price| CdpEngine::get_available_debit_value(currency_id, collateral, debit, price))
+					.unwrap_or_default(),
+				stability_fee: CdpEngine::get_stability_fee(currency_id),
+			}
+		}
+
+		fn get_debit_ceiling_utilisation(
+			currency_id: CurrencyId,
+		) -> module_cdp_engine_rpc_runtime_api::DebitCeilingUtilisation<Balance> {
+			module_cdp_engine_rpc_runtime_api::DebitCeilingUtilisation {
+				total_debit_value: CdpEngine::get_total_debit_value(currency_id),
+				maximum_total_debit_value: CdpEngine::maximum_total_debit_value(currency_id),
+				utilisation: CdpEngine::get_debit_ceiling_utilisation(currency_id),
+			}
+		}
+
+		fn get_risk_params_history(
+			currency_id: CurrencyId,
+		) -> Vec<module_cdp_engine_rpc_runtime_api::RiskParamsRecord<AccountId, BlockNumber, Balance>> {
+			CdpEngine::risk_params_history(currency_id)
+				.into_iter()
+				.map(|record| module_cdp_engine_rpc_runtime_api::RiskParamsRecord {
+					block_number: record.block_number,
+					origin: match record.origin {
+						module_cdp_engine::RiskParamsUpdateOrigin::Root => {
+							module_cdp_engine_rpc_runtime_api::RiskParamsUpdateOrigin::Root
+						}
+						module_cdp_engine::RiskParamsUpdateOrigin::Signed(who) => {
+							module_cdp_engine_rpc_runtime_api::RiskParamsUpdateOrigin::Signed(who)
+						}
+						module_cdp_engine::RiskParamsUpdateOrigin::Collective(yes_votes, total_votes) => {
+							module_cdp_engine_rpc_runtime_api::RiskParamsUpdateOrigin::Collective(yes_votes, total_votes)
+						}
+						module_cdp_engine::RiskParamsUpdateOrigin::StabilityFeeController => {
+							module_cdp_engine_rpc_runtime_api::RiskParamsUpdateOrigin::StabilityFeeController
+						}
+						module_cdp_engine::RiskParamsUpdateOrigin::Other => {
+							module_cdp_engine_rpc_runtime_api::RiskParamsUpdateOrigin::Other
+						}
+					},
+					stability_fee: record.params.stability_fee,
+					liquidation_ratio: record.params.liquidation_ratio,
+					liquidation_penalty: record.params.liquidation_penalty,
+					required_collateral_ratio: record.params.required_collateral_ratio,
+					maximum_total_debit_value: record.params.maximum_total_debit_value,
+					partial_liquidation_buffer: record.partial_liquidation_buffer,
+					retired: record.retired,
+				})
+				.collect()
+		}
+	}
+
+	impl module_auction_manager_rpc_runtime_api::AuctionManagerApi<
+		Block,
+		AccountId,
+		AuctionId,
+		CurrencyId,
+		Balance,
+		BlockNumber,
+	> for Runtime {
+		fn get_active_auctions(
+		) -> Vec<module_auction_manager_rpc_runtime_api::ActiveAuction<AccountId, AuctionId, CurrencyId, Balance, BlockNumber>> {
+			let now = System::block_number();
+			AuctionManager::active_auction_ids()
+				.into_iter()
+				.filter_map(|auction_id| {
+					AuctionManager::get_auction_bid_state(auction_id, now).map(|state| {
+						module_auction_manager_rpc_runtime_api::ActiveAuction {
+							auction_id,
+							auction_type: match state.auction_type {
+								module_auction_manager::AuctionType::Collateral => {
+									module_auction_manager_rpc_runtime_api::AuctionType::Collateral
+								}
+								module_auction_manager::AuctionType::DutchCollateral => {
+									module_auction_manager_rpc_runtime_api::AuctionType::DutchCollateral
+								}
+								module_auction_manager::AuctionType::Debit => {
+									module_auction_manager_rpc_runtime_api::AuctionType::Debit
+								}
+								module_auction_manager::AuctionType::Surplus => {
+									module_auction_manager_rpc_runtime_api::AuctionType::Surplus
+								}
+							},
+							currency_id: state.currency_id,
+							stage: match state.stage {
+								module_auction_manager::AuctionStage::Forward => {
+									module_auction_manager_rpc_runtime_api::AuctionStage::Forward
+								}
+								module_auction_manager::AuctionStage::Reverse => {
+									module_auction_manager_rpc_runtime_api::AuctionStage::Reverse
+								}
+								module_auction_manager::AuctionStage::Descending => {
+									module_auction_manager_rpc_runtime_api::AuctionStage::Descending
+								}
+							},
+							last_bidder: state.last_bid.as_ref().map(|(who, _)| who.clone()),
+							last_bid_price: state.last_bid.map(|(_, price)| price).unwrap_or_default(),
+							minimum_next_bid: state.minimum_next_bid.unwrap_or_default(),
+							current_price: state.current_price,
+							end_time: state.end_time,
+							time_left: state.time_left,
+							amount: state.amount,
+							payment: state.payment,
+						}
+					})
+				})
+				.collect()
+		}
+	}
+
 	impl module_staking_pool_rpc_runtime_api::StakingPoolApi<
 		Block,
 		AccountId,
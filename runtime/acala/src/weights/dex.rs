@@ -31,6 +31,17 @@ impl<T: frame_system::Trait> module_dex::WeightInfo for WeightInfo<T> {
 				.saturating_add(DbWeight::get().writes(7 as Weight))
 		}
 	}
+	fn add_liquidity_single_token(deposit: bool) -> Weight {
+		if deposit {
+			(205_000_000 as Weight)
+				.saturating_add(DbWeight::get().reads(24 as Weight))
+				.saturating_add(DbWeight::get().writes(16 as Weight))
+		} else {
+			(160_000_000 as Weight)
+				.saturating_add(DbWeight::get().reads(18 as Weight))
+				.saturating_add(DbWeight::get().writes(11 as Weight))
+		}
+	}
 	fn swap_with_exact_supply() -> Weight {
 		(80_000_000 as Weight)
 			.saturating_add(DbWeight::get().reads(12 as Weight))
@@ -41,4 +52,94 @@ impl<T: frame_system::Trait> module_dex::WeightInfo for WeightInfo<T> {
 			.saturating_add(DbWeight::get().reads(12 as Weight))
 			.saturating_add(DbWeight::get().writes(9 as Weight))
 	}
+	fn swap_with_exact_supply_by_best_path() -> Weight {
+		(95_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(17 as Weight))
+			.saturating_add(DbWeight::get().writes(9 as Weight))
+	}
+	fn swap_with_exact_target_by_best_path() -> Weight {
+		(97_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(17 as Weight))
+			.saturating_add(DbWeight::get().writes(9 as Weight))
+	}
+	fn enable_trading_pair() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn disable_trading_pair() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn list_trading_pair() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn add_provision() -> Weight {
+		(80_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn end_provisioning() -> Weight {
+		(110_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+	}
+	fn claim_dex_share() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn cancel_provisioning() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn refund_provision() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn set_exchange_fee() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(0 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_protocol_fee_share() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(0 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn place_limit_order() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn cancel_limit_order() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn execute_limit_order() -> Weight {
+		(90_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(16 as Weight))
+			.saturating_add(DbWeight::get().writes(13 as Weight))
+	}
+	fn expire_limit_order() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn flash_swap() -> Weight {
+		(70_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(9 as Weight))
+			.saturating_add(DbWeight::get().writes(7 as Weight))
+	}
+	fn set_pool_type() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(0 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
 }
@@ -28,4 +28,34 @@ impl<T: frame_system::Trait> module_honzon::WeightInfo for WeightInfo<T> {
 			.saturating_add(DbWeight::get().reads(21 as Weight))
 			.saturating_add(DbWeight::get().writes(8 as Weight))
 	}
+	fn set_cross_margin_mode(c: u32) -> Weight {
+		(63_214_000 as Weight)
+			.saturating_add((105_382_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn close_loan_has_debit_by_dex() -> Weight {
+		(583_094_000 as Weight)
+			.saturating_add(DbWeight::get().reads(25 as Weight))
+			.saturating_add(DbWeight::get().writes(13 as Weight))
+	}
+	fn expand_position_collateral() -> Weight {
+		(605_731_000 as Weight)
+			.saturating_add(DbWeight::get().reads(27 as Weight))
+			.saturating_add(DbWeight::get().writes(12 as Weight))
+	}
+	fn delegate() -> Weight {
+		(58_726_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn undelegate() -> Weight {
+		(56_943_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn adjust_loan_by_delegation() -> Weight {
+		(571_836_000 as Weight)
+			.saturating_add(DbWeight::get().reads(25 as Weight))
+			.saturating_add(DbWeight::get().writes(11 as Weight))
+	}
 }
@@ -1,6 +1,7 @@
 use crate::{
-	AcalaOracle, AccountId, AuctionId, AuctionManager, Balance, CdpTreasury, Currencies, CurrencyId, EmergencyShutdown,
-	GetNativeCurrencyId, GetStableCurrencyId, Price, Runtime, TokenSymbol, DOLLARS,
+	AcalaOracle, AccountId, AuctionId, AuctionManager, Balance, CdpTreasury, Currencies, CurrencyId,
+	DutchAuctionResetDuration, EmergencyShutdown, GetNativeCurrencyId, GetStableCurrencyId, MaxBidsPerBatch, Price,
+	Runtime, System, TokenSymbol, DOLLARS,
 };
 
 use super::utils::set_balance;
@@ -90,6 +91,89 @@ runtime_benchmarks! {
 		// shutdown
 		EmergencyShutdown::emergency_shutdown(RawOrigin::Root.into())?;
 	}: cancel(RawOrigin::None, auction_id)
+
+	// `take` a dutch collateral auction, worst case:
+	// raise the remaining target and finish the auction with refund
+	take {
+		let taker: AccountId = account("taker", 0, SEED);
+		let funder: AccountId = account("funder", 0, SEED);
+		let stable_currency_id = GetStableCurrencyId::get();
+
+		// set balance
+		Currencies::deposit(stable_currency_id, &taker, dollar(200))?;
+		Currencies::deposit(CurrencyId::Token(TokenSymbol::DOT), &funder, dollar(1))?;
+		CdpTreasury::deposit_collateral(&funder, CurrencyId::Token(TokenSymbol::DOT), dollar(1))?;
+
+		// feed price
+		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(CurrencyId::Token(TokenSymbol::DOT), Price::saturating_from_integer(120))])?;
+
+		// create dutch collateral auction
+		AuctionManager::new_dutch_collateral_auction(&funder, CurrencyId::Token(TokenSymbol::DOT), dollar(1), dollar(100))?;
+		let auction_id: AuctionId = Default::default();
+	}: _(RawOrigin::Signed(taker), auction_id, dollar(1), Price::saturating_from_integer(200))
+
+	// `reset` a dutch collateral auction
+	reset {
+		let caller: AccountId = account("caller", 0, SEED);
+		let funder: AccountId = account("funder", 0, SEED);
+
+		// set balance
+		Currencies::deposit(CurrencyId::Token(TokenSymbol::DOT), &funder, dollar(1))?;
+		CdpTreasury::deposit_collateral(&funder, CurrencyId::Token(TokenSymbol::DOT), dollar(1))?;
+
+		// feed price
+		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(CurrencyId::Token(TokenSymbol::DOT), Price::saturating_from_integer(120))])?;
+
+		// create dutch collateral auction
+		AuctionManager::new_dutch_collateral_auction(&funder, CurrencyId::Token(TokenSymbol::DOT), dollar(1), dollar(100))?;
+		let auction_id: AuctionId = Default::default();
+
+		// wait until the auction can be reset
+		System::set_block_number(System::block_number() + DutchAuctionResetDuration::get());
+	}: _(RawOrigin::Signed(caller), auction_id)
+
+	// `batch_bid` on collateral auctions, worst case:
+	// all the auctions have been already bid
+	batch_bid {
+		let c in 1 .. MaxBidsPerBatch::get();
+
+		let bidder: AccountId = account("bidder", 0, SEED);
+		let previous_bidder: AccountId = account("previous_bidder", 0, SEED);
+		let funder: AccountId = account("funder", 0, SEED);
+		let stable_currency_id = GetStableCurrencyId::get();
+
+		// set balance
+		Currencies::deposit(stable_currency_id, &bidder, dollar(80 * c))?;
+		Currencies::deposit(stable_currency_id, &previous_bidder, dollar(50 * c))?;
+		Currencies::deposit(CurrencyId::Token(TokenSymbol::DOT), &funder, dollar(c))?;
+		CdpTreasury::deposit_collateral(&funder, CurrencyId::Token(TokenSymbol::DOT), dollar(c))?;
+
+		// create collateral auctions and bid them
+		let mut previous_bids = vec![];
+		let mut bids = vec![];
+		for i in 0 .. c {
+			AuctionManager::new_collateral_auction(&funder, CurrencyId::Token(TokenSymbol::DOT), dollar(1), dollar(100))?;
+			previous_bids.push((i as AuctionId, dollar(50)));
+			bids.push((i as AuctionId, dollar(80)));
+		}
+		AuctionManager::batch_bid(RawOrigin::Signed(previous_bidder).into(), previous_bids)?;
+	}: _(RawOrigin::Signed(bidder), bids)
+
+	// `partial_bid` on a collateral auction
+	partial_bid {
+		let bidder: AccountId = account("bidder", 0, SEED);
+		let funder: AccountId = account("funder", 0, SEED);
+		let stable_currency_id = GetStableCurrencyId::get();
+
+		// set balance
+		Currencies::deposit(stable_currency_id, &bidder, dollar(80))?;
+		Currencies::deposit(CurrencyId::Token(TokenSymbol::DOT), &funder, dollar(2))?;
+		CdpTreasury::deposit_collateral(&funder, CurrencyId::Token(TokenSymbol::DOT), dollar(2))?;
+
+		// create collateral auction
+		AuctionManager::new_collateral_auction(&funder, CurrencyId::Token(TokenSymbol::DOT), dollar(2), dollar(200))?;
+		let auction_id: AuctionId = Default::default();
+	}: _(RawOrigin::Signed(bidder), auction_id, dollar(1), dollar(80))
 }
 
 #[cfg(test)]
@@ -124,4 +208,32 @@ mod tests {
 			assert_ok!(test_benchmark_cancel_collateral_auction());
 		});
 	}
+
+	#[test]
+	fn test_take() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_take());
+		});
+	}
+
+	#[test]
+	fn test_reset() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_reset());
+		});
+	}
+
+	#[test]
+	fn test_batch_bid() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_batch_bid());
+		});
+	}
+
+	#[test]
+	fn test_partial_bid() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_partial_bid());
+		});
+	}
 }
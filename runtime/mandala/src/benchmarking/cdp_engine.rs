@@ -1,16 +1,22 @@
 use crate::{
-	AcalaOracle, AccountId, Amount, Balance, CdpEngine, CollateralCurrencyIds, CurrencyId, Dex, EmergencyShutdown,
-	GetStableCurrencyId, MaxSlippageSwapWithDEX, MinimumDebitValue, Price, Rate, Ratio, Runtime, TokenSymbol, DOLLARS,
+	AcalaOracle, AccountId, Amount, Balance, BlockNumber, CdpEngine, CurrencyId, Dex, EmergencyShutdown,
+	GetStableCurrencyId, KeeperDeposit, MaxSlippageSwapWithDEX, MinimumDebitValue, Price, Rate, Ratio, Runtime, System,
+	TokenSymbol, DOLLARS,
 };
 
-use super::utils::set_balance;
+use super::utils::{collateral_currency_ids, set_balance};
 use core::convert::TryInto;
 use frame_benchmarking::account;
+use frame_support::{traits::OnInitialize, StorageValue};
 use frame_system::RawOrigin;
+use module_cdp_engine::StabilityFeeControllerParams;
 use module_support::DEXManager;
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::Change;
-use sp_runtime::{traits::UniqueSaturatedInto, FixedPointNumber};
+use sp_runtime::{
+	traits::{UniqueSaturatedInto, Zero},
+	FixedPointNumber,
+};
 use sp_std::prelude::*;
 
 const SEED: u32 = 0;
@@ -27,6 +33,7 @@ fn inject_liquidity(
 	set_balance(currency_id, &maker, max_other_currency_amount.unique_saturated_into());
 	set_balance(base_currency_id, &maker, max_amount.unique_saturated_into());
 
+	Dex::enable_trading_pair(RawOrigin::Root.into(), base_currency_id, currency_id)?;
 	Dex::add_liquidity(
 		RawOrigin::Signed(maker.clone()).into(),
 		base_currency_id,
@@ -44,6 +51,18 @@ fn dollar(d: u32) -> Balance {
 	DOLLARS.saturating_mul(d)
 }
 
+fn stability_fee_controller_params() -> StabilityFeeControllerParams<BlockNumber> {
+	StabilityFeeControllerParams {
+		target_price: Price::one(),
+		price_tolerance: Price::saturating_from_rational(1, 100),
+		min_stability_fee: Rate::zero(),
+		max_stability_fee: Rate::saturating_from_rational(10, 1000000000),
+		step: Rate::saturating_from_rational(1, 1000000000),
+		max_change_per_era: Rate::saturating_from_rational(5, 1000000000),
+		era_length: 14400,
+	}
+}
+
 runtime_benchmarks! {
 	{ Runtime, module_cdp_engine }
 
@@ -63,10 +82,65 @@ runtime_benchmarks! {
 	set_global_params {
 	}: _(RawOrigin::Root, Rate::saturating_from_rational(1, 1000000))
 
+	set_partial_liquidation_buffer {
+	}: _(RawOrigin::Root, CurrencyId::Token(TokenSymbol::DOT), Some(Ratio::saturating_from_rational(20, 100)))
+
+	register_keeper {
+		let keeper: AccountId = account("keeper", 0, SEED);
+		set_balance(GetStableCurrencyId::get(), &keeper, KeeperDeposit::get());
+	}: _(RawOrigin::Signed(keeper))
+
+	deregister_keeper {
+		let keeper: AccountId = account("keeper", 0, SEED);
+		set_balance(GetStableCurrencyId::get(), &keeper, KeeperDeposit::get());
+		CdpEngine::register_keeper(RawOrigin::Signed(keeper.clone()).into())?;
+	}: _(RawOrigin::Signed(keeper))
+
+	set_keeper_reward_rate {
+	}: _(RawOrigin::Root, Rate::saturating_from_rational(10, 100))
+
+	set_cross_margin_liquidation_order {
+		let c in 0 .. collateral_currency_ids().len() as u32;
+		let liquidation_order: Vec<CurrencyId> = collateral_currency_ids().into_iter().take(c as usize).collect();
+	}: _(RawOrigin::Root, liquidation_order)
+
+	set_stability_fee_controller {
+	}: _(RawOrigin::Root, Some(stability_fee_controller_params()))
+
+	// `on_initialize`, worst case:
+	// an era with adjustments ends and is recorded, and the global stability fee is adjusted
+	on_initialize {
+		CdpEngine::set_stability_fee_controller(RawOrigin::Root.into(), Some(stability_fee_controller_params()))?;
+		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(GetStableCurrencyId::get(), Price::saturating_from_rational(95, 100))])?;
+		module_cdp_engine::StabilityFeeControllerEra::<Runtime>::put((0, Rate::saturating_from_rational(1, 1000000000)));
+		System::set_block_number(stability_fee_controller_params().era_length);
+	}: {
+		CdpEngine::on_initialize(System::block_number());
+	}
+
+	register_collateral_type {
+	}: _(RawOrigin::Root, CurrencyId::Token(TokenSymbol::ACA))
+
+	pause_collateral_type {
+		let currency_id: CurrencyId = collateral_currency_ids()[0];
+	}: _(RawOrigin::Root, currency_id)
+
+	resume_collateral_type {
+		let currency_id: CurrencyId = collateral_currency_ids()[0];
+		CdpEngine::pause_collateral_type(RawOrigin::Root.into(), currency_id)?;
+	}: _(RawOrigin::Root, currency_id)
+
+	// `retire_collateral_type`, worst case:
+	// the collateral type is in the cross-margin liquidation order
+	retire_collateral_type {
+		let currency_id: CurrencyId = collateral_currency_ids()[0];
+		CdpEngine::set_cross_margin_liquidation_order(RawOrigin::Root.into(), collateral_currency_ids())?;
+	}: _(RawOrigin::Root, currency_id)
+
 	// `liquidate` by_auction
 	liquidate_by_auction {
 		let owner: AccountId = account("owner", 0, SEED);
-		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
+		let currency_id: CurrencyId = collateral_currency_ids()[0];
 		let min_debit_value = MinimumDebitValue::get();
 		let debit_exchange_rate = CdpEngine::get_debit_exchange_rate(currency_id);
 		let collateral_price = Price::one();		// 1 USD
@@ -110,7 +184,7 @@ runtime_benchmarks! {
 	liquidate_by_dex {
 		let owner: AccountId = account("owner", 0, SEED);
 		let funder: AccountId = account("funder", 0, SEED);
-		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
+		let currency_id: CurrencyId = collateral_currency_ids()[0];
 		let min_debit_value = MinimumDebitValue::get();
 		let debit_exchange_rate = CdpEngine::get_debit_exchange_rate(currency_id);
 		let collateral_price = Price::one();		// 1 USD
@@ -161,9 +235,74 @@ runtime_benchmarks! {
 		assert!(base_currency_amount < base_amount_in_dex);
 	}
 
+	// `liquidate` a cross-margin vault with `c` collateral types
+	liquidate_cross_margin_vault {
+		let c in 1 .. collateral_currency_ids().len() as u32;
+		let owner: AccountId = account("owner", 0, SEED);
+		let funder: AccountId = account("funder", 0, SEED);
+		let currency_ids: Vec<CurrencyId> = collateral_currency_ids().into_iter().take(c as usize).collect();
+		let min_debit_value = MinimumDebitValue::get();
+		let collateral_price = Price::one();		// 1 USD
+		let collateral_amount = min_debit_value * 2;
+		let max_slippage_swap_with_dex = MaxSlippageSwapWithDEX::get();
+		let collateral_amount_in_dex = max_slippage_swap_with_dex.reciprocal().unwrap().saturating_mul_int(min_debit_value * 10);
+		let base_amount_in_dex = collateral_amount_in_dex * 2;
+
+		// feed price
+		AcalaOracle::feed_values(
+			RawOrigin::Root.into(),
+			currency_ids.iter().map(|currency_id| (*currency_id, collateral_price)).collect(),
+		)?;
+
+		for currency_id in currency_ids.iter() {
+			let debit_exchange_rate = CdpEngine::get_debit_exchange_rate(*currency_id);
+			let min_debit_amount = debit_exchange_rate.reciprocal().unwrap().saturating_mul_int(min_debit_value);
+			let min_debit_amount: Amount = min_debit_amount.unique_saturated_into();
+
+			inject_liquidity(funder.clone(), *currency_id, base_amount_in_dex, collateral_amount_in_dex)?;
+
+			// set balance
+			set_balance(*currency_id, &owner, collateral_amount.unique_saturated_into());
+
+			// set risk params
+			CdpEngine::set_collateral_params(
+				RawOrigin::Root.into(),
+				*currency_id,
+				Change::NoChange,
+				Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+				Change::NewValue(Some(Rate::saturating_from_rational(10, 100))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+				Change::NewValue(min_debit_value * 100),
+			)?;
+
+			// adjust position
+			CdpEngine::adjust_position(&owner, *currency_id, collateral_amount.try_into().unwrap(), min_debit_amount)?;
+		}
+
+		// put all positions in the cross-margin vault
+		CdpEngine::set_cross_margin_liquidation_order(RawOrigin::Root.into(), currency_ids.clone())?;
+		CdpEngine::set_cross_margin(&owner, true)?;
+
+		// modify liquidation rate to make the vault unsafe
+		for currency_id in currency_ids.iter() {
+			CdpEngine::set_collateral_params(
+				RawOrigin::Root.into(),
+				*currency_id,
+				Change::NoChange,
+				Change::NewValue(Some(Ratio::saturating_from_rational(1000, 100))),
+				Change::NoChange,
+				Change::NoChange,
+				Change::NoChange,
+			)?;
+		}
+	}: liquidate(RawOrigin::None, currency_ids[0], owner.clone())
+	verify {
+		assert!(CdpEngine::processed_cdps().contains(&(currency_ids[0], owner)));
+	}
+
 	settle {
 		let owner: AccountId = account("owner", 0, SEED);
-		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
+		let currency_id: CurrencyId = collateral_currency_ids()[0];
 		let min_debit_value = MinimumDebitValue::get();
 		let debit_exchange_rate = CdpEngine::get_debit_exchange_rate(currency_id);
 		let collateral_price = Price::one();		// 1 USD
@@ -202,10 +341,21 @@ mod tests {
 	use frame_support::assert_ok;
 
 	fn new_test_ext() -> sp_io::TestExternalities {
-		frame_system::GenesisConfig::default()
+		let mut t = frame_system::GenesisConfig::default()
 			.build_storage::<Runtime>()
-			.unwrap()
-			.into()
+			.unwrap();
+
+		module_cdp_engine::GenesisConfig {
+			global_stability_fee: Default::default(),
+			collaterals_params: collateral_currency_ids()
+				.into_iter()
+				.map(|currency_id| (currency_id, None, None, None, None, 0))
+				.collect(),
+		}
+		.assimilate_storage::<Runtime>(&mut t)
+		.unwrap();
+
+		t.into()
 	}
 
 	#[test]
@@ -222,6 +372,83 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn test_set_partial_liquidation_buffer() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_partial_liquidation_buffer());
+		});
+	}
+
+	#[test]
+	fn test_register_keeper() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_register_keeper());
+		});
+	}
+
+	#[test]
+	fn test_deregister_keeper() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_deregister_keeper());
+		});
+	}
+
+	#[test]
+	fn test_set_keeper_reward_rate() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_keeper_reward_rate());
+		});
+	}
+
+	#[test]
+	fn test_set_cross_margin_liquidation_order() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_cross_margin_liquidation_order());
+		});
+	}
+
+	#[test]
+	fn test_set_stability_fee_controller() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_stability_fee_controller());
+		});
+	}
+
+	#[test]
+	fn test_on_initialize() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_on_initialize());
+		});
+	}
+
+	#[test]
+	fn test_register_collateral_type() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_register_collateral_type());
+		});
+	}
+
+	#[test]
+	fn test_pause_collateral_type() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_pause_collateral_type());
+		});
+	}
+
+	#[test]
+	fn test_resume_collateral_type() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_resume_collateral_type());
+		});
+	}
+
+	#[test]
+	fn test_retire_collateral_type() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_retire_collateral_type());
+		});
+	}
+
 	#[test]
 	fn test_liquidate_by_auction() {
 		new_test_ext().execute_with(|| {
@@ -236,6 +463,13 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn test_liquidate_cross_margin_vault() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_liquidate_cross_margin_vault());
+		});
+	}
+
 	#[test]
 	fn test_settle() {
 		new_test_ext().execute_with(|| {
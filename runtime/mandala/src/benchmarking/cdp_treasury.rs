@@ -1,6 +1,8 @@
-use crate::{Balance, CdpTreasury, CollateralCurrencyIds, Currencies, CurrencyId, Runtime, DOLLARS};
+use crate::{Balance, CdpTreasury, Currencies, CurrencyId, Runtime, DOLLARS};
 
+use super::utils::collateral_currency_ids;
 use frame_system::RawOrigin;
+use module_cdp_treasury::{CollateralAuctionType, SurplusStrategy};
 use module_support::CDPTreasury;
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::MultiCurrency;
@@ -25,13 +27,23 @@ runtime_benchmarks! {
 	}: _(RawOrigin::Root,dollar(100), dollar(200))
 
 	auction_collateral {
-		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
+		let currency_id: CurrencyId = collateral_currency_ids()[0];
 		Currencies::deposit(currency_id, &CdpTreasury::account_id(), dollar(10000))?;
 	}: _(RawOrigin::Root, currency_id, dollar(1000), dollar(1000), true)
 
 	set_collateral_auction_maximum_size {
-		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
+		let currency_id: CurrencyId = collateral_currency_ids()[0];
 	}: _(RawOrigin::Root,currency_id, 200)
+
+	set_collateral_auction_type {
+		let currency_id: CurrencyId = collateral_currency_ids()[0];
+	}: _(RawOrigin::Root, currency_id, CollateralAuctionType::Dutch)
+
+	set_surplus_handling_strategy {
+	}: _(RawOrigin::Root, SurplusStrategy::BuybackAndBurn)
+
+	set_surplus_buffer_size {
+	}: _(RawOrigin::Root, dollar(1000))
 }
 
 #[cfg(test)]
@@ -40,10 +52,21 @@ mod tests {
 	use frame_support::assert_ok;
 
 	fn new_test_ext() -> sp_io::TestExternalities {
-		frame_system::GenesisConfig::default()
+		let mut t = frame_system::GenesisConfig::default()
 			.build_storage::<Runtime>()
-			.unwrap()
-			.into()
+			.unwrap();
+
+		module_cdp_engine::GenesisConfig {
+			global_stability_fee: Default::default(),
+			collaterals_params: collateral_currency_ids()
+				.into_iter()
+				.map(|currency_id| (currency_id, None, None, None, None, 0))
+				.collect(),
+		}
+		.assimilate_storage::<Runtime>(&mut t)
+		.unwrap();
+
+		t.into()
 	}
 
 	#[test]
@@ -73,4 +96,25 @@ mod tests {
 			assert_ok!(test_benchmark_set_collateral_auction_maximum_size());
 		});
 	}
+
+	#[test]
+	fn test_set_collateral_auction_type() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_collateral_auction_type());
+		});
+	}
+
+	#[test]
+	fn test_set_surplus_handling_strategy() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_surplus_handling_strategy());
+		});
+	}
+
+	#[test]
+	fn test_set_surplus_buffer_size() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_surplus_buffer_size());
+		});
+	}
 }
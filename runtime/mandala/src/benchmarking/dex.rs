@@ -1,15 +1,26 @@
-use crate::{AccountId, Balance, Currencies, CurrencyId, Dex, EnabledTradingPairs, Runtime, TradingPathLimit};
+use crate::{
+	AccountId, Balance, Call, Currencies, CurrencyId, Dex, GetNativeCurrencyId, LimitOrderDeposit, Ratio, Runtime,
+	System, TokenSymbol, TradingPair, TradingPathLimit,
+};
 
 use super::utils::dollars;
 use frame_benchmarking::account;
 use frame_system::RawOrigin;
+use module_dex::PoolType;
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::MultiCurrencyExtended;
-use sp_runtime::traits::UniqueSaturatedInto;
+use sp_runtime::{traits::UniqueSaturatedInto, FixedPointNumber};
 use sp_std::prelude::*;
 
 const SEED: u32 = 0;
 
+fn trading_pair() -> TradingPair {
+	TradingPair::new(
+		CurrencyId::Token(TokenSymbol::AUSD),
+		CurrencyId::Token(TokenSymbol::DOT),
+	)
+}
+
 fn inject_liquidity(
 	maker: AccountId,
 	currency_id_a: CurrencyId,
@@ -30,6 +41,7 @@ fn inject_liquidity(
 		max_amount_b.unique_saturated_into(),
 	)?;
 
+	Dex::enable_trading_pair(RawOrigin::Root.into(), currency_id_a, currency_id_b)?;
 	Dex::add_liquidity(
 		RawOrigin::Signed(maker.clone()).into(),
 		currency_id_a,
@@ -47,11 +59,96 @@ runtime_benchmarks! {
 
 	_ {}
 
+	enable_trading_pair {
+		let trading_pair = trading_pair();
+	}: _(RawOrigin::Root, trading_pair.0, trading_pair.1)
+
+	disable_trading_pair {
+		let trading_pair = trading_pair();
+		Dex::enable_trading_pair(RawOrigin::Root.into(), trading_pair.0, trading_pair.1)?;
+	}: _(RawOrigin::Root, trading_pair.0, trading_pair.1)
+
+	list_trading_pair {
+		let trading_pair = trading_pair();
+	}: _(RawOrigin::Root, trading_pair.0, trading_pair.1, dollars(1u32), dollars(1u32), dollars(100u32), dollars(100u32), 10)
+
+	add_provision {
+		let founder: AccountId = account("founder", 0, SEED);
+		let trading_pair = trading_pair();
+		Dex::list_trading_pair(RawOrigin::Root.into(), trading_pair.0, trading_pair.1, dollars(1u32), dollars(1u32), dollars(100u32), dollars(100u32), 10)?;
+
+		// set balance
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.0, &founder, dollars(100u32).unique_saturated_into())?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.1, &founder, dollars(100u32).unique_saturated_into())?;
+	}: _(RawOrigin::Signed(founder), trading_pair.0, trading_pair.1, dollars(100u32), dollars(100u32))
+
+	end_provisioning {
+		let founder: AccountId = account("founder", 0, SEED);
+		let caller: AccountId = account("caller", 0, SEED);
+		let trading_pair = trading_pair();
+		Dex::list_trading_pair(RawOrigin::Root.into(), trading_pair.0, trading_pair.1, dollars(1u32), dollars(1u32), dollars(100u32), dollars(100u32), 0)?;
+
+		// founder add provision to reach the target
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.0, &founder, dollars(100u32).unique_saturated_into())?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.1, &founder, dollars(100u32).unique_saturated_into())?;
+		Dex::add_provision(RawOrigin::Signed(founder).into(), trading_pair.0, trading_pair.1, dollars(100u32), dollars(100u32))?;
+	}: _(RawOrigin::Signed(caller), trading_pair.0, trading_pair.1)
+
+	claim_dex_share {
+		let founder: AccountId = account("founder", 0, SEED);
+		let caller: AccountId = account("caller", 0, SEED);
+		let trading_pair = trading_pair();
+		Dex::list_trading_pair(RawOrigin::Root.into(), trading_pair.0, trading_pair.1, dollars(1u32), dollars(1u32), dollars(100u32), dollars(100u32), 0)?;
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.0, &founder, dollars(100u32).unique_saturated_into())?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.1, &founder, dollars(100u32).unique_saturated_into())?;
+		Dex::add_provision(RawOrigin::Signed(founder.clone()).into(), trading_pair.0, trading_pair.1, dollars(100u32), dollars(100u32))?;
+		Dex::end_provisioning(RawOrigin::Signed(caller.clone()).into(), trading_pair.0, trading_pair.1)?;
+	}: _(RawOrigin::Signed(caller), founder, trading_pair.0, trading_pair.1)
+
+	cancel_provisioning {
+		let trading_pair = trading_pair();
+		Dex::list_trading_pair(RawOrigin::Root.into(), trading_pair.0, trading_pair.1, dollars(1u32), dollars(1u32), dollars(100u32), dollars(100u32), 0)?;
+	}: _(RawOrigin::Root, trading_pair.0, trading_pair.1)
+
+	refund_provision {
+		let founder: AccountId = account("founder", 0, SEED);
+		let caller: AccountId = account("caller", 0, SEED);
+		let trading_pair = trading_pair();
+		Dex::list_trading_pair(RawOrigin::Root.into(), trading_pair.0, trading_pair.1, dollars(1u32), dollars(1u32), dollars(100u32), dollars(100u32), 0)?;
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.0, &founder, dollars(100u32).unique_saturated_into())?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.1, &founder, dollars(100u32).unique_saturated_into())?;
+		Dex::add_provision(RawOrigin::Signed(founder.clone()).into(), trading_pair.0, trading_pair.1, dollars(100u32), dollars(100u32))?;
+		Dex::cancel_provisioning(RawOrigin::Root.into(), trading_pair.0, trading_pair.1)?;
+	}: _(RawOrigin::Signed(caller), founder, trading_pair.0, trading_pair.1)
+
+	set_exchange_fee {
+		let trading_pair = trading_pair();
+	}: _(RawOrigin::Root, trading_pair.0, trading_pair.1, Some((3, 1000)))
+
+	set_pool_type {
+		let trading_pair = trading_pair();
+	}: _(RawOrigin::Root, trading_pair.0, trading_pair.1, PoolType::StableSwap(100))
+
+	set_protocol_fee_share {
+	}: _(RawOrigin::Root, Some(Ratio::saturating_from_rational(1, 6)))
+
+	flash_swap {
+		let trading_pair = trading_pair();
+		let maker: AccountId = account("maker", 0, SEED);
+		let borrower: AccountId = account("borrower", 0, SEED);
+		inject_liquidity(maker, trading_pair.0, trading_pair.1, dollars(10000u32), dollars(10000u32), false)?;
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.0, &borrower, dollars(100u32).unique_saturated_into())?;
+		let call = Box::new(Call::System(frame_system::Call::remark(vec![])));
+	}: _(RawOrigin::Signed(borrower), trading_pair.0, trading_pair.1, dollars(1000u32), call)
+
 	// add liquidity but don't staking lp
 	add_liquidity {
 		let first_maker: AccountId = account("first_maker", 0, SEED);
 		let second_maker: AccountId = account("second_maker", 0, SEED);
-		let trading_pair = EnabledTradingPairs::get()[0];
+		let trading_pair = trading_pair();
 		let amount_a = dollars(100u32);
 		let amount_b = dollars(10000u32);
 
@@ -67,7 +164,7 @@ runtime_benchmarks! {
 	add_liquidity_and_deposit {
 		let first_maker: AccountId = account("first_maker", 0, SEED);
 		let second_maker: AccountId = account("second_maker", 0, SEED);
-		let trading_pair = EnabledTradingPairs::get()[0];
+		let trading_pair = trading_pair();
 		let amount_a = dollars(100u32);
 		let amount_b = dollars(10000u32);
 
@@ -79,24 +176,44 @@ runtime_benchmarks! {
 		inject_liquidity(first_maker.clone(), trading_pair.0, trading_pair.1, amount_a, amount_b, true)?;
 	}: add_liquidity(RawOrigin::Signed(second_maker), trading_pair.0, trading_pair.1, amount_a, amount_b, true)
 
+	// add liquidity with single token but don't staking lp
+	add_liquidity_single_token {
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = account("taker", 0, SEED);
+		let trading_pair = trading_pair();
+		inject_liquidity(maker, trading_pair.0, trading_pair.1, dollars(10000u32), dollars(10000u32), false)?;
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.1, &taker, dollars(100u32).unique_saturated_into())?;
+	}: add_liquidity_single_token(RawOrigin::Signed(taker), trading_pair.1, trading_pair.0, dollars(100u32), 0, false)
+
+	// worst: add liquidity with single token and stake lp
+	add_liquidity_single_token_and_deposit {
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = account("taker", 0, SEED);
+		let trading_pair = trading_pair();
+		inject_liquidity(maker, trading_pair.0, trading_pair.1, dollars(10000u32), dollars(10000u32), false)?;
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.1, &taker, dollars(100u32).unique_saturated_into())?;
+	}: add_liquidity_single_token(RawOrigin::Signed(taker), trading_pair.1, trading_pair.0, dollars(100u32), 0, true)
+
 	// remove liquidity by liquid lp share
 	remove_liquidity {
 		let maker: AccountId = account("maker", 0, SEED);
-		let trading_pair = EnabledTradingPairs::get()[0];
+		let trading_pair = trading_pair();
 		inject_liquidity(maker.clone(), trading_pair.0, trading_pair.1, dollars(100u32), dollars(10000u32), false)?;
 	}: remove_liquidity(RawOrigin::Signed(maker), trading_pair.0, trading_pair.1, dollars(50u32).unique_saturated_into(), false)
 
 	// remove liquidity by withdraw staking lp share
 	remove_liquidity_by_withdraw {
 		let maker: AccountId = account("maker", 0, SEED);
-		let trading_pair = EnabledTradingPairs::get()[0];
+		let trading_pair = trading_pair();
 		inject_liquidity(maker.clone(), trading_pair.0, trading_pair.1, dollars(100u32), dollars(10000u32), true)?;
 	}: remove_liquidity(RawOrigin::Signed(maker), trading_pair.0, trading_pair.1, dollars(50u32).unique_saturated_into(), true)
 
 	swap_with_exact_supply {
 		let u in 2 .. TradingPathLimit::get() as u32;
 
-		let trading_pair = EnabledTradingPairs::get()[0];
+		let trading_pair = trading_pair();
 		let mut path: Vec<CurrencyId> = vec![];
 		for i in 1 .. u {
 			if i == 1 {
@@ -121,7 +238,7 @@ runtime_benchmarks! {
 	swap_with_exact_target {
 		let u in 2 .. TradingPathLimit::get() as u32;
 
-		let trading_pair = EnabledTradingPairs::get()[0];
+		let trading_pair = trading_pair();
 		let mut path: Vec<CurrencyId> = vec![];
 		for i in 1 .. u {
 			if i == 1 {
@@ -142,6 +259,83 @@ runtime_benchmarks! {
 
 		<Currencies as MultiCurrencyExtended<_>>::update_balance(path[0], &taker, dollars(10000u32).unique_saturated_into())?;
 	}: swap_with_exact_target(RawOrigin::Signed(taker), path, dollars(10u32), dollars(100u32))
+
+	swap_with_exact_supply_by_best_path {
+		let trading_pair = trading_pair();
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = account("taker", 0, SEED);
+		inject_liquidity(maker, trading_pair.0, trading_pair.1, dollars(10000u32), dollars(10000u32), false)?;
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.0, &taker, dollars(10000u32).unique_saturated_into())?;
+	}: swap_with_exact_supply_by_best_path(RawOrigin::Signed(taker), trading_pair.0, trading_pair.1, dollars(100u32), 0)
+
+	swap_with_exact_target_by_best_path {
+		let trading_pair = trading_pair();
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = account("taker", 0, SEED);
+		inject_liquidity(maker, trading_pair.0, trading_pair.1, dollars(10000u32), dollars(10000u32), false)?;
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.0, &taker, dollars(10000u32).unique_saturated_into())?;
+	}: swap_with_exact_target_by_best_path(RawOrigin::Signed(taker), trading_pair.0, trading_pair.1, dollars(10u32), dollars(100u32))
+	place_limit_order {
+		let trading_pair = trading_pair();
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = account("taker", 0, SEED);
+		inject_liquidity(maker, trading_pair.0, trading_pair.1, dollars(10000u32), dollars(10000u32), false)?;
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.0, &taker, dollars(10000u32).unique_saturated_into())?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(GetNativeCurrencyId::get(), &taker, LimitOrderDeposit::get().unique_saturated_into())?;
+	}: _(RawOrigin::Signed(taker), vec![trading_pair.0, trading_pair.1], dollars(100u32), dollars(100u32), 10)
+
+	cancel_limit_order {
+		let trading_pair = trading_pair();
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = account("taker", 0, SEED);
+		inject_liquidity(maker, trading_pair.0, trading_pair.1, dollars(10000u32), dollars(10000u32), false)?;
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.0, &taker, dollars(10000u32).unique_saturated_into())?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(GetNativeCurrencyId::get(), &taker, LimitOrderDeposit::get().unique_saturated_into())?;
+		Dex::place_limit_order(RawOrigin::Signed(taker.clone()).into(), vec![trading_pair.0, trading_pair.1], dollars(100u32), dollars(100u32), 10)?;
+	}: _(RawOrigin::Signed(taker), 0)
+
+	execute_limit_order {
+		let u in 2 .. TradingPathLimit::get() as u32;
+
+		let trading_pair = trading_pair();
+		let mut path: Vec<CurrencyId> = vec![];
+		for i in 1 .. u {
+			if i == 1 {
+				path.push(trading_pair.0);
+				path.push(trading_pair.1);
+			} else {
+				if i % 2 == 0 {
+					path.push(trading_pair.0);
+				} else {
+					path.push(trading_pair.1);
+				}
+			}
+		}
+
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = account("taker", 0, SEED);
+		inject_liquidity(maker, trading_pair.0, trading_pair.1, dollars(10000u32), dollars(10000u32), false)?;
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(path[0], &taker, dollars(10000u32).unique_saturated_into())?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(GetNativeCurrencyId::get(), &taker, LimitOrderDeposit::get().unique_saturated_into())?;
+		Dex::place_limit_order(RawOrigin::Signed(taker).into(), path, dollars(100u32), 0, 10)?;
+	}: _(RawOrigin::None, 0)
+
+	expire_limit_order {
+		let trading_pair = trading_pair();
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = account("taker", 0, SEED);
+		inject_liquidity(maker, trading_pair.0, trading_pair.1, dollars(10000u32), dollars(10000u32), false)?;
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.0, &taker, dollars(10000u32).unique_saturated_into())?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(GetNativeCurrencyId::get(), &taker, LimitOrderDeposit::get().unique_saturated_into())?;
+		Dex::place_limit_order(RawOrigin::Signed(taker).into(), vec![trading_pair.0, trading_pair.1], dollars(100u32), dollars(100u32), 10)?;
+		System::set_block_number(11);
+	}: _(RawOrigin::None, 0)
 }
 
 #[cfg(test)]
@@ -156,6 +350,90 @@ mod tests {
 			.into()
 	}
 
+	#[test]
+	fn test_enable_trading_pair() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_enable_trading_pair());
+		});
+	}
+
+	#[test]
+	fn test_disable_trading_pair() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_disable_trading_pair());
+		});
+	}
+
+	#[test]
+	fn test_list_trading_pair() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_list_trading_pair());
+		});
+	}
+
+	#[test]
+	fn test_add_provision() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_add_provision());
+		});
+	}
+
+	#[test]
+	fn test_end_provisioning() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_end_provisioning());
+		});
+	}
+
+	#[test]
+	fn test_claim_dex_share() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_claim_dex_share());
+		});
+	}
+
+	#[test]
+	fn test_cancel_provisioning() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_cancel_provisioning());
+		});
+	}
+
+	#[test]
+	fn test_refund_provision() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_refund_provision());
+		});
+	}
+
+	#[test]
+	fn test_set_exchange_fee() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_exchange_fee());
+		});
+	}
+
+	#[test]
+	fn test_set_pool_type() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_pool_type());
+		});
+	}
+
+	#[test]
+	fn test_set_protocol_fee_share() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_protocol_fee_share());
+		});
+	}
+
+	#[test]
+	fn test_flash_swap() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_flash_swap());
+		});
+	}
+
 	#[test]
 	fn test_add_liquidity() {
 		new_test_ext().execute_with(|| {
@@ -170,6 +448,20 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn test_add_liquidity_single_token() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_add_liquidity_single_token());
+		});
+	}
+
+	#[test]
+	fn test_add_liquidity_single_token_and_deposit() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_add_liquidity_single_token_and_deposit());
+		});
+	}
+
 	#[test]
 	fn test_remove_liquidity() {
 		new_test_ext().execute_with(|| {
@@ -197,4 +489,46 @@ mod tests {
 			assert_ok!(test_benchmark_swap_with_exact_target());
 		});
 	}
+
+	#[test]
+	fn test_swap_with_exact_supply_by_best_path() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_swap_with_exact_supply_by_best_path());
+		});
+	}
+
+	#[test]
+	fn test_swap_with_exact_target_by_best_path() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_swap_with_exact_target_by_best_path());
+		});
+	}
+
+	#[test]
+	fn test_place_limit_order() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_place_limit_order());
+		});
+	}
+
+	#[test]
+	fn test_cancel_limit_order() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_cancel_limit_order());
+		});
+	}
+
+	#[test]
+	fn test_execute_limit_order() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_execute_limit_order());
+		});
+	}
+
+	#[test]
+	fn test_expire_limit_order() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_expire_limit_order());
+		});
+	}
 }
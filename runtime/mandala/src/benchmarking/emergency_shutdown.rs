@@ -1,8 +1,6 @@
-use crate::{
-	AcalaOracle, AccountId, Balance, CdpTreasury, CollateralCurrencyIds, EmergencyShutdown, Price, Runtime, DOLLARS,
-};
+use crate::{AcalaOracle, AccountId, Balance, CdpTreasury, EmergencyShutdown, Price, Runtime, DOLLARS};
 
-use super::utils::set_balance;
+use super::utils::{collateral_currency_ids, set_balance};
 use frame_benchmarking::account;
 use frame_system::RawOrigin;
 use module_support::CDPTreasury;
@@ -23,8 +21,8 @@ runtime_benchmarks! {
 	_ {}
 
 	emergency_shutdown {
-		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
-		let currency_ids = CollateralCurrencyIds::get();
+		let c in 0 .. collateral_currency_ids().len().saturating_sub(1) as u32;
+		let currency_ids = collateral_currency_ids();
 		let mut values = vec![];
 
 		for i in 0 .. c {
@@ -38,8 +36,8 @@ runtime_benchmarks! {
 	}: _(RawOrigin::Root)
 
 	refund_collaterals {
-		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
-		let currency_ids = CollateralCurrencyIds::get();
+		let c in 0 .. collateral_currency_ids().len().saturating_sub(1) as u32;
+		let currency_ids = collateral_currency_ids();
 		let funder: AccountId = account("funder", 0, SEED);
 		let caller: AccountId = account("caller", 0, SEED);
 		let mut values = vec![];
@@ -66,10 +64,21 @@ mod tests {
 	use frame_support::assert_ok;
 
 	fn new_test_ext() -> sp_io::TestExternalities {
-		frame_system::GenesisConfig::default()
+		let mut t = frame_system::GenesisConfig::default()
 			.build_storage::<Runtime>()
-			.unwrap()
-			.into()
+			.unwrap();
+
+		module_cdp_engine::GenesisConfig {
+			global_stability_fee: Default::default(),
+			collaterals_params: collateral_currency_ids()
+				.into_iter()
+				.map(|currency_id| (currency_id, None, None, None, None, 0))
+				.collect(),
+		}
+		.assimilate_storage::<Runtime>(&mut t)
+		.unwrap();
+
+		t.into()
 	}
 
 	#[test]
@@ -1,12 +1,13 @@
 use crate::{
-	AcalaOracle, AccountId, Amount, CdpEngine, CollateralCurrencyIds, CurrencyId, ExchangeRate, Honzon,
+	AcalaOracle, AccountId, Amount, Balance, CdpEngine, CurrencyId, Dex, ExchangeRate, GetStableCurrencyId, Honzon,
 	MinimumDebitValue, Price, Rate, Ratio, Runtime, TokenSymbol,
 };
 
-use super::utils::set_balance;
+use super::utils::{collateral_currency_ids, set_balance};
 use core::convert::TryInto;
 use frame_benchmarking::account;
 use frame_system::RawOrigin;
+use module_honzon::DelegatedPermissions;
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::Change;
 use sp_runtime::{
@@ -17,6 +18,31 @@ use sp_std::prelude::*;
 
 const SEED: u32 = 0;
 
+fn inject_liquidity(
+	maker: AccountId,
+	currency_id: CurrencyId,
+	max_amount: Balance,
+	max_other_currency_amount: Balance,
+) -> Result<(), &'static str> {
+	let base_currency_id = GetStableCurrencyId::get();
+
+	// set balance
+	set_balance(currency_id, &maker, max_other_currency_amount);
+	set_balance(base_currency_id, &maker, max_amount);
+
+	Dex::enable_trading_pair(RawOrigin::Root.into(), base_currency_id, currency_id)?;
+	Dex::add_liquidity(
+		RawOrigin::Signed(maker.clone()).into(),
+		base_currency_id,
+		currency_id,
+		max_amount,
+		max_other_currency_amount,
+		false,
+	)?;
+
+	Ok(())
+}
+
 runtime_benchmarks! {
 	{ Runtime, module_honzon }
 
@@ -38,10 +64,10 @@ runtime_benchmarks! {
 	}: _(RawOrigin::Signed(caller), CurrencyId::Token(TokenSymbol::DOT), to)
 
 	unauthorize_all {
-		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
+		let c in 0 .. collateral_currency_ids().len().saturating_sub(1) as u32;
 
 		let caller: AccountId = account("caller", 0, SEED);
-		let currency_ids = CollateralCurrencyIds::get();
+		let currency_ids = collateral_currency_ids();
 		let to: AccountId = account("to", 0, SEED);
 
 		for i in 0 .. c {
@@ -53,11 +79,22 @@ runtime_benchmarks! {
 		}
 	}: _(RawOrigin::Signed(caller))
 
+	// `set_cross_margin_mode`, worst case:
+	// disable the cross-margin mode with `c` collateral types in the vault
+	set_cross_margin_mode {
+		let c in 0 .. collateral_currency_ids().len() as u32;
+
+		let caller: AccountId = account("caller", 0, SEED);
+		let liquidation_order: Vec<CurrencyId> = collateral_currency_ids().into_iter().take(c as usize).collect();
+		CdpEngine::set_cross_margin_liquidation_order(RawOrigin::Root.into(), liquidation_order)?;
+		Honzon::set_cross_margin_mode(RawOrigin::Signed(caller.clone()).into(), true)?;
+	}: _(RawOrigin::Signed(caller), false)
+
 	// `adjust_loan`, best case:
 	// adjust both collateral and debit
 	adjust_loan {
 		let caller: AccountId = account("caller", 0, SEED);
-		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
+		let currency_id: CurrencyId = collateral_currency_ids()[0];
 		let min_debit_value = MinimumDebitValue::get();
 		let debit_exchange_rate = CdpEngine::get_debit_exchange_rate(currency_id);
 		let collateral_price = Price::one();		// 1 USD
@@ -84,8 +121,84 @@ runtime_benchmarks! {
 		)?;
 	}: _(RawOrigin::Signed(caller), currency_id, collateral_amount.try_into().unwrap(), debit_amount)
 
+	close_loan_has_debit_by_dex {
+		let currency_id: CurrencyId = collateral_currency_ids()[0];
+		let sender: AccountId = account("sender", 0, SEED);
+		let maker: AccountId = account("maker", 0, SEED);
+		let min_debit_value = MinimumDebitValue::get();
+		let debit_exchange_rate = CdpEngine::get_debit_exchange_rate(currency_id);
+		let min_debit_amount = debit_exchange_rate.reciprocal().unwrap().saturating_add(ExchangeRate::from_inner(1)).saturating_mul_int(min_debit_value);
+		let min_debit_amount: Amount = min_debit_amount.unique_saturated_into();
+		let debit_amount = min_debit_amount * 10;
+		let collateral_amount: Balance = min_debit_value * 10 * 2;
+
+		// set balance and inject liquidity
+		set_balance(currency_id, &sender, collateral_amount);
+		inject_liquidity(maker, currency_id, min_debit_value * 100, min_debit_value * 100)?;
+
+		// feed price
+		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(currency_id, Price::one())])?;
+
+		// set risk params
+		CdpEngine::set_collateral_params(
+			RawOrigin::Root.into(),
+			currency_id,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(Some(Rate::saturating_from_rational(10, 100))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(min_debit_value * 100),
+		)?;
+
+		// initialize sender's loan
+		Honzon::adjust_loan(
+			RawOrigin::Signed(sender.clone()).into(),
+			currency_id,
+			collateral_amount.try_into().unwrap(),
+			debit_amount,
+		)?;
+	}: _(RawOrigin::Signed(sender), currency_id, collateral_amount)
+
+	expand_position_collateral {
+		let currency_id: CurrencyId = collateral_currency_ids()[0];
+		let sender: AccountId = account("sender", 0, SEED);
+		let maker: AccountId = account("maker", 0, SEED);
+		let min_debit_value = MinimumDebitValue::get();
+		let debit_exchange_rate = CdpEngine::get_debit_exchange_rate(currency_id);
+		let min_debit_amount = debit_exchange_rate.reciprocal().unwrap().saturating_add(ExchangeRate::from_inner(1)).saturating_mul_int(min_debit_value);
+		let min_debit_amount: Amount = min_debit_amount.unique_saturated_into();
+		let debit_amount = min_debit_amount * 10;
+		let collateral_amount: Balance = min_debit_value * 10 * 4;
+
+		// set balance and inject liquidity
+		set_balance(currency_id, &sender, collateral_amount);
+		inject_liquidity(maker, currency_id, min_debit_value * 1000, min_debit_value * 1000)?;
+
+		// feed price
+		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(currency_id, Price::one())])?;
+
+		// set risk params
+		CdpEngine::set_collateral_params(
+			RawOrigin::Root.into(),
+			currency_id,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(Some(Rate::saturating_from_rational(10, 100))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(min_debit_value * 1000),
+		)?;
+
+		// initialize sender's loan
+		Honzon::adjust_loan(
+			RawOrigin::Signed(sender.clone()).into(),
+			currency_id,
+			collateral_amount.try_into().unwrap(),
+			debit_amount,
+		)?;
+	}: _(RawOrigin::Signed(sender), currency_id, Ratio::saturating_from_rational(300, 100), 0)
+
 	transfer_loan_from {
-		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
+		let currency_id: CurrencyId = collateral_currency_ids()[0];
 		let sender: AccountId = account("sender", 0, SEED);
 		let receiver: AccountId = account("receiver", 0, SEED);
 		let min_debit_value = MinimumDebitValue::get();
@@ -127,6 +240,72 @@ runtime_benchmarks! {
 			receiver.clone()
 		)?;
 	}: _(RawOrigin::Signed(receiver), currency_id, sender)
+
+	delegate {
+		let caller: AccountId = account("caller", 0, SEED);
+		let manager: AccountId = account("manager", 0, SEED);
+		let permissions = DelegatedPermissions {
+			adjust_collateral: true,
+			repay: true,
+			borrow_cap: MinimumDebitValue::get() * 100,
+		};
+	}: _(RawOrigin::Signed(caller), CurrencyId::Token(TokenSymbol::DOT), manager, permissions, 100)
+
+	undelegate {
+		let caller: AccountId = account("caller", 0, SEED);
+		let manager: AccountId = account("manager", 0, SEED);
+		Honzon::delegate(
+			RawOrigin::Signed(caller.clone()).into(),
+			CurrencyId::Token(TokenSymbol::DOT),
+			manager.clone(),
+			Default::default(),
+			100,
+		)?;
+	}: _(RawOrigin::Signed(caller), CurrencyId::Token(TokenSymbol::DOT), manager)
+
+	// `adjust_loan_by_delegation`, worst case:
+	// adjust both collateral and debit, and consume the borrow cap
+	adjust_loan_by_delegation {
+		let owner: AccountId = account("owner", 0, SEED);
+		let manager: AccountId = account("manager", 0, SEED);
+		let currency_id: CurrencyId = collateral_currency_ids()[0];
+		let min_debit_value = MinimumDebitValue::get();
+		let debit_exchange_rate = CdpEngine::get_debit_exchange_rate(currency_id);
+		let min_debit_amount = debit_exchange_rate.reciprocal().unwrap().saturating_add(ExchangeRate::from_inner(1)).saturating_mul_int(min_debit_value);
+		let min_debit_amount: Amount = min_debit_amount.unique_saturated_into();
+		let debit_amount = min_debit_amount * 10;
+		let collateral_amount = (min_debit_value * 10 * 2).unique_saturated_into();
+
+		// set balance
+		set_balance(currency_id, &owner, collateral_amount);
+
+		// feed price
+		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(currency_id, Price::one())])?;
+
+		// set risk params
+		CdpEngine::set_collateral_params(
+			RawOrigin::Root.into(),
+			currency_id,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(Some(Rate::saturating_from_rational(10, 100))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(min_debit_value * 100),
+		)?;
+
+		// delegate to manager
+		Honzon::delegate(
+			RawOrigin::Signed(owner.clone()).into(),
+			currency_id,
+			manager.clone(),
+			DelegatedPermissions {
+				adjust_collateral: true,
+				repay: true,
+				borrow_cap: min_debit_value * 100,
+			},
+			100,
+		)?;
+	}: _(RawOrigin::Signed(manager), owner, currency_id, collateral_amount.try_into().unwrap(), debit_amount)
 }
 
 #[cfg(test)]
@@ -135,10 +314,21 @@ mod tests {
 	use frame_support::assert_ok;
 
 	fn new_test_ext() -> sp_io::TestExternalities {
-		frame_system::GenesisConfig::default()
+		let mut t = frame_system::GenesisConfig::default()
 			.build_storage::<Runtime>()
-			.unwrap()
-			.into()
+			.unwrap();
+
+		module_cdp_engine::GenesisConfig {
+			global_stability_fee: Default::default(),
+			collaterals_params: collateral_currency_ids()
+				.into_iter()
+				.map(|currency_id| (currency_id, None, None, None, None, 0))
+				.collect(),
+		}
+		.assimilate_storage::<Runtime>(&mut t)
+		.unwrap();
+
+		t.into()
 	}
 
 	#[test]
@@ -162,10 +352,52 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn test_set_cross_margin_mode() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_cross_margin_mode());
+		});
+	}
+
 	#[test]
 	fn test_adjust_loan() {
 		new_test_ext().execute_with(|| {
 			assert_ok!(test_benchmark_adjust_loan());
 		});
 	}
+
+	#[test]
+	fn test_close_loan_has_debit_by_dex() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_close_loan_has_debit_by_dex());
+		});
+	}
+
+	#[test]
+	fn test_expand_position_collateral() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_expand_position_collateral());
+		});
+	}
+
+	#[test]
+	fn test_delegate() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_delegate());
+		});
+	}
+
+	#[test]
+	fn test_undelegate() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_undelegate());
+		});
+	}
+
+	#[test]
+	fn test_adjust_loan_by_delegation() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_adjust_loan_by_delegation());
+		});
+	}
 }
@@ -1,9 +1,8 @@
 use crate::{
-	AccountId, Balance, CollateralCurrencyIds, CurrencyId, GetStableCurrencyId, Incentives, Rate, Rewards, Runtime,
-	TokenSymbol, DOLLARS,
+	AccountId, Balance, CurrencyId, GetStableCurrencyId, Incentives, Rate, Rewards, Runtime, TokenSymbol, DOLLARS,
 };
 
-use super::utils::set_balance;
+use super::utils::{collateral_currency_ids, set_balance};
 use frame_benchmarking::account;
 use frame_support::storage::StorageMap;
 use frame_system::RawOrigin;
@@ -50,8 +49,8 @@ runtime_benchmarks! {
 	}: _(RawOrigin::Signed(caller), pool_id)
 
 	update_loans_incentive_rewards {
-		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
-		let currency_ids = CollateralCurrencyIds::get();
+		let c in 0 .. collateral_currency_ids().len().saturating_sub(1) as u32;
+		let currency_ids = collateral_currency_ids();
 		let mut values = vec![];
 
 		for i in 0 .. c {
@@ -61,8 +60,8 @@ runtime_benchmarks! {
 	}: _(RawOrigin::Root, values)
 
 	update_dex_incentive_rewards {
-		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
-		let currency_ids = CollateralCurrencyIds::get();
+		let c in 0 .. collateral_currency_ids().len().saturating_sub(1) as u32;
+		let currency_ids = collateral_currency_ids();
 		let caller: AccountId = account("caller", 0, SEED);
 		let mut values = vec![];
 		let base_currency_id = GetStableCurrencyId::get();
@@ -83,8 +82,8 @@ runtime_benchmarks! {
 	}: _(RawOrigin::Root, dollar(100))
 
 	update_dex_saving_rates {
-		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
-		let currency_ids = CollateralCurrencyIds::get();
+		let c in 0 .. collateral_currency_ids().len().saturating_sub(1) as u32;
+		let currency_ids = collateral_currency_ids();
 		let caller: AccountId = account("caller", 0, SEED);
 		let mut values = vec![];
 		let base_currency_id = GetStableCurrencyId::get();
@@ -108,10 +107,21 @@ mod tests {
 	use frame_support::assert_ok;
 
 	fn new_test_ext() -> sp_io::TestExternalities {
-		frame_system::GenesisConfig::default()
+		let mut t = frame_system::GenesisConfig::default()
 			.build_storage::<Runtime>()
-			.unwrap()
-			.into()
+			.unwrap();
+
+		module_cdp_engine::GenesisConfig {
+			global_stability_fee: Default::default(),
+			collaterals_params: collateral_currency_ids()
+				.into_iter()
+				.map(|currency_id| (currency_id, None, None, None, None, 0))
+				.collect(),
+		}
+		.assimilate_storage::<Runtime>(&mut t)
+		.unwrap();
+
+		t.into()
 	}
 
 	#[test]
@@ -1,5 +1,6 @@
-use crate::{AcalaDataProvider, AcalaOracle, CollateralCurrencyIds, FixedPointNumber, Origin, Price, Runtime, System};
+use crate::{AcalaDataProvider, AcalaOracle, FixedPointNumber, Origin, Price, Runtime, System};
 
+use super::utils::collateral_currency_ids;
 use frame_support::traits::OnFinalize;
 use orml_benchmarking::runtime_benchmarks_instance;
 use sp_std::prelude::*;
@@ -11,8 +12,8 @@ runtime_benchmarks_instance! {
 
 	// feed values
 	feed_values {
-		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
-		let currency_ids = CollateralCurrencyIds::get();
+		let c in 0 .. collateral_currency_ids().len().saturating_sub(1) as u32;
+		let currency_ids = collateral_currency_ids();
 		let mut values = vec![];
 
 		for i in 0 .. c {
@@ -21,7 +22,7 @@ runtime_benchmarks_instance! {
 	}: _(Origin::root(), values)
 
 	on_finalize {
-		let currency_ids = CollateralCurrencyIds::get();
+		let currency_ids = collateral_currency_ids();
 		let mut values = vec![];
 
 		for currency_id in currency_ids {
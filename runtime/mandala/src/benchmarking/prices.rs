@@ -1,5 +1,6 @@
-use crate::{AcalaOracle, CollateralCurrencyIds, CurrencyId, Origin, Price, Prices, Runtime, TokenSymbol};
+use crate::{AcalaOracle, CurrencyId, Origin, Price, Prices, Runtime, TokenSymbol};
 
+use super::utils::collateral_currency_ids;
 use frame_system::RawOrigin;
 use orml_benchmarking::runtime_benchmarks;
 use sp_runtime::FixedPointNumber;
@@ -11,14 +12,14 @@ runtime_benchmarks! {
 	_ {}
 
 	lock_price {
-		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
+		let currency_id: CurrencyId = collateral_currency_ids()[0];
 
 		// feed price
 		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(currency_id, Price::one())])?;
 	}: _(RawOrigin::Root, CurrencyId::Token(TokenSymbol::DOT))
 
 	unlock_price {
-		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
+		let currency_id: CurrencyId = collateral_currency_ids()[0];
 
 		// feed price
 		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(currency_id, Price::one())])?;
@@ -1,5 +1,6 @@
-use crate::{AccumulatePeriod, CollateralCurrencyIds, Rewards, Runtime, System};
+use crate::{AccumulatePeriod, Rewards, Runtime, System};
 
+use super::utils::collateral_currency_ids;
 use frame_support::storage::StorageMap;
 use frame_support::traits::OnInitialize;
 use module_incentives::PoolId;
@@ -12,8 +13,8 @@ runtime_benchmarks! {
 	_ {}
 
 	on_initialize {
-		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
-		let currency_ids = CollateralCurrencyIds::get();
+		let c in 0 .. collateral_currency_ids().len().saturating_sub(1) as u32;
+		let currency_ids = collateral_currency_ids();
 		let block_number = AccumulatePeriod::get();
 
 		for i in 0 .. c {
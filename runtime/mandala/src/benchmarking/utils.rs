@@ -6,6 +6,7 @@ use crate::{
 use frame_support::traits::StoredMap;
 use orml_traits::{MultiCurrency, MultiCurrencyExtended};
 use sp_runtime::traits::{SaturatedConversion, StaticLookup};
+use sp_std::prelude::*;
 
 pub fn lookup_of_account(who: AccountId) -> <<Runtime as frame_system::Trait>::Lookup as StaticLookup>::Source {
 	<Runtime as frame_system::Trait>::Lookup::unlookup(who)
@@ -37,3 +38,13 @@ pub fn set_aca_balance(who: &AccountId, balance: Balance) {
 pub fn dollars<T: Into<u128>>(d: T) -> Balance {
 	DOLLARS.saturating_mul(d.into())
 }
+
+/// The collateral types registered in the genesis of dev chain.
+pub fn collateral_currency_ids() -> Vec<CurrencyId> {
+	vec![
+		CurrencyId::Token(TokenSymbol::DOT),
+		CurrencyId::Token(TokenSymbol::XBTC),
+		CurrencyId::Token(TokenSymbol::LDOT),
+		CurrencyId::Token(TokenSymbol::RENBTC),
+	]
+}
@@ -10,14 +10,15 @@ use sp_std::marker::PhantomData;
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Trait> module_cdp_engine::WeightInfo for WeightInfo<T> {
 	fn set_collateral_params() -> Weight {
-		(132_649_000 as Weight)
-			.saturating_add(DbWeight::get().reads(1 as Weight))
-			.saturating_add(DbWeight::get().writes(1 as Weight))
+		(158_917_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
 	}
 	fn set_global_params() -> Weight {
 		(46_103_000 as Weight)
 			.saturating_add((8_000 as Weight).saturating_mul(0 as Weight))
-			.saturating_add(DbWeight::get().writes(1 as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
 	}
 	fn liquidate_by_auction() -> Weight {
 		(843_630_000 as Weight)
@@ -29,9 +30,68 @@ impl<T: frame_system::Trait> module_cdp_engine::WeightInfo for WeightInfo<T> {
 			.saturating_add(DbWeight::get().reads(26 as Weight))
 			.saturating_add(DbWeight::get().writes(15 as Weight))
 	}
+	fn liquidate_cross_margin_vault(c: u32) -> Weight {
+		(173_482_000 as Weight)
+			.saturating_add((812_907_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads(9 as Weight))
+			.saturating_add(DbWeight::get().reads((21 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+			.saturating_add(DbWeight::get().writes((13 as Weight).saturating_mul(c as Weight)))
+	}
 	fn settle() -> Weight {
 		(336_821_000 as Weight)
 			.saturating_add(DbWeight::get().reads(11 as Weight))
 			.saturating_add(DbWeight::get().writes(7 as Weight))
 	}
+	fn set_partial_liquidation_buffer() -> Weight {
+		(45_612_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn register_keeper() -> Weight {
+		(68_427_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn deregister_keeper() -> Weight {
+		(64_051_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn set_keeper_reward_rate() -> Weight {
+		(43_879_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_cross_margin_liquidation_order(c: u32) -> Weight {
+		(42_318_000 as Weight)
+			.saturating_add((1_162_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_stability_fee_controller() -> Weight {
+		(47_236_000 as Weight).saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn on_initialize() -> Weight {
+		(38_542_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn register_collateral_type() -> Weight {
+		(31_274_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn pause_collateral_type() -> Weight {
+		(30_612_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn resume_collateral_type() -> Weight {
+		(30_498_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn retire_collateral_type() -> Weight {
+		(68_407_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+	}
 }
@@ -25,6 +25,24 @@ impl<T: frame_system::Trait> module_cdp_treasury::WeightInfo for WeightInfo<T> {
 			.saturating_add(DbWeight::get().writes(204 as Weight))
 	}
 	fn set_collateral_auction_maximum_size() -> Weight {
-		(54_430_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+		(57_702_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_collateral_auction_type() -> Weight {
+		(56_928_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_surplus_handling_strategy() -> Weight {
+		(28_241_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_surplus_buffer_size() -> Weight {
+		(27_735_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn buyback_with_surplus() -> Weight {
+		(140_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(13 as Weight))
+			.saturating_add(DbWeight::get().writes(9 as Weight))
 	}
 }
@@ -31,6 +31,17 @@ impl<T: frame_system::Trait> module_dex::WeightInfo for WeightInfo<T> {
 				.saturating_add(DbWeight::get().writes(7 as Weight))
 		}
 	}
+	fn add_liquidity_single_token(deposit: bool) -> Weight {
+		if deposit {
+			(536_401_000 as Weight)
+				.saturating_add(DbWeight::get().reads(22 as Weight))
+				.saturating_add(DbWeight::get().writes(15 as Weight))
+		} else {
+			(491_877_000 as Weight)
+				.saturating_add(DbWeight::get().reads(16 as Weight))
+				.saturating_add(DbWeight::get().writes(10 as Weight))
+		}
+	}
 	fn swap_with_exact_supply() -> Weight {
 		(409_297_000 as Weight)
 			.saturating_add(DbWeight::get().reads(9 as Weight))
@@ -41,4 +52,94 @@ impl<T: frame_system::Trait> module_dex::WeightInfo for WeightInfo<T> {
 			.saturating_add(DbWeight::get().reads(9 as Weight))
 			.saturating_add(DbWeight::get().writes(6 as Weight))
 	}
+	fn swap_with_exact_supply_by_best_path() -> Weight {
+		(436_105_000 as Weight)
+			.saturating_add(DbWeight::get().reads(14 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+	}
+	fn swap_with_exact_target_by_best_path() -> Weight {
+		(438_512_000 as Weight)
+			.saturating_add(DbWeight::get().reads(14 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+	}
+	fn enable_trading_pair() -> Weight {
+		(21_346_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn disable_trading_pair() -> Weight {
+		(22_018_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn list_trading_pair() -> Weight {
+		(26_513_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn add_provision() -> Weight {
+		(112_746_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn end_provisioning() -> Weight {
+		(141_907_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(7 as Weight))
+	}
+	fn claim_dex_share() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn cancel_provisioning() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn refund_provision() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn set_exchange_fee() -> Weight {
+		(19_425_000 as Weight)
+			.saturating_add(DbWeight::get().reads(0 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_protocol_fee_share() -> Weight {
+		(17_062_000 as Weight)
+			.saturating_add(DbWeight::get().reads(0 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn place_limit_order() -> Weight {
+		(48_217_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn cancel_limit_order() -> Weight {
+		(35_904_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn execute_limit_order() -> Weight {
+		(437_582_000 as Weight)
+			.saturating_add(DbWeight::get().reads(13 as Weight))
+			.saturating_add(DbWeight::get().writes(10 as Weight))
+	}
+	fn expire_limit_order() -> Weight {
+		(34_671_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn flash_swap() -> Weight {
+		(86_219_000 as Weight)
+			.saturating_add(DbWeight::get().reads(9 as Weight))
+			.saturating_add(DbWeight::get().writes(7 as Weight))
+	}
+	fn set_pool_type() -> Weight {
+		(19_113_000 as Weight)
+			.saturating_add(DbWeight::get().reads(0 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
 }
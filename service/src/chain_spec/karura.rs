@@ -102,11 +102,11 @@ fn karura_genesis(
 ) -> karura_runtime::GenesisConfig {
 	use karura_runtime::{
 		get_all_module_accounts, AcalaOracleConfig, BabeConfig, Balance, BalancesConfig, BandOracleConfig,
-		CdpEngineConfig, CdpTreasuryConfig, ContractsConfig, CurrencyId, GeneralCouncilMembershipConfig, GrandpaConfig,
-		HomaCouncilMembershipConfig, HonzonCouncilMembershipConfig, IndicesConfig, NewAccountDeposit,
+		CdpEngineConfig, CdpTreasuryConfig, ContractsConfig, CurrencyId, DexConfig, GeneralCouncilMembershipConfig,
+		GrandpaConfig, HomaCouncilMembershipConfig, HonzonCouncilMembershipConfig, IndicesConfig, NewAccountDeposit,
 		OperatorMembershipAcalaConfig, OperatorMembershipBandConfig, SessionConfig, StakerStatus, StakingConfig,
 		StakingPoolConfig, SudoConfig, SystemConfig, TechnicalCommitteeMembershipConfig, TokenSymbol, TokensConfig,
-		VestingConfig, CENTS, DOLLARS,
+		TradingPair, VestingConfig, CENTS, DOLLARS,
 	};
 
 	let new_account_deposit = NewAccountDeposit::get();
@@ -213,6 +213,30 @@ fn karura_genesis(
 				(CurrencyId::Token(TokenSymbol::RENBTC), 5 * CENTS),
 			],
 		}),
+		module_dex: Some(DexConfig {
+			initial_enabled_trading_pairs: vec![
+				TradingPair::new(
+					CurrencyId::Token(TokenSymbol::AUSD),
+					CurrencyId::Token(TokenSymbol::DOT),
+				),
+				TradingPair::new(
+					CurrencyId::Token(TokenSymbol::AUSD),
+					CurrencyId::Token(TokenSymbol::XBTC),
+				),
+				TradingPair::new(
+					CurrencyId::Token(TokenSymbol::AUSD),
+					CurrencyId::Token(TokenSymbol::LDOT),
+				),
+				TradingPair::new(
+					CurrencyId::Token(TokenSymbol::AUSD),
+					CurrencyId::Token(TokenSymbol::ACA),
+				),
+				TradingPair::new(
+					CurrencyId::Token(TokenSymbol::AUSD),
+					CurrencyId::Token(TokenSymbol::RENBTC),
+				),
+			],
+		}),
 		module_cdp_engine: Some(CdpEngineConfig {
 			collaterals_params: vec![
 				(
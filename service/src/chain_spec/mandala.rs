@@ -185,11 +185,11 @@ fn testnet_genesis(
 ) -> mandala_runtime::GenesisConfig {
 	use mandala_runtime::{
 		get_all_module_accounts, AcalaOracleConfig, AirDropConfig, BabeConfig, BalancesConfig, BandOracleConfig,
-		CdpEngineConfig, CdpTreasuryConfig, ContractsConfig, CurrencyId, EVMConfig, GeneralCouncilMembershipConfig,
-		GrandpaConfig, HomaCouncilMembershipConfig, HonzonCouncilMembershipConfig, IndicesConfig, NewAccountDeposit,
-		OperatorMembershipAcalaConfig, OperatorMembershipBandConfig, SessionConfig, StakerStatus, StakingConfig,
-		StakingPoolConfig, SudoConfig, SystemConfig, TechnicalCommitteeMembershipConfig, TokenSymbol, TokensConfig,
-		VestingConfig, DOLLARS,
+		CdpEngineConfig, CdpTreasuryConfig, ContractsConfig, CurrencyId, DexConfig, EVMConfig,
+		GeneralCouncilMembershipConfig, GrandpaConfig, HomaCouncilMembershipConfig, HonzonCouncilMembershipConfig,
+		IndicesConfig, NewAccountDeposit, OperatorMembershipAcalaConfig, OperatorMembershipBandConfig, SessionConfig,
+		StakerStatus, StakingConfig, StakingPoolConfig, SudoConfig, SystemConfig, TechnicalCommitteeMembershipConfig,
+		TokenSymbol, TokensConfig, TradingPair, VestingConfig, DOLLARS,
 	};
 
 	let new_account_deposit = NewAccountDeposit::get();
@@ -290,6 +290,30 @@ fn testnet_genesis(
 				(CurrencyId::Token(TokenSymbol::RENBTC), DOLLARS),
 			],
 		}),
+		module_dex: Some(DexConfig {
+			initial_enabled_trading_pairs: vec![
+				TradingPair::new(
+					CurrencyId::Token(TokenSymbol::AUSD),
+					CurrencyId::Token(TokenSymbol::DOT),
+				),
+				TradingPair::new(
+					CurrencyId::Token(TokenSymbol::AUSD),
+					CurrencyId::Token(TokenSymbol::XBTC),
+				),
+				TradingPair::new(
+					CurrencyId::Token(TokenSymbol::AUSD),
+					CurrencyId::Token(TokenSymbol::LDOT),
+				),
+				TradingPair::new(
+					CurrencyId::Token(TokenSymbol::AUSD),
+					CurrencyId::Token(TokenSymbol::ACA),
+				),
+				TradingPair::new(
+					CurrencyId::Token(TokenSymbol::AUSD),
+					CurrencyId::Token(TokenSymbol::RENBTC),
+				),
+			],
+		}),
 		module_cdp_engine: Some(CdpEngineConfig {
 			collaterals_params: vec![
 				(
@@ -362,11 +386,12 @@ fn mandala_genesis(
 ) -> mandala_runtime::GenesisConfig {
 	use mandala_runtime::{
 		get_all_module_accounts, AcalaOracleConfig, AirDropConfig, AirDropCurrencyId, BabeConfig, Balance,
-		BalancesConfig, BandOracleConfig, CdpEngineConfig, CdpTreasuryConfig, ContractsConfig, CurrencyId, EVMConfig,
-		GeneralCouncilMembershipConfig, GrandpaConfig, HomaCouncilMembershipConfig, HonzonCouncilMembershipConfig,
-		IndicesConfig, NewAccountDeposit, OperatorMembershipAcalaConfig, OperatorMembershipBandConfig, SessionConfig,
-		StakerStatus, StakingConfig, StakingPoolConfig, SudoConfig, SystemConfig, TechnicalCommitteeMembershipConfig,
-		TokenSymbol, TokensConfig, VestingConfig, CENTS, DOLLARS,
+		BalancesConfig, BandOracleConfig, CdpEngineConfig, CdpTreasuryConfig, ContractsConfig, CurrencyId, DexConfig,
+		EVMConfig, GeneralCouncilMembershipConfig, GrandpaConfig, HomaCouncilMembershipConfig,
+		HonzonCouncilMembershipConfig, IndicesConfig, NewAccountDeposit, OperatorMembershipAcalaConfig,
+		OperatorMembershipBandConfig, SessionConfig, StakerStatus, StakingConfig, StakingPoolConfig, SudoConfig,
+		SystemConfig, TechnicalCommitteeMembershipConfig, TokenSymbol, TokensConfig, TradingPair, VestingConfig, CENTS,
+		DOLLARS,
 	};
 
 	let new_account_deposit = NewAccountDeposit::get();
@@ -462,6 +487,30 @@ fn mandala_genesis(
 				(CurrencyId::Token(TokenSymbol::RENBTC), 5 * CENTS),
 			],
 		}),
+		module_dex: Some(DexConfig {
+			initial_enabled_trading_pairs: vec![
+				TradingPair::new(
+					CurrencyId::Token(TokenSymbol::AUSD),
+					CurrencyId::Token(TokenSymbol::DOT),
+				),
+				TradingPair::new(
+					CurrencyId::Token(TokenSymbol::AUSD),
+					CurrencyId::Token(TokenSymbol::XBTC),
+				),
+				TradingPair::new(
+					CurrencyId::Token(TokenSymbol::AUSD),
+					CurrencyId::Token(TokenSymbol::LDOT),
+				),
+				TradingPair::new(
+					CurrencyId::Token(TokenSymbol::AUSD),
+					CurrencyId::Token(TokenSymbol::ACA),
+				),
+				TradingPair::new(
+					CurrencyId::Token(TokenSymbol::AUSD),
+					CurrencyId::Token(TokenSymbol::RENBTC),
+				),
+			],
+		}),
 		module_cdp_engine: Some(CdpEngineConfig {
 			collaterals_params: vec![
 				(
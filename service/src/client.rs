@@ -1,6 +1,8 @@
 //! Acala Client abstractions.
 
-use acala_primitives::{AccountId, Balance, Block, BlockNumber, CurrencyId, DataProviderId, Hash, Header, Nonce};
+use acala_primitives::{
+	AccountId, AuctionId, Balance, Block, BlockNumber, CurrencyId, DataProviderId, Hash, Header, Nonce,
+};
 use runtime_common::TimeStampedPrice;
 use sc_client_api::{Backend as BackendT, BlockchainEvents, KeyIterator};
 use sp_api::{CallApiAt, NumberFor, ProvideRuntimeApi};
@@ -26,6 +28,9 @@ pub trait RuntimeApiCollection:
 	+ pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
 	+ orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, TimeStampedPrice>
 	+ module_staking_pool_rpc::StakingPoolRuntimeApi<Block, AccountId, Balance>
+	+ module_dex_rpc::DexRuntimeApi<Block, AccountId, CurrencyId, Balance>
+	+ module_cdp_engine_rpc::CdpEngineRuntimeApi<Block, AccountId, CurrencyId, Balance, BlockNumber>
+	+ module_auction_manager_rpc::AuctionManagerRuntimeApi<Block, AccountId, AuctionId, CurrencyId, Balance, BlockNumber>
 	+ module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block>
 	+ sp_api::Metadata<Block>
 	+ sp_offchain::OffchainWorkerApi<Block>
@@ -47,7 +52,16 @@ where
 		+ pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
 		+ orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, TimeStampedPrice>
 		+ module_staking_pool_rpc::StakingPoolRuntimeApi<Block, AccountId, Balance>
-		+ module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block>
+		+ module_dex_rpc::DexRuntimeApi<Block, AccountId, CurrencyId, Balance>
+		+ module_cdp_engine_rpc::CdpEngineRuntimeApi<Block, AccountId, CurrencyId, Balance, BlockNumber>
+		+ module_auction_manager_rpc::AuctionManagerRuntimeApi<
+			Block,
+			AccountId,
+			AuctionId,
+			CurrencyId,
+			Balance,
+			BlockNumber,
+		> + module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block>
 		+ sp_api::Metadata<Block>
 		+ sp_offchain::OffchainWorkerApi<Block>
 		+ sp_session::SessionKeys<Block>,